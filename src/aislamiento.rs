@@ -0,0 +1,278 @@
+/* Ejecutar todas las lecciones en el mismo proceso tiene un problema:
+ * con panic = 'abort' en el Cargo.toml (y en general con cualquier
+ * panic) la primera lección que falla se lleva a todas las demás. Aquí
+ * cada lección se lanza como un proceso hijo del mismo binario, se
+ * guarda lo que imprimió, cómo terminó y, si hubo, el mensaje del panic.
+ * Un ciclo infinito tampoco debe colgar a las demás: el hijo que tarda
+ * más de TIEMPO_MAXIMO se mata y cuenta como tiempo agotado
+ */
+
+use std::env;
+use std::ffi::OsString;
+use std::io::{self, Read};
+use std::panic;
+use std::process::{self, Child, Command, ExitStatus, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::entrada;
+use crate::lecciones::Leccion;
+
+// Variable de entorno con la que el padre le avisa al hijo que está aislado
+const VARIABLE_HIJO: &str = "RUST_NOTAS_AISLADA";
+// Prefijo de la línea de stderr con la que el hijo reporta su panic
+const MARCA_PANICO: &str = "@@panico@@ ";
+// Código de salida del hijo cuando un caso llegó a un sin_resolver!()
+const CODIGO_SIN_RESOLVER: i32 = 3;
+// Ninguna lección tarda ni un segundo; un hijo que pasa de esto está atorado
+pub const TIEMPO_MAXIMO: Duration = Duration::from_secs(10);
+// Cada cuánto se revisa si el hijo ya terminó
+const INTERVALO: Duration = Duration::from_millis(10);
+
+pub struct Resultado {
+    pub leccion: &'static str,
+    pub stdout: String,
+    pub stderr: String,
+    pub estado: ExitStatus,
+    pub panico: Option<String>,
+    pub tiempo_agotado: bool,
+}
+
+impl Resultado {
+    pub fn exito(&self) -> bool {
+        !self.tiempo_agotado && self.estado.success()
+    }
+
+    pub fn sin_resolver(&self) -> bool {
+        self.estado.code() == Some(CODIGO_SIN_RESOLVER)
+    }
+
+    pub fn descripcion_estado(&self) -> String {
+        if self.exito() {
+            return String::from("ok");
+        }
+        if self.tiempo_agotado {
+            return String::from("tiempo agotado");
+        }
+        if self.panico.is_some() {
+            return String::from("pánico");
+        }
+        match self.estado.code() {
+            Some(codigo) => format!("error ({codigo})"),
+            None => describir_senal(&self.estado),
+        }
+    }
+}
+
+#[cfg(unix)]
+fn describir_senal(estado: &ExitStatus) -> String {
+    use std::os::unix::process::ExitStatusExt;
+    match estado.signal() {
+        Some(senal) => format!("señal {senal}"),
+        None => String::from("desconocido"),
+    }
+}
+
+#[cfg(not(unix))]
+fn describir_senal(_estado: &ExitStatus) -> String {
+    String::from("desconocido")
+}
+
+pub fn es_hijo() -> bool {
+    env::var_os(VARIABLE_HIJO).is_some()
+}
+
+/* Lo que hace sin_resolver!(). En un hijo termina sin panic y con un
+ * código que el padre reconoce; con panic = 'abort' en release no se
+ * podría atrapar un panic, así que no hay otra forma de avisarle. Fuera
+ * de un hijo se porta como todo!()
+ */
+pub fn terminar_sin_resolver(lugar: &str) -> ! {
+    if es_hijo() {
+        process::exit(CODIGO_SIN_RESOLVER);
+    }
+    panic!("falta resolver el ejercicio de {lugar}")
+}
+
+// Se llama al inicio del programa, sólo hace algo si somos un hijo
+pub fn preparar_hijo() {
+    if !es_hijo() {
+        return;
+    }
+
+    // Se conserva el mensaje de siempre y además se deja una línea
+    // fácil de encontrar para el padre
+    let gancho_original = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        gancho_original(info);
+        let mensaje = if let Some(texto) = info.payload().downcast_ref::<&str>() {
+            texto.to_string()
+        } else if let Some(texto) = info.payload().downcast_ref::<String>() {
+            texto.clone()
+        } else {
+            String::from("(panic sin mensaje)")
+        };
+        let mensaje = mensaje.replace('\n', " ");
+        match info.location() {
+            Some(lugar) => eprintln!("{MARCA_PANICO}{mensaje} ({lugar})"),
+            None => eprintln!("{MARCA_PANICO}{mensaje}"),
+        }
+    }));
+}
+
+pub fn ejecutar_aislada(leccion: &Leccion) -> io::Result<Resultado> {
+    let mut args = vec![OsString::from("run"), OsString::from(leccion.nombre)];
+    // Las lecciones interactivas leen sus respuestas de su guion
+    if let Some(guion) = entrada::guion_de(leccion.nombre) {
+        args.push(OsString::from("--entrada"));
+        args.push(guion.into_os_string());
+    }
+    lanzar(leccion.nombre, &args)
+}
+
+// Un caso de prueba de un ejercicio, ver ejercicios/mod.rs
+pub fn ejecutar_caso_aislado(leccion: &Leccion, ejercicio: &str, caso: usize) -> io::Result<Resultado> {
+    let args = ["__caso", leccion.nombre, ejercicio, &caso.to_string()].map(OsString::from);
+    lanzar(leccion.nombre, &args)
+}
+
+fn lanzar(leccion: &'static str, args: &[OsString]) -> io::Result<Resultado> {
+    let hijo = Command::new(env::current_exe()?)
+        .args(args)
+        .env(VARIABLE_HIJO, "1")
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    let salida = esperar(hijo, TIEMPO_MAXIMO)?;
+
+    let panico = salida
+        .stderr
+        .lines()
+        .find_map(|linea| linea.strip_prefix(MARCA_PANICO))
+        .map(str::to_string);
+
+    Ok(Resultado {
+        leccion,
+        stdout: salida.stdout,
+        stderr: salida.stderr,
+        estado: salida.estado,
+        panico,
+        tiempo_agotado: salida.tiempo_agotado,
+    })
+}
+
+struct Salida {
+    stdout: String,
+    stderr: String,
+    estado: ExitStatus,
+    tiempo_agotado: bool,
+}
+
+/* Como Command::output() pero con límite de tiempo. stdout y stderr se
+ * leen en otros hilos mientras tanto: si el hijo llena el buffer del
+ * pipe y nadie lo lee se queda esperando y parecería atorado
+ */
+fn esperar(mut hijo: Child, limite: Duration) -> io::Result<Salida> {
+    let stdout = leer_en_otro_hilo(hijo.stdout.take());
+    let stderr = leer_en_otro_hilo(hijo.stderr.take());
+
+    let inicio = Instant::now();
+    let mut tiempo_agotado = false;
+    let estado = loop {
+        if let Some(estado) = hijo.try_wait()? {
+            break estado;
+        }
+        if inicio.elapsed() >= limite {
+            tiempo_agotado = true;
+            // Si terminó justo entre try_wait y kill, kill falla y da igual
+            let _ = hijo.kill();
+            break hijo.wait()?;
+        }
+        thread::sleep(INTERVALO);
+    };
+
+    Ok(Salida {
+        stdout: stdout.join().unwrap_or_default(),
+        stderr: stderr.join().unwrap_or_default(),
+        estado,
+        tiempo_agotado,
+    })
+}
+
+fn leer_en_otro_hilo(fuente: Option<impl Read + Send + 'static>) -> thread::JoinHandle<String> {
+    thread::spawn(move || {
+        let mut bytes = Vec::new();
+        if let Some(mut fuente) = fuente {
+            let _ = fuente.read_to_end(&mut bytes);
+        }
+        String::from_utf8_lossy(&bytes).into_owned()
+    })
+}
+
+pub fn imprimir_resumen(resultados: &[Resultado]) {
+    let ancho_nombre = resultados
+        .iter()
+        .map(|resultado| resultado.leccion.len())
+        .max()
+        .unwrap_or(0)
+        .max("Lección".chars().count());
+    let estados: Vec<String> = resultados.iter().map(Resultado::descripcion_estado).collect();
+    let ancho_estado = estados
+        .iter()
+        .map(|estado| estado.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max("Estado".len());
+
+    println!("{:<ancho_nombre$}  {:<ancho_estado$}  Detalle", "Lección", "Estado");
+    println!("{}  {}  {}", "-".repeat(ancho_nombre), "-".repeat(ancho_estado), "-".repeat(7));
+    for (resultado, estado) in resultados.iter().zip(&estados) {
+        // Si no hubo panic, la última línea de stderr suele explicar el error
+        let detalle = match &resultado.panico {
+            Some(panico) => panico.clone(),
+            None if resultado.tiempo_agotado => {
+                format!("se detuvo después de {} s", TIEMPO_MAXIMO.as_secs())
+            }
+            None if !resultado.exito() => resultado.stderr.lines().last().unwrap_or("").to_string(),
+            None => String::new(),
+        };
+        println!("{:<ancho_nombre$}  {:<ancho_estado$}  {detalle}", resultado.leccion, estado);
+    }
+
+    let exitosas = resultados.iter().filter(|resultado| resultado.exito()).count();
+    println!("\n{exitosas} de {} lecciones terminaron bien", resultados.len());
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    fn esperar_comando(programa: &str, args: &[&str], limite: Duration) -> Salida {
+        let hijo = Command::new(programa)
+            .args(args)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .unwrap();
+        esperar(hijo, limite).unwrap()
+    }
+
+    #[test]
+    fn un_hijo_que_no_termina_se_mata() {
+        let inicio = Instant::now();
+        let salida = esperar_comando("sleep", &["30"], Duration::from_millis(200));
+        assert!(salida.tiempo_agotado);
+        assert!(!salida.estado.success());
+        assert!(inicio.elapsed() < Duration::from_secs(10));
+    }
+
+    #[test]
+    fn un_hijo_rapido_termina_normal() {
+        let salida = esperar_comando("sh", &["-c", "echo hola; echo adios >&2"], TIEMPO_MAXIMO);
+        assert!(!salida.tiempo_agotado);
+        assert!(salida.estado.success());
+        assert_eq!(salida.stdout, "hola\n");
+        assert_eq!(salida.stderr, "adios\n");
+    }
+}
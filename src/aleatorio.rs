@@ -0,0 +1,72 @@
+/* Números pseudoaleatorios con semilla, sin depender de ningún crate.
+ * Con la misma semilla siempre sale la misma secuencia, que es lo que
+ * se quiere para repetir un quiz o una simulación. Es SplitMix64: muy
+ * simple y suficiente para barajar o simular, no para criptografía
+ */
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub struct Aleatorio {
+    estado: u64,
+}
+
+impl Aleatorio {
+    pub fn new(semilla: u64) -> Self {
+        Self { estado: semilla }
+    }
+
+    // Una semilla distinta en cada ejecución, a partir de la hora
+    pub fn semilla_del_reloj() -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duracion| duracion.as_nanos() as u64)
+            .unwrap_or(0)
+    }
+
+    pub fn siguiente(&mut self) -> u64 {
+        self.estado = self.estado.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.estado;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /* Un número en 0..limite. Se descartan los valores de la parte
+     * "incompleta" del rango para que todos tengan la misma probabilidad
+     */
+    pub fn hasta(&mut self, limite: u64) -> u64 {
+        assert!(limite > 0, "el límite debe ser mayor a 0");
+        let zona = u64::MAX - u64::MAX % limite;
+        loop {
+            let valor = self.siguiente();
+            if valor < zona {
+                return valor % limite;
+            }
+        }
+    }
+
+    // Un decimal en [0, 1), con los 53 bits que caben en la mantisa de un f64
+    pub fn decimal(&mut self) -> f64 {
+        (self.siguiente() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /* Un valor con distribución normal (la campana), con Box-Muller: dos
+     * uniformes se vuelven una normal. Sirve para simular errores de
+     * medición o de fabricación, que casi siempre quedan cerca de la media
+     */
+    pub fn normal(&mut self, media: f64, desviacion: f64) -> f64 {
+        // 1 - decimal está en (0, 1] y así ln nunca recibe 0
+        let u1 = 1.0 - self.decimal();
+        let u2 = self.decimal();
+        let z = (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos();
+        media + desviacion * z
+    }
+
+    // Fisher-Yates: cada orden posible tiene la misma probabilidad
+    pub fn barajar<T>(&mut self, elementos: &mut [T]) {
+        for i in (1..elementos.len()).rev() {
+            let j = self.hasta(i as u64 + 1) as usize;
+            elementos.swap(i, j);
+        }
+    }
+}
@@ -21,6 +21,48 @@
  * Para más detalles, la documentación: https://doc.rust-lang.org/std/collections/index.html
  */
 
+use crate::ejercicios::{self, caso, Ejercicio};
+use crate::lecciones::{Entrada, Leccion};
+use crate::mensajes::mensaje;
+use crate::operaciones::matriz::Matriz;
+
+pub const LECCION: Leccion = Leccion {
+    nombre: "collections",
+    titulo: "Colecciones",
+    orden: 10,
+    entrada: Entrada::Simple(main),
+    ejercicios: EJERCICIOS,
+    preguntas: "",
+    fuente: include_str!("collections.rs"),
+};
+
+const EJERCICIOS: &[Ejercicio] = &[
+    Ejercicio {
+        nombre: "pares",
+        enunciado: "Devuelve un vector nuevo sólo con los números pares, en el mismo orden",
+        firma: "fn pares(numeros: &[i32]) -> Vec<i32>",
+        casos: || {
+            vec![
+                caso("pares(&[1, 2, 3, 4])", || ejercicios::collections::pares(&[1, 2, 3, 4]), vec![2, 4]),
+                caso("pares(&[])", || ejercicios::collections::pares(&[]), vec![]),
+                caso("pares(&[-2, 7, 0])", || ejercicios::collections::pares(&[-2, 7, 0]), vec![-2, 0]),
+            ]
+        },
+    },
+    Ejercicio {
+        nombre: "elemento",
+        enunciado: "Devuelve el elemento en una posición o None si no existe, sin provocar un panic",
+        firma: "fn elemento(numeros: &[i32], posicion: usize) -> Option<i32>",
+        casos: || {
+            vec![
+                caso("elemento(&[12, 23, 1], 1)", || ejercicios::collections::elemento(&[12, 23, 1], 1), Some(23)),
+                caso("elemento(&[12, 23, 1], 3)", || ejercicios::collections::elemento(&[12, 23, 1], 3), None),
+                caso("elemento(&[], 0)", || ejercicios::collections::elemento(&[], 0), None),
+            ]
+        },
+    },
+];
+
 pub fn main() {
     /* Vect<T>: Permite almacenar datos de un mismo tipo uno
      * tras de otro y cambiar su tamaño en tiempo de ejecución
@@ -29,23 +71,24 @@ pub fn main() {
 
     // Inicializar vacío
     let mut _v: Vec<i32> = Vec::new();
-    println!("Vector: {:?}", _v);
+    println!("{}", mensaje!("collections.vector", format!("{:?}", _v)));
 
     _v.push(1);
     _v.push(2);
     _v.push(3);
-    println!("Vector: {:?}", _v);
+    println!("{}", mensaje!("collections.vector", format!("{:?}", _v)));
 
-    //inicializar con datos
+    //inicializar con datos, aquí lo que importa es el macro vec!
+    #[allow(clippy::useless_vec)]
     let _v = vec![1, 2, 3];
 
-    println!("\nObtener datos de un vector");
+    println!("{}", mensaje!("collections.obtener_datos"));
     /* Obtener datos de un vector
      * De esta forma es insegura, pues podemos tratar de acceder
      * a un indice no existente, y obtendremos un "main panicjed at"
      */
     let third: &i32 = &_v[2];
-    println!("The third element is {third}");
+    println!("{}", mensaje!("collections.tercer_elemento", third));
 
     /* Una forma más apropiada de manejar el caso en el que no exista
      * El método get() devuelve un Option<T> y permite manejar el caso
@@ -53,33 +96,35 @@ pub fn main() {
      */
     let third: Option<&i32> = _v.get(3);
     match third {
-        Some(third) => println!("The third element is {third}"),
-        None => println!("There is no third element."),
+        Some(third) => println!("{}", mensaje!("collections.tercer_elemento", third)),
+        None => println!("{}", mensaje!("collections.sin_tercer_elemento")),
     }
 
-    println!("\nFor en un vector");
+    println!("{}", mensaje!("collections.for_en_vector"));
     let mut vector = vec![12, 23, 1];
-    println!("Vector {:?}\n", vector);
+    println!("{}", mensaje!("collections.vector_completo", format!("{:?}", vector)));
     let mut i = 1;
     for element in &vector {
-        println!("Elemento {}: {}", i, element);
+        println!("{}", mensaje!("collections.elemento", i, element));
         i += 1;
     }
 
-    println!("\nCambiar valores en un for: ");
+    println!("{}", mensaje!("collections.cambiar_en_for"));
     i = 1;
     for elemento in &mut vector {
         // Elemento es un puntero al elemento en el
         // vector y para cambiarlo accedemos al contenido
         // en el puntero
         *elemento = i;
-        println!("Elemento {}: {}", i, elemento);
+        println!("{}", mensaje!("collections.elemento", i, elemento));
         i += 1;
     }
 
     // Podemos almacenar varios datos en un vector si usamos un enum
-    println!("\nDiferentes tipos de dato en un vector");
+    println!("{}", mensaje!("collections.varios_tipos"));
     let _vector: Vec<VariosTipos> = Vec::new();
+    // 3.1416 es un flotante cualquiera, no se busca PI
+    #[allow(clippy::approx_constant)]
     let _vector = vec![
         VariosTipos::Int(3),
         VariosTipos::Float(3.1416),
@@ -97,20 +142,16 @@ pub fn main() {
     // Al igual que cualquier otra variable, cuando deja de usarse se
     // libera
 
-    // Por si debiera usar un """array""" bidimensional dinámico por alguna wea (seguramente deba
-    // hacerse refactorización)
-    let mut votos: Vec<Vec<usize>> = Vec::new();
+    // Para un """array""" bidimensional dinámico está Matriz
+    // (src/operaciones/matriz.rs): guarda todo en un solo Vec, fila tras
+    // fila, en vez de un Vec por cada fila como Vec<Vec<usize>>
     let tope = 10;
+    // Se inicializa con valores predeterminados (por ejemplo, ceros)
+    let votos: Matriz<usize> = Matriz::ceros(tope, tope);
 
-    // Inicializar el arreglo bidimensional con valores predeterminados (por ejemplo, ceros)
-    for _ in 0..tope {
-        let fila = vec![0; tope];
-        votos.push(fila);
-    }
-
-    for municipio in &votos {
+    for municipio in votos.iter_filas() {
         for _candidato in municipio {
-            //aquí se accede al dato dentro del vector que está dentro del vector xd
+            // cada fila es un slice, y votos[(municipio, candidato)] da un dato
         }
     }
 }
@@ -124,9 +165,9 @@ enum VariosTipos {
 impl VariosTipos {
     fn obtener_datos(&self) {
         match self {
-            VariosTipos::Int(dato) => println!("El entero almacenado es: {}", dato),
-            VariosTipos::Float(dato) => println!("El flotante almacenado es: {}", dato),
-            VariosTipos::Text(dato) => println!("El texto almacenado es: {}", dato),
+            VariosTipos::Int(dato) => println!("{}", mensaje!("collections.entero", dato)),
+            VariosTipos::Float(dato) => println!("{}", mensaje!("collections.flotante", dato)),
+            VariosTipos::Text(dato) => println!("{}", mensaje!("collections.texto", dato)),
         }
     }
 }
@@ -49,6 +49,8 @@ fn _func_panic() {
     panic!("Crash and burn");
 }
 
+// vector[10] es el panic que se quiere ver, no importa que no haga nada más
+#[allow(clippy::no_effect, clippy::useless_vec)]
 fn _func_panic2() {
     /* Si estuvieramos en C, obtendríamos el resultado de lo que fuera que
      * estuviera en la memoria, así no le corresponda al array, o vector
@@ -122,7 +124,9 @@ fn _crear_un_archivo() {
      */
     let archivo = File::open("hola.txt");
 
-    // Por ownership match liberaría la memoria de la variable
+    // Por ownership match liberaría la memoria de la variable. El archivo
+    // abierto no se usa, lo que importa es el match
+    #[allow(unused_variables)]
     let archivo = match archivo {
         // Obtenemos el T en Ok<T>
         Ok(archivo) => archivo,
@@ -138,6 +142,7 @@ fn _crear_un_archivo2() {
     let archivo = File::open("hola.txt");
 
     // Por ownership match liberaría la memoria de la variable
+    #[allow(unused_variables)]
     let archivo = match archivo {
         // Obtenemos el T en Ok<T>
         Ok(archivo) => archivo,
@@ -192,6 +197,8 @@ fn _crear_un_archivo4() {
  */
 
 fn _crear_un_archivo5() {
+    // Sólo importa el mensaje de expect, el archivo no se lee
+    #[allow(unused_variables)]
     let greeting_file =
         File::open("hello.txt").expect("hello.txt should be included in this project");
 }
@@ -213,17 +220,19 @@ fn _crear_un_archivo5() {
  * que permiten pasar un bloque de código como argumento y en caso de
  * unwrap_or_else nos permiten manejar el error e incluso retornar un valor
  */
+// maybe_value es None a propósito para que se llame el closure
+#[allow(clippy::unnecessary_literal_unwrap)]
 fn _unwrap_or_else_prueba() {
     let maybe_value: Option<i32> = None;
 
     // unwrap_or_else se usa para proporcionar un valor predeterminado en caso de None
     let result = maybe_value.unwrap_or_else(|| {
-        println!("No hay valor, proporcionando uno predeterminado");
+        println!("{}", mensaje!("control_de_errores.sin_valor"));
         32
     });
 
-    println!("Resultado: {}", result);
-    println!("Maybe_Value: {:?}", maybe_value);
+    println!("{}", mensaje!("control_de_errores.resultado", result));
+    println!("{}", mensaje!("control_de_errores.maybe_value", format!("{:?}", maybe_value)));
 }
 
 /* Propagando errores
@@ -237,6 +246,7 @@ fn _unwrap_or_else_prueba() {
 use std::io::{self, Read};
 
 // Esta función puede refactorizarse pero se deja así por fines de la enseñanza
+#[allow(clippy::question_mark)]
 fn _read_username_from_file() -> Result<String, io::Error> {
     let username_file_result = File::open("hello.txt");
 
@@ -300,14 +310,17 @@ fn _read_username_from_file2() -> Result<String, io::Error> {
 
 //use std::io;
 
-// Definición del tipo de error personalizado
+// Definición del tipo de error personalizado. Sólo la crea el From de
+// abajo, que usa _read_username_from_file3 y nadie llama
 #[derive(Debug)]
+#[allow(dead_code)]
 struct OurError {
     _message: String,
 }
 
 // Implementación de From para convertir io::Error a OurError
 impl From<io::Error> for OurError {
+    #[allow(clippy::useless_format)]
     fn from(_error: io::Error) -> Self {
         OurError {
             _message: format!("Ocurrió un error personalizado"),
@@ -409,6 +422,19 @@ fn _last_char_of_first_line2(text: &str) -> Option<char> {
     text.lines().next()?.chars().last()
 }
 
+use crate::lecciones::{Entrada, Leccion};
+use crate::mensajes::mensaje;
+
+pub const LECCION: Leccion = Leccion {
+    nombre: "control_de_errores",
+    titulo: "Control de errores",
+    orden: 12,
+    entrada: Entrada::ConError(main),
+    ejercicios: &[],
+    preguntas: "",
+    fuente: include_str!("control_de_errores.rs"),
+};
+
 // Caso error en main, significa, retorna "unit" () o el error
 // Chatgpt:
 /* El tipo () se utiliza principalmente para representar la ausencia de valor o la falta de
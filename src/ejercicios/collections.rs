@@ -0,0 +1,14 @@
+/* Ejercicios de la lección collections. Reemplaza cada sin_resolver!() por tu
+ * solución y comprueba con: cargo run -- check collections
+ */
+
+// Un vector nuevo sólo con los números pares, en el mismo orden
+pub fn pares(numeros: &[i32]) -> Vec<i32> {
+    sin_resolver!()
+}
+
+// El elemento en la posición dada o None si no existe, sin usar [] para
+// que no pueda haber un panic
+pub fn elemento(numeros: &[i32], posicion: usize) -> Option<i32> {
+    sin_resolver!()
+}
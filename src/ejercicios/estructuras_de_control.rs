@@ -0,0 +1,10 @@
+/* Ejercicios de la lección estructuras_de_control. Reemplaza el
+ * sin_resolver!() por tu solución y comprueba con:
+ * cargo run -- check estructuras_de_control
+ */
+
+// "Fizz" si n es divisible entre 3, "Buzz" si lo es entre 5, "FizzBuzz"
+// si lo es entre ambos y el número como texto en cualquier otro caso
+pub fn fizzbuzz(n: u32) -> String {
+    sin_resolver!()
+}
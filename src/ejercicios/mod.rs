@@ -0,0 +1,231 @@
+/* Ejercicios para practicar lo de cada lección. Cada lección puede
+ * declarar los suyos en su constante LECCION: un enunciado, la firma
+ * de la función que hay que escribir en src/ejercicios/<leccion>.rs y
+ * unos casos de prueba que no se ven desde ese archivo. Se califican con
+ * cargo run -- check <leccion>, que corre cada caso en su propio proceso
+ * para que un panic sólo cuente como un caso fallido. Las funciones
+ * vienen con sin_resolver!(), que check cuenta aparte como pendiente
+ */
+
+use std::fmt::Debug;
+use std::io;
+
+use crate::aislamiento::{self, Resultado};
+use crate::lecciones::Leccion;
+
+/* Va en lugar de la solución mientras no se escriba. Como todo!(), pero
+ * check lo reporta como "sin resolver" en vez de como un panic. Se
+ * define antes de los mod para que los archivos de ejercicios lo vean
+ */
+macro_rules! sin_resolver {
+    () => {
+        $crate::aislamiento::terminar_sin_resolver(concat!(file!(), ":", line!()))
+    };
+}
+
+// Mientras las funciones tengan sin_resolver!() sus parámetros no se usan
+#[allow(unused_variables)]
+pub mod collections;
+#[allow(unused_variables)]
+pub mod estructuras_de_control;
+#[allow(unused_variables)]
+pub mod option;
+
+pub struct Ejercicio {
+    pub nombre: &'static str,
+    pub enunciado: &'static str,
+    // La firma que debe tener la función en el archivo de ejercicios
+    pub firma: &'static str,
+    pub casos: fn() -> Vec<Caso>,
+}
+
+pub struct Caso {
+    pub descripcion: String,
+    pub probar: Box<dyn Fn() -> Result<(), String>>,
+}
+
+// Un caso que compara lo que devuelve la función con lo esperado
+pub fn caso<T, F>(descripcion: &str, funcion: F, esperado: T) -> Caso
+where
+    T: PartialEq + Debug + 'static,
+    F: Fn() -> T + 'static,
+{
+    Caso {
+        descripcion: descripcion.to_string(),
+        probar: Box::new(move || {
+            let obtenido = funcion();
+            if obtenido == esperado {
+                Ok(())
+            } else {
+                Err(format!("se esperaba {esperado:?} y se obtuvo {obtenido:?}"))
+            }
+        }),
+    }
+}
+
+/* Lo que hace el proceso hijo: corre un único caso y termina con error
+ * si no pasó. El padre se entera del panic por aislamiento.rs
+ */
+pub fn correr_caso(leccion: &Leccion, ejercicio: &str, indice: usize) -> Result<(), String> {
+    let ejercicio = leccion
+        .ejercicios
+        .iter()
+        .find(|candidato| candidato.nombre == ejercicio)
+        .ok_or_else(|| format!("la lección {} no tiene el ejercicio {ejercicio}", leccion.nombre))?;
+    let casos = (ejercicio.casos)();
+    let caso = casos
+        .get(indice)
+        .ok_or_else(|| format!("el ejercicio {} no tiene el caso {indice}", ejercicio.nombre))?;
+    (caso.probar)()
+}
+
+pub struct Calificacion {
+    pub correctos: usize,
+    pub sin_resolver: usize,
+    pub total: usize,
+    // Ejercicios en los que pasaron todos los casos
+    pub ejercicios_pasados: Vec<&'static str>,
+}
+
+pub fn calificar(leccion: &Leccion) -> io::Result<Calificacion> {
+    calificar_con(leccion, aislamiento::ejecutar_caso_aislado)
+}
+
+// Igual que calificar pero con otra forma de correr cada caso, para las pruebas
+fn calificar_con<F>(leccion: &Leccion, mut correr: F) -> io::Result<Calificacion>
+where
+    F: FnMut(&Leccion, &str, usize) -> io::Result<Resultado>,
+{
+    let mut correctos = 0;
+    let mut sin_resolver = 0;
+    let mut total = 0;
+    let mut ejercicios_pasados = Vec::new();
+
+    for ejercicio in leccion.ejercicios {
+        let mut pasaron_todos = true;
+        println!("\n{}: {}", ejercicio.nombre, ejercicio.enunciado);
+        println!("  {} (en src/ejercicios/{}.rs)", ejercicio.firma, leccion.nombre);
+
+        for (indice, caso) in (ejercicio.casos)().iter().enumerate() {
+            let resultado = correr(leccion, ejercicio.nombre, indice)?;
+            total += 1;
+            if resultado.exito() {
+                correctos += 1;
+                println!("  [ok]    {}", caso.descripcion);
+            } else if resultado.sin_resolver() {
+                pasaron_todos = false;
+                sin_resolver += 1;
+                println!("  [sin resolver] {}", caso.descripcion);
+            } else {
+                pasaron_todos = false;
+                let motivo = match &resultado.panico {
+                    Some(panico) => format!("panic: {panico}"),
+                    None if resultado.tiempo_agotado => format!(
+                        "tiempo agotado, no terminó en {} s",
+                        aislamiento::TIEMPO_MAXIMO.as_secs()
+                    ),
+                    None => resultado.stderr.lines().last().unwrap_or("").to_string(),
+                };
+                println!("  [falla] {}: {motivo}", caso.descripcion);
+            }
+        }
+        if pasaron_todos {
+            ejercicios_pasados.push(ejercicio.nombre);
+        }
+    }
+
+    Ok(Calificacion {
+        correctos,
+        sin_resolver,
+        total,
+        ejercicios_pasados,
+    })
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use std::os::unix::process::ExitStatusExt;
+    use std::process::ExitStatus;
+
+    use super::*;
+    use crate::lecciones::Entrada;
+
+    fn sumar_bien(a: i32, b: i32) -> i32 {
+        a + b
+    }
+
+    fn sumar_mal(a: i32, b: i32) -> i32 {
+        a - b
+    }
+
+    const LECCION: Leccion = Leccion {
+        nombre: "prueba",
+        titulo: "Lección de prueba",
+        orden: 0,
+        entrada: Entrada::Simple(|| {}),
+        ejercicios: &[
+            Ejercicio {
+                nombre: "resuelto",
+                enunciado: "Suma dos números",
+                firma: "fn sumar_bien(a: i32, b: i32) -> i32",
+                casos: || {
+                    vec![
+                        caso("sumar_bien(2, 3)", || sumar_bien(2, 3), 5),
+                        caso("sumar_bien(-1, 1)", || sumar_bien(-1, 1), 0),
+                    ]
+                },
+            },
+            Ejercicio {
+                nombre: "equivocado",
+                enunciado: "Suma dos números",
+                firma: "fn sumar_mal(a: i32, b: i32) -> i32",
+                casos: || {
+                    vec![
+                        caso("sumar_mal(2, 3)", || sumar_mal(2, 3), 5),
+                        // Restar cero da lo mismo que sumarlo
+                        caso("sumar_mal(4, 0)", || sumar_mal(4, 0), 4),
+                    ]
+                },
+            },
+        ],
+        preguntas: "",
+        fuente: "",
+    };
+
+    /* Corre el caso en este mismo proceso y arma el Resultado que daría el
+     * hijo: código 0 si pasó, 1 con el motivo en stderr si no
+     */
+    fn correr_aqui(leccion: &Leccion, ejercicio: &str, indice: usize) -> io::Result<Resultado> {
+        let (estado, stderr) = match correr_caso(leccion, ejercicio, indice) {
+            Ok(()) => (ExitStatus::from_raw(0), String::new()),
+            // En unix el código de salida va en el segundo byte
+            Err(motivo) => (ExitStatus::from_raw(1 << 8), motivo),
+        };
+        Ok(Resultado {
+            leccion: "prueba",
+            stdout: String::new(),
+            stderr,
+            estado,
+            panico: None,
+            tiempo_agotado: false,
+        })
+    }
+
+    #[test]
+    fn cuenta_los_casos_que_pasan_y_los_que_fallan() {
+        let calificacion = calificar_con(&LECCION, correr_aqui).unwrap();
+        assert_eq!(calificacion.total, 4);
+        assert_eq!(calificacion.correctos, 3);
+        assert_eq!(calificacion.sin_resolver, 0);
+        assert_eq!(calificacion.ejercicios_pasados, ["resuelto"]);
+    }
+
+    #[test]
+    fn el_caso_equivocado_explica_la_diferencia() {
+        assert_eq!(correr_caso(&LECCION, "resuelto", 0), Ok(()));
+        assert_eq!(
+            correr_caso(&LECCION, "equivocado", 0),
+            Err(String::from("se esperaba 5 y se obtuvo -1"))
+        );
+    }
+}
@@ -0,0 +1,14 @@
+/* Ejercicios de la lección option. Reemplaza cada sin_resolver!() por tu
+ * solución y comprueba con: cargo run -- check option
+ */
+
+// Suma b al valor de a, si a es None se toma como 0
+pub fn sumar_opcion(a: Option<i32>, b: i32) -> i32 {
+    sin_resolver!()
+}
+
+// Suma ambos valores sólo si los dos existen, si falta alguno el
+// resultado es None
+pub fn sumar_opciones(a: Option<i32>, b: Option<i32>) -> Option<i32> {
+    sin_resolver!()
+}
@@ -0,0 +1,141 @@
+/* De dónde leen las lecciones interactivas. En lugar de llamar directo
+ * a io::stdin() se lee con entrada::leer_linea, que por defecto es la
+ * terminal de siempre pero puede cambiarse por un guion con respuestas
+ * ya escritas (para correr las lecciones sin nadie enfrente, como en
+ * las pruebas) o por una grabadora que guarda lo que se tecleó para
+ * repetirlo después con el guion
+ */
+
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::fs::{self, File};
+use std::io::{self, BufRead, Write};
+use std::path::{Path, PathBuf};
+
+// Carpeta con las respuestas guardadas para cada lección interactiva,
+// dentro del proyecto
+const CARPETA_GUIONES: &str = "entradas";
+
+pub trait FuenteEntrada {
+    // Devuelve la siguiente línea sin el salto de línea, o None si ya
+    // no hay nada más que leer
+    fn leer_linea(&mut self) -> io::Result<Option<String>>;
+}
+
+// La terminal, lo mismo que io::stdin().read_line()
+pub struct Terminal;
+
+impl FuenteEntrada for Terminal {
+    fn leer_linea(&mut self) -> io::Result<Option<String>> {
+        let mut linea = String::new();
+        if io::stdin().lock().read_line(&mut linea)? == 0 {
+            return Ok(None);
+        }
+        quitar_salto(&mut linea);
+        Ok(Some(linea))
+    }
+}
+
+/* Respuestas escritas de antemano, una por línea. Como nadie las
+ * teclea, se imprimen al leerse para que la salida se vea igual que
+ * una sesión en la terminal
+ */
+pub struct Guion {
+    lineas: VecDeque<String>,
+}
+
+impl Guion {
+    pub fn new(texto: &str) -> Self {
+        Self {
+            lineas: texto.lines().map(str::to_string).collect(),
+        }
+    }
+
+    pub fn desde_archivo(ruta: &Path) -> io::Result<Self> {
+        Ok(Self::new(&fs::read_to_string(ruta)?))
+    }
+}
+
+impl FuenteEntrada for Guion {
+    fn leer_linea(&mut self) -> io::Result<Option<String>> {
+        let linea = self.lineas.pop_front();
+        if let Some(linea) = &linea {
+            println!("{linea}");
+        }
+        Ok(linea)
+    }
+}
+
+/* Lee de otra fuente (normalmente la terminal) y va guardando cada
+ * respuesta en un archivo que después sirve como Guion
+ */
+pub struct Grabadora {
+    fuente: Box<dyn FuenteEntrada>,
+    archivo: File,
+}
+
+impl Grabadora {
+    pub fn new(fuente: Box<dyn FuenteEntrada>, ruta: &Path) -> io::Result<Self> {
+        Ok(Self {
+            fuente,
+            archivo: File::create(ruta)?,
+        })
+    }
+}
+
+impl FuenteEntrada for Grabadora {
+    fn leer_linea(&mut self) -> io::Result<Option<String>> {
+        let linea = self.fuente.leer_linea()?;
+        if let Some(linea) = &linea {
+            // Se escribe en cada línea para no perder la sesión si la
+            // lección termina en un panic
+            writeln!(self.archivo, "{linea}")?;
+            self.archivo.flush()?;
+        }
+        Ok(linea)
+    }
+}
+
+fn quitar_salto(linea: &mut String) {
+    if linea.ends_with('\n') {
+        linea.pop();
+        if linea.ends_with('\r') {
+            linea.pop();
+        }
+    }
+}
+
+/* El guion de una lección, si existe: entradas/<leccion>.txt junto al
+ * Cargo.toml, para que se encuentre aunque se corra desde otra carpeta
+ */
+pub fn guion_de(leccion: &str) -> Option<PathBuf> {
+    let ruta = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join(CARPETA_GUIONES)
+        .join(format!("{leccion}.txt"));
+    ruta.is_file().then_some(ruta)
+}
+
+thread_local! {
+    static FUENTE: RefCell<Box<dyn FuenteEntrada>> = RefCell::new(Box::new(Terminal));
+}
+
+// Cambia la fuente de la que leen las lecciones a partir de ahora
+pub fn usar(fuente: Box<dyn FuenteEntrada>) {
+    FUENTE.with(|actual| *actual.borrow_mut() = fuente);
+}
+
+/* Funciona igual que read_line: agrega la línea (con su salto) al
+ * final de la cadena y devuelve cuántos bytes se leyeron, 0 si ya no
+ * hay más entrada
+ */
+pub fn leer_linea(buffer: &mut String) -> io::Result<usize> {
+    let linea = FUENTE.with(|fuente| fuente.borrow_mut().leer_linea())?;
+    match linea {
+        Some(linea) => {
+            buffer.push_str(&linea);
+            buffer.push('\n');
+            Ok(linea.len() + 1)
+        }
+        None => Ok(0),
+    }
+}
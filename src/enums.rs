@@ -22,6 +22,19 @@ enum TipoDeIp {
     V6,
 }
 
+use crate::lecciones::{Entrada, Leccion};
+use crate::mensajes::mensaje;
+
+pub const LECCION: Leccion = Leccion {
+    nombre: "enums",
+    titulo: "Enums",
+    orden: 6,
+    entrada: Entrada::Simple(main),
+    ejercicios: &[],
+    preguntas: "",
+    fuente: include_str!("enums.rs"),
+};
+
 pub fn main() {
     // Se crea un nuevo tipo de dato
     let four = TipoDeIp::V4;
@@ -66,7 +79,7 @@ pub fn main() {
 }
 
 fn route(_tipo_ip: TipoDeIp) {
-    println!("Hacer algo");
+    println!("{}", mensaje!("enums.hacer_algo"));
 }
 
 struct Ip{
@@ -74,11 +87,14 @@ struct Ip{
     _ip: String,
 }
 
+// La dirección se guarda en la variante para el ejemplo, nadie la lee
+#[allow(dead_code)]
 enum DirIpV1{
     V4(String),
     V6(String),
 }
 
+#[allow(dead_code)]
 enum DirIpV2{
     V4(u8, u8, u8, u8),
     V6(String),
@@ -86,6 +102,6 @@ enum DirIpV2{
 
 impl DirIpV2 {
     fn ping(&self){
-        println!("haciendo ping");
+        println!("{}", mensaje!("enums.ping"));
     }
 }
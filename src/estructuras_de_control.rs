@@ -1,31 +1,62 @@
 // Crear una función que recibe dos argumentos
 fn funcion(argumento1: u16, argumento2: u16) {
-    println!("Esta es una función custom");
+    println!("{}", mensaje!("estructuras_de_control.funcion_custom"));
 
     let variable = argumento2 + argumento1;
-    println!("Suma de arg1 y arg2: {variable}");
+    println!("{}", mensaje!("estructuras_de_control.suma", variable));
 }
 
 // Función con retorno tipo i32, rust automaticamente retorna
 // el valor del último bloque de código escrito en la función
 // siempre y cuando no se agregue el ; al final de este.
 fn func_retorno() -> i32 {
-    println!("Hola");
+    println!("{}", mensaje!("estructuras_de_control.hola"));
     // Si colocamos 5; recibiremos un error
     5
 }
 
 // también podemos hacer uso de la palabra reservada return
+#[allow(clippy::needless_return)]
 fn multiplicar(arg1: i32, arg2: i32) -> i32 {
     return arg1 * arg2;
 }
 
+use crate::ejercicios::{self, caso, Ejercicio};
+use crate::lecciones::{Entrada, Leccion};
+use crate::mensajes::mensaje;
+
+pub const LECCION: Leccion = Leccion {
+    nombre: "estructuras_de_control",
+    titulo: "Funciones y estructuras de control",
+    orden: 3,
+    entrada: Entrada::Simple(main),
+    ejercicios: EJERCICIOS,
+    preguntas: "",
+    fuente: include_str!("estructuras_de_control.rs"),
+};
+
+// Como el if de number % 4, 3 y 2 de main pero con 3 y 5
+const EJERCICIOS: &[Ejercicio] = &[Ejercicio {
+    nombre: "fizzbuzz",
+    enunciado: "Fizz si n es divisible entre 3, Buzz entre 5, FizzBuzz entre ambos, si no el número",
+    firma: "fn fizzbuzz(n: u32) -> String",
+    casos: || {
+        vec![
+            caso("fizzbuzz(1)", || ejercicios::estructuras_de_control::fizzbuzz(1), String::from("1")),
+            caso("fizzbuzz(9)", || ejercicios::estructuras_de_control::fizzbuzz(9), String::from("Fizz")),
+            caso("fizzbuzz(10)", || ejercicios::estructuras_de_control::fizzbuzz(10), String::from("Buzz")),
+            caso("fizzbuzz(30)", || ejercicios::estructuras_de_control::fizzbuzz(30), String::from("FizzBuzz")),
+            caso("fizzbuzz(0)", || ejercicios::estructuras_de_control::fizzbuzz(0), String::from("FizzBuzz")),
+        ]
+    },
+}];
+
 pub fn main() {
-    println!("\nFUNCIONES\n");
+    println!("{}", mensaje!("estructuras_de_control.funciones"));
     funcion(2, 3);
 
     let var = func_retorno();
-    println!("Valor de var: {var}");
+    println!("{}", mensaje!("estructuras_de_control.valor_de_var", var));
 
     multiplicar(3, 9);
 
@@ -33,31 +64,31 @@ pub fn main() {
     // es verdadera, se ejecuratá el bloque de código dentro de esta
     // sentencia. existe una setencia else para ejecutar otro bloque
     // de codigo en caso de ser esta expresión falsa
-    println!("\nEXPRESIÓN IF\n");
+    println!("{}", mensaje!("estructuras_de_control.expresion_if"));
     if var == 5 {
-        println!("Si");
+        println!("{}", mensaje!("estructuras_de_control.si"));
     } else {
-        println!("No");
+        println!("{}", mensaje!("estructuras_de_control.no"));
     }
 
     // Se pueden concatenar varias sentencias if y else de esta forma
     let number = 6;
 
     if number % 4 == 0 {
-        println!("number is divisible by 4");
+        println!("{}", mensaje!("estructuras_de_control.divisible_entre_4"));
     } else if number % 3 == 0 {
-        println!("number is divisible by 3");
+        println!("{}", mensaje!("estructuras_de_control.divisible_entre_3"));
     } else if number % 2 == 0 {
-        println!("number is divisible by 2");
+        println!("{}", mensaje!("estructuras_de_control.divisible_entre_2"));
     } else {
-        println!("number is not divisible by 4, 3, or 2");
+        println!("{}", mensaje!("estructuras_de_control.no_divisible"));
     }
 
     // Debido a que if es una expresión, se puede usarse para asignar valores
     // pero en ambos bloques de código se debe tener el mismo tipo de dato
     let condition = true;
     let number = if condition { 5 } else { 6 };
-    println!("The value of number is: {number}");
+    println!("{}", mensaje!("estructuras_de_control.valor_de_number", number));
 
     // Bucles. Existen 3 tipos de bucles en rust, loop, while y for
 
@@ -65,7 +96,7 @@ pub fn main() {
     // indefinidamente hasta que se cierre manualmente el programa
     // también existe la palabra continue para que al llegar a esta
     // sentencia se pase automaticamente a la siguiente iteración
-    println!("\nBUCLE LOOP\n");
+    println!("{}", mensaje!("estructuras_de_control.bucle_loop"));
     let mut var = 0;
 
     let var2 = loop {
@@ -87,16 +118,16 @@ pub fn main() {
     // interior sea verdadera. Las sentencias break y continue también afectan
     // a este ciclo
     
-    println!("\nBUCLE WHILE\n");
+    println!("{}", mensaje!("estructuras_de_control.bucle_while"));
     var = 0;
     let arr = [50, 40, 30, 20, 10];
     while var < 5 {
-        println!("Iteracion {var}: {}", arr[var]);
+        println!("{}", mensaje!("estructuras_de_control.iteracion", var, arr[var]));
         var += 1;
     }
-    println!("Fuera del while");
+    println!("{}", mensaje!("estructuras_de_control.fuera_del_while"));
 
-    println!("\nBUCLE FOR\n");
+    println!("{}", mensaje!("estructuras_de_control.bucle_for"));
     // for. Este es más similar a un for each más que a un for como en C
     // Se define una variable elemento, que a cada itercación tendrá el
     // valor de cada dato en el arreglo arr
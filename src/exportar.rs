@@ -0,0 +1,458 @@
+/* Exportar las notas como un libro para leerlas en el navegador sin
+ * abrir un editor. Lo importante de cada archivo son los comentarios
+ * largos /* ... */, así que esos se vuelven texto y el código que queda
+ * entre ellos se muestra en bloques con colores. Se genera una página
+ * por lección, en el orden de las clases, y un índice con todas ellas.
+ * También puede generarse en Markdown
+ */
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::lecciones::{self, Leccion};
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Formato {
+    Html,
+    Markdown,
+}
+
+enum Bloque {
+    // Líneas de un comentario /* */ ya sin los asteriscos
+    Prosa(Vec<String>),
+    Codigo(Vec<String>),
+}
+
+const PALABRAS_RESERVADAS: &[&str] = &[
+    "as", "break", "const", "continue", "crate", "dyn", "else", "enum", "fn", "for", "if", "impl",
+    "in", "let", "loop", "match", "mod", "move", "mut", "pub", "ref", "return", "self", "Self",
+    "static", "struct", "super", "trait", "type", "use", "where", "while", "true", "false",
+];
+
+const ESTILO: &str = "body { max-width: 50rem; margin: 2rem auto; padding: 0 1rem;
+       font-family: sans-serif; line-height: 1.5; color: #222; }
+nav { display: flex; justify-content: space-between; margin: 1rem 0; }
+pre { background: #f6f8fa; padding: 0.8rem; overflow-x: auto; border-radius: 4px; }
+.kw { color: #a626a4; font-weight: bold; }
+.str { color: #50a14f; }
+.num { color: #986801; }
+.com { color: #a0a1a7; font-style: italic; }
+.mac { color: #4078f2; }
+.tipo { color: #c18401; }
+";
+
+pub fn exportar(carpeta: &Path, formato: Formato) -> io::Result<()> {
+    fs::create_dir_all(carpeta)?;
+    let todas = lecciones::todas();
+
+    for (i, leccion) in todas.iter().enumerate() {
+        let anterior = i.checked_sub(1).map(|i| todas[i]);
+        let siguiente = todas.get(i + 1).copied();
+        let bloques = separar(leccion.fuente);
+        let (archivo, pagina) = match formato {
+            Formato::Html => (
+                format!("{}.html", leccion.nombre),
+                pagina_html(leccion, &bloques, anterior, siguiente),
+            ),
+            Formato::Markdown => (
+                format!("{}.md", leccion.nombre),
+                pagina_markdown(leccion, &bloques, anterior, siguiente),
+            ),
+        };
+        fs::write(carpeta.join(archivo), pagina)?;
+    }
+
+    match formato {
+        Formato::Html => {
+            fs::write(carpeta.join("index.html"), indice_html(&todas))?;
+            fs::write(carpeta.join("estilo.css"), ESTILO)?;
+        }
+        Formato::Markdown => fs::write(carpeta.join("README.md"), indice_markdown(&todas))?,
+    }
+    Ok(())
+}
+
+/* Separa el archivo en bloques de prosa y de código. Sólo cuentan como
+ * prosa los comentarios /* */ que empiezan al inicio de una línea y
+ * terminan al final de otra, los demás se quedan en el código
+ */
+fn separar(fuente: &str) -> Vec<Bloque> {
+    let mut bloques = Vec::new();
+    let mut codigo: Vec<String> = Vec::new();
+    let mut prosa: Option<Vec<String>> = None;
+    let mut saltar_registro = false;
+    let mut saltar_atributo = false;
+
+    for linea in fuente.lines() {
+        let sin_sangria = linea.trim_start();
+
+        if let Some(lineas) = prosa.as_mut() {
+            match linea.split_once("*/") {
+                Some((ultima, _)) => {
+                    lineas.push(limpiar_prosa(ultima));
+                    bloques.push(Bloque::Prosa(prosa.take().unwrap_or_default()));
+                }
+                None => lineas.push(limpiar_prosa(linea)),
+            }
+            continue;
+        }
+
+        // Los #![allow(...)] y el comentario que los explica tampoco
+        if saltar_atributo || sin_sangria.starts_with("#![") {
+            quitar_comentario_anterior(&mut codigo);
+            saltar_atributo = !linea.trim_end().ends_with(']');
+            continue;
+        }
+
+        // Lo que sólo sirve para registrar la lección no es parte de la nota,
+        // ni los comentarios // que lo explican
+        if sin_sangria.starts_with("use crate::lecciones")
+            || sin_sangria.starts_with("use crate::ejercicios")
+        {
+            continue;
+        }
+        if linea.starts_with("pub const LECCION") || linea.starts_with("const EJERCICIOS") {
+            quitar_comentario_anterior(&mut codigo);
+            saltar_registro = !linea.ends_with(';');
+            continue;
+        }
+        if saltar_registro {
+            saltar_registro = !(linea.starts_with("};") || linea.starts_with("}];"));
+            continue;
+        }
+
+        if let Some(resto) = sin_sangria.strip_prefix("/*") {
+            match resto.split_once("*/") {
+                // Un comentario de una sola línea
+                Some((texto, despues)) if despues.trim().is_empty() => {
+                    cerrar_codigo(&mut bloques, &mut codigo);
+                    bloques.push(Bloque::Prosa(vec![limpiar_prosa(texto)]));
+                }
+                Some(_) => codigo.push(linea.to_string()),
+                None => {
+                    cerrar_codigo(&mut bloques, &mut codigo);
+                    prosa = Some(vec![limpiar_prosa(resto)]);
+                }
+            }
+            continue;
+        }
+
+        codigo.push(linea.to_string());
+    }
+
+    if let Some(lineas) = prosa {
+        bloques.push(Bloque::Prosa(lineas));
+    }
+    cerrar_codigo(&mut bloques, &mut codigo);
+    bloques
+}
+
+// Las líneas // que quedaron justo antes de algo que no va en la nota
+fn quitar_comentario_anterior(codigo: &mut Vec<String>) {
+    while codigo
+        .last()
+        .is_some_and(|linea| linea.trim_start().starts_with("//"))
+    {
+        codigo.pop();
+    }
+}
+
+fn limpiar_prosa(linea: &str) -> String {
+    let sin_sangria = linea.trim_start();
+    match sin_sangria.strip_prefix('*') {
+        Some(texto) => texto
+            .strip_prefix(' ')
+            .unwrap_or(texto)
+            .trim_end()
+            .to_string(),
+        // Sin asterisco suele ser código de ejemplo dentro del comentario,
+        // se conserva la sangría
+        None => linea.trim_end().to_string(),
+    }
+}
+
+fn cerrar_codigo(bloques: &mut Vec<Bloque>, codigo: &mut Vec<String>) {
+    let lineas = std::mem::take(codigo);
+    let inicio = lineas.iter().position(|linea| !linea.trim().is_empty());
+    let fin = lineas.iter().rposition(|linea| !linea.trim().is_empty());
+    let (Some(inicio), Some(fin)) = (inicio, fin) else {
+        return;
+    };
+
+    // Se quita la sangría que todas las líneas tienen en común
+    let lineas = &lineas[inicio..=fin];
+    let sangria = lineas
+        .iter()
+        .filter(|linea| !linea.trim().is_empty())
+        .map(|linea| linea.len() - linea.trim_start().len())
+        .min()
+        .unwrap_or(0);
+    let lineas = lineas
+        .iter()
+        .map(|linea| linea.get(sangria..).unwrap_or("").to_string())
+        .collect();
+    bloques.push(Bloque::Codigo(lineas));
+}
+
+/* Las líneas de un comentario en párrafos: una línea vacía separa
+ * párrafos, y las líneas con sangría o que empiezan con | (como el
+ * diagrama de ownership) se muestran tal cual
+ */
+enum Parrafo {
+    Texto(String),
+    Preformateado(Vec<String>),
+}
+
+fn parrafos(lineas: &[String]) -> Vec<Parrafo> {
+    let mut parrafos = Vec::new();
+    let mut texto: Vec<&str> = Vec::new();
+    let mut pre: Vec<String> = Vec::new();
+
+    let es_preformateada = |linea: &str| linea.starts_with('|') || linea.starts_with("    ");
+    for linea in lineas {
+        if (linea.trim().is_empty() || es_preformateada(linea)) && !texto.is_empty() {
+            parrafos.push(Parrafo::Texto(texto.join(" ")));
+            texto.clear();
+        }
+        if es_preformateada(linea) {
+            pre.push(linea.clone());
+            continue;
+        }
+        if !pre.is_empty() {
+            parrafos.push(Parrafo::Preformateado(std::mem::take(&mut pre)));
+        }
+        if !linea.trim().is_empty() {
+            texto.push(linea.trim());
+        }
+    }
+    if !texto.is_empty() {
+        parrafos.push(Parrafo::Texto(texto.join(" ")));
+    }
+    if !pre.is_empty() {
+        parrafos.push(Parrafo::Preformateado(pre));
+    }
+    parrafos
+}
+
+fn escapar(texto: &str) -> String {
+    texto
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn span(clase: &str, texto: &str) -> String {
+    format!("<span class=\"{clase}\">{}</span>", escapar(texto))
+}
+
+/* Colores para el código: un recorrido carácter por carácter que
+ * reconoce comentarios, cadenas, caracteres, números, macros, palabras
+ * reservadas y tipos (los identificadores que empiezan con mayúscula)
+ */
+fn resaltar(codigo: &str) -> String {
+    let caracteres: Vec<char> = codigo.chars().collect();
+    let texto = |inicio: usize, fin: usize| caracteres[inicio..fin].iter().collect::<String>();
+    let mut html = String::new();
+    let mut i = 0;
+
+    while i < caracteres.len() {
+        let c = caracteres[i];
+        let inicio = i;
+        if c == '/' && caracteres.get(i + 1) == Some(&'/') {
+            while i < caracteres.len() && caracteres[i] != '\n' {
+                i += 1;
+            }
+            html.push_str(&span("com", &texto(inicio, i)));
+        } else if c == '/' && caracteres.get(i + 1) == Some(&'*') {
+            i += 2;
+            while i < caracteres.len() && !(caracteres[i - 1] == '*' && caracteres[i] == '/') {
+                i += 1;
+            }
+            i = (i + 1).min(caracteres.len());
+            html.push_str(&span("com", &texto(inicio, i)));
+        } else if c == '"' {
+            i += 1;
+            while i < caracteres.len() && caracteres[i] != '"' {
+                if caracteres[i] == '\\' {
+                    i += 1;
+                }
+                i += 1;
+            }
+            i = (i + 1).min(caracteres.len());
+            html.push_str(&span("str", &texto(inicio, i)));
+        } else if c == '\'' && caracteres.get(i + 2) == Some(&'\'') {
+            // Un char como 'a', si no es un lifetime como 'static
+            i += 3;
+            html.push_str(&span("str", &texto(inicio, i)));
+        } else if c.is_ascii_digit() {
+            while i < caracteres.len()
+                && (caracteres[i].is_alphanumeric() || caracteres[i] == '.' || caracteres[i] == '_')
+            {
+                if caracteres[i] == '.' && !caracteres.get(i + 1).is_some_and(char::is_ascii_digit)
+                {
+                    break;
+                }
+                i += 1;
+            }
+            html.push_str(&span("num", &texto(inicio, i)));
+        } else if c.is_alphabetic() || c == '_' {
+            while i < caracteres.len() && (caracteres[i].is_alphanumeric() || caracteres[i] == '_')
+            {
+                i += 1;
+            }
+            let palabra = texto(inicio, i);
+            if caracteres.get(i) == Some(&'!') {
+                i += 1;
+                html.push_str(&span("mac", &texto(inicio, i)));
+            } else if PALABRAS_RESERVADAS.contains(&palabra.as_str()) {
+                html.push_str(&span("kw", &palabra));
+            } else if palabra.starts_with(|c: char| c.is_uppercase()) {
+                html.push_str(&span("tipo", &palabra));
+            } else {
+                html.push_str(&escapar(&palabra));
+            }
+        } else {
+            html.push_str(&escapar(&c.to_string()));
+            i += 1;
+        }
+    }
+    html
+}
+
+fn navegacion_html(anterior: Option<&Leccion>, siguiente: Option<&Leccion>) -> String {
+    let enlace = |leccion: Option<&Leccion>, flecha_antes: &str, flecha_despues: &str| match leccion
+    {
+        Some(leccion) => format!(
+            "<a href=\"{}.html\">{flecha_antes}{}{flecha_despues}</a>",
+            leccion.nombre,
+            escapar(leccion.titulo)
+        ),
+        None => String::from("<span></span>"),
+    };
+    format!(
+        "<nav>{}<a href=\"index.html\">Índice</a>{}</nav>\n",
+        enlace(anterior, "← ", ""),
+        enlace(siguiente, "", " →")
+    )
+}
+
+fn encabezado_html(titulo: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"es\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>{}</title>\n<link rel=\"stylesheet\" href=\"estilo.css\">\n</head>\n<body>\n",
+        escapar(titulo)
+    )
+}
+
+fn pagina_html(
+    leccion: &Leccion,
+    bloques: &[Bloque],
+    anterior: Option<&Leccion>,
+    siguiente: Option<&Leccion>,
+) -> String {
+    let mut html = encabezado_html(leccion.titulo);
+    html.push_str(&navegacion_html(anterior, siguiente));
+    html.push_str(&format!(
+        "<h1>{}. {}</h1>\n",
+        leccion.orden,
+        escapar(leccion.titulo)
+    ));
+
+    for bloque in bloques {
+        match bloque {
+            Bloque::Prosa(lineas) => {
+                for parrafo in parrafos(lineas) {
+                    match parrafo {
+                        Parrafo::Texto(texto) => {
+                            html.push_str(&format!("<p>{}</p>\n", escapar(&texto)))
+                        }
+                        Parrafo::Preformateado(lineas) => {
+                            html.push_str(&format!("<pre>{}</pre>\n", escapar(&lineas.join("\n"))))
+                        }
+                    }
+                }
+            }
+            Bloque::Codigo(lineas) => {
+                html.push_str(&format!(
+                    "<pre><code>{}</code></pre>\n",
+                    resaltar(&lineas.join("\n"))
+                ));
+            }
+        }
+    }
+
+    html.push_str(&navegacion_html(anterior, siguiente));
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+fn indice_html(lecciones: &[&Leccion]) -> String {
+    let mut html = encabezado_html("Mi estudio sobre Rust");
+    html.push_str("<h1>Mi estudio sobre Rust</h1>\n<h2>Contenido</h2>\n<ol>\n");
+    for leccion in lecciones {
+        html.push_str(&format!(
+            "<li><a href=\"{}.html\">{}</a></li>\n",
+            leccion.nombre,
+            escapar(leccion.titulo)
+        ));
+    }
+    html.push_str("</ol>\n</body>\n</html>\n");
+    html
+}
+
+fn navegacion_markdown(anterior: Option<&Leccion>, siguiente: Option<&Leccion>) -> String {
+    let mut partes = Vec::new();
+    if let Some(leccion) = anterior {
+        partes.push(format!("[← {}]({}.md)", leccion.titulo, leccion.nombre));
+    }
+    partes.push(String::from("[Índice](README.md)"));
+    if let Some(leccion) = siguiente {
+        partes.push(format!("[{} →]({}.md)", leccion.titulo, leccion.nombre));
+    }
+    partes.join(" · ") + "\n"
+}
+
+fn pagina_markdown(
+    leccion: &Leccion,
+    bloques: &[Bloque],
+    anterior: Option<&Leccion>,
+    siguiente: Option<&Leccion>,
+) -> String {
+    let mut md = navegacion_markdown(anterior, siguiente);
+    md.push_str(&format!("\n# {}. {}\n", leccion.orden, leccion.titulo));
+
+    for bloque in bloques {
+        match bloque {
+            Bloque::Prosa(lineas) => {
+                for parrafo in parrafos(lineas) {
+                    match parrafo {
+                        Parrafo::Texto(texto) => md.push_str(&format!("\n{texto}\n")),
+                        Parrafo::Preformateado(lineas) => {
+                            md.push_str(&format!("\n```text\n{}\n```\n", lineas.join("\n")))
+                        }
+                    }
+                }
+            }
+            Bloque::Codigo(lineas) => {
+                md.push_str(&format!("\n```rust\n{}\n```\n", lineas.join("\n")))
+            }
+        }
+    }
+
+    md.push('\n');
+    md.push_str(&navegacion_markdown(anterior, siguiente));
+    md
+}
+
+fn indice_markdown(lecciones: &[&Leccion]) -> String {
+    let mut md = String::from("# Mi estudio sobre Rust\n\n## Contenido\n\n");
+    for leccion in lecciones {
+        md.push_str(&format!(
+            "{}. [{}]({}.md)\n",
+            leccion.orden, leccion.titulo, leccion.nombre
+        ));
+    }
+    md
+}
@@ -3,6 +3,8 @@
  * ¿qué pasa cuando cambiamos los tipos? pues debemos
  * reescribir la funcion para el tipo adecuado
  */ 
+// El println!("") es la línea en blanco de siempre, aunque clippy prefiera println!()
+#[allow(clippy::println_empty_string)]
 fn iterar_algo_u32(x: &[u32]){
     for element in x{
         println!("{}", element);
@@ -10,6 +12,7 @@ fn iterar_algo_u32(x: &[u32]){
     println!("");
 }
 
+#[allow(clippy::println_empty_string)]
 fn iterar_algo_f32(x: &[f32]){
     for element in x{
         println!("{}", element);
@@ -32,6 +35,7 @@ fn iterar_algo_f32(x: &[f32]){
  * compilador que estos elementos deben cumplir esa "propiedad",
  * rasgo o trait.
  */ 
+#[allow(clippy::println_empty_string)]
 fn iterar_algo<T: std::fmt::Display>(x: &[T]){
     for element in x{
         println!("{}", element);
@@ -60,7 +64,7 @@ impl _Coso {
  */ 
 
 fn hacer_algo<T, U>(_x: &T, _y: &T, _z: &U){
-    println!("Haciendo algo\n");
+    println!("{}\n", mensaje!("generics.haciendo_algo"));
 }
 
 // Podemos definir tipos genericos en nuestros structs
@@ -86,7 +90,8 @@ impl <T: std::ops::Add<Output = T> + Copy>  Vector3<T> {
         Vector3{x,y,z}
     }
 
-    //Forma dos
+    //Forma dos, self: &Self es lo mismo que &self escrito completo
+    #[allow(clippy::needless_arbitrary_self_type)]
     fn add_vector(self: &Self, vector2: &Self ) -> Self{
         Self::new(self.x + vector2.x, self.y + vector2.y, self.z + vector2.z)
     }
@@ -100,7 +105,7 @@ impl Vector3<f32> {
      */
 
     fn hacer_algo(&self){
-        println!("Haciendo algo");
+        println!("{}", mensaje!("generics.haciendo_algo"));
     }
 }
 
@@ -110,7 +115,20 @@ struct _Algo<TipoGenerico>{
     _y: TipoGenerico,
 }
 
-pub fn _main() {
+use crate::lecciones::{Entrada, Leccion};
+use crate::mensajes::mensaje;
+
+pub const LECCION: Leccion = Leccion {
+    nombre: "generics",
+    titulo: "Genéricos",
+    orden: 13,
+    entrada: Entrada::Simple(main),
+    ejercicios: &[],
+    preguntas: "",
+    fuente: include_str!("generics.rs"),
+};
+
+pub fn main() {
     let arr_u32: [u32;3]  = [1, 3, 4]; 
     iterar_algo_u32(&arr_u32);
 
@@ -1,4 +1,17 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+
+use crate::lecciones::{Entrada, Leccion};
+use crate::mensajes::mensaje;
+
+pub const LECCION: Leccion = Leccion {
+    nombre: "hashmaps",
+    titulo: "HashMaps",
+    orden: 11,
+    entrada: Entrada::Simple(main),
+    ejercicios: &[],
+    preguntas: include_str!("preguntas/hashmaps.txt"),
+    fuente: include_str!("hashmaps.rs"),
+};
 
 pub fn main() {
     // Defines un conjunto de elementos al cual se le asocia
@@ -11,16 +24,16 @@ pub fn main() {
 
     // Get devuelve un some
     if let Some(dato) = scores.get("Blue") {
-        println!("Dato: {dato}");
+        println!("{}", mensaje!("hashmaps.dato", dato));
     } else {
-        println!("No existe dato con ese hash");
+        println!("{}", mensaje!("hashmaps.sin_dato"));
     }
 
     // Get devuelve un some
     if let Some(dato) = scores.get("Blue") {
-        println!("Dato: {dato}");
+        println!("{}", mensaje!("hashmaps.dato", dato));
     } else {
-        println!("No existe dato con ese hash");
+        println!("{}", mensaje!("hashmaps.sin_dato"));
     }
 
     // Pero parce ser que no es necesario
@@ -66,7 +79,13 @@ pub fn main() {
     * Es útil cuando calcular el valor predeterminado puede ser
     * costoso o depende de la lógica.
     */
-    for (key, value) in &scores {
+    /* El orden en el que se recorre un HashMap cambia en cada ejecución
+     * (la semilla del SipHash del que se habla al final es aleatoria),
+     * así que si se quiere imprimir siempre en el mismo orden se pasa
+     * primero a un BTreeMap, que guarda las claves ordenadas
+     */
+    let ordenado: BTreeMap<_, _> = scores.iter().collect();
+    for (key, value) in ordenado {
         println!("{key}: {value}");
     }
 
@@ -93,7 +112,7 @@ pub fn main() {
     scores.entry(String::from("Yellow")).or_insert(50);
     // Como ya existe blue, no hace ningún cambio
     scores.entry(String::from("Blue")).or_insert(50);
-    println!("{:?}", scores);
+    println!("{:?}", scores.iter().collect::<BTreeMap<_, _>>());
 
     /* Entry devuelve un Entry, que es un enum del módulo hash_map
      * y puede tener valor Ocupied y Vacant
@@ -133,7 +152,7 @@ pub fn main() {
         *count += 1;
     }
 
-    println!("{:?}", map);
+    println!("{:?}", map.iter().collect::<BTreeMap<_, _>>());
 
     /* Por defecto, HashMap utiliza una función de hashing llamada SipHash
      * que puede proporcionar resistencia a ataques de Denegación de Servicio
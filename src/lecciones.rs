@@ -0,0 +1,73 @@
+/* Registro de lecciones. Antes para elegir qué "clase" correr había
+ * que comentar y descomentar las llamadas en main.rs, ahora cada
+ * archivo declara una constante LECCION con su nombre, su título y
+ * el orden en el que se fueron escribiendo, y aquí se juntan todas
+ */
+
+use std::error::Error;
+
+use crate::ejercicios::Ejercicio;
+use crate::{
+    collections, control_de_errores, enums, estructuras_de_control, generics, hashmaps,
+    leer_de_consola, match_cf, modulos, option, ownership, structs, tipos_datos, traits,
+};
+
+/* No todas las lecciones tienen el mismo tipo de main, la de control
+ * de errores devuelve un Result, así que se guardan ambas formas
+ */
+pub enum Entrada {
+    Simple(fn()),
+    ConError(fn() -> Result<(), Box<dyn Error>>),
+}
+
+pub struct Leccion {
+    // Nombre corto con el que se llama desde la terminal
+    pub nombre: &'static str,
+    pub titulo: &'static str,
+    pub orden: u32,
+    pub entrada: Entrada,
+    // Ejercicios para practicar, ver ejercicios/mod.rs
+    pub ejercicios: &'static [Ejercicio],
+    // Banco de preguntas para el quiz, vacío si no tiene (ver quiz.rs)
+    pub preguntas: &'static str,
+    // El código del archivo, para exportarlo como libro (ver exportar.rs)
+    pub fuente: &'static str,
+}
+
+impl Leccion {
+    pub fn ejecutar(&self) -> Result<(), Box<dyn Error>> {
+        match self.entrada {
+            Entrada::Simple(main) => {
+                main();
+                Ok(())
+            }
+            Entrada::ConError(main) => main(),
+        }
+    }
+}
+
+// Todas las lecciones ordenadas como se fueron dando las "clases"
+pub fn todas() -> Vec<&'static Leccion> {
+    let mut lecciones = vec![
+        &tipos_datos::LECCION,
+        &leer_de_consola::LECCION,
+        &estructuras_de_control::LECCION,
+        &ownership::LECCION,
+        &structs::LECCION,
+        &enums::LECCION,
+        &option::LECCION,
+        &match_cf::LECCION,
+        &modulos::LECCION,
+        &collections::LECCION,
+        &hashmaps::LECCION,
+        &control_de_errores::LECCION,
+        &generics::LECCION,
+        &traits::LECCION,
+    ];
+    lecciones.sort_by_key(|leccion| leccion.orden);
+    lecciones
+}
+
+pub fn buscar(nombre: &str) -> Option<&'static Leccion> {
+    todas().into_iter().find(|leccion| leccion.nombre == nombre)
+}
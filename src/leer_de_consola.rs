@@ -1,45 +1,92 @@
 //use std::io; por defecto
-use std::io::{self, Write}; // Extra para la limpieza del buffer
+use std::error::Error;
+use std::io::Write; // Extra para la limpieza del buffer
+
+use crate::mensajes::{self, mensaje};
+use crate::{entrada, pedir};
 
 // Print no agrega el salto de linea por defecto pero posiblemente
 // para que se imprima de inmediato lo que se quiere mostrar se
-// deba limpiar el buffer
-fn print(input: String){
+// deba limpiar el buffer. pedir.rs también lo usa para sus preguntas
+pub fn print(input: String){
     print!("{input}");
     std::io::stdout().flush().expect("Error limpiando el buffer (?)");
 }
 
-pub fn main() {
+use crate::lecciones::{Entrada, Leccion};
+
+pub const LECCION: Leccion = Leccion {
+    nombre: "leer_de_consola",
+    titulo: "Leer datos por consola",
+    orden: 2,
+    entrada: Entrada::ConError(main),
+    ejercicios: &[],
+    preguntas: "",
+    fuente: include_str!("leer_de_consola.rs"),
+};
+
+pub fn main() -> Result<(), Box<dyn Error>> {
     // Leer dato por consola
     // Creamos una cadena de texto en el heap
     let mut index = String::new();
-    println!("Ingresa un número: ");
+    println!("{}", mensaje!("leer_de_consola.ingresa_numero"));
 
     // Lees como cadena, y pasas por referencia la cadena
     // los pasos por referencia tmb son inmutables por defecto
-    // y manejas un error en caso de no poder leer el dato
-    io::stdin()
-        .read_line(&mut index)
+    // y manejas un error en caso de no poder leer el dato.
+    // Originalmente era io::stdin().read_line(&mut index), entrada.rs
+    // funciona igual pero permite darle las respuestas desde un archivo
+    entrada::leer_linea(&mut index)
         .expect("Failed to read line");
 
     // Conviertes la cadena a un dato usize en una variable del mismo nombre
-    let index: usize = index
+    let index: Result<usize, _> = index
         // El método trim elimina espacios al inicio y al final
         .trim()
         // Parse convierte la cadena al tipo de dato numérico dado
-        .parse()
-        // Se maneja un error en caso de no ingresarse un número
-        .expect("ERROR: El dato ingresado no es un número");
+        .parse();
+
+    // Aquí antes había un .expect("ERROR: El dato ingresado no es un número")
+    // que terminaba el programa con un panic al primer error de dedo. Como
+    // parse devuelve un Result podemos manejar el error y volver a preguntar,
+    // que es lo que hace pedir::pedir hasta que el dato sea del tipo pedido
+    let index: usize = match index {
+        Ok(index) => index,
+        Err(_) => pedir::pedir(mensajes::texto("leer_de_consola.no_es_numero"))?,
+    };
 
-    println!("Entrada: {index}");
+    println!("{}", mensaje!("leer_de_consola.entrada", index));
 
     let cadena = "   Holaaks djsd  ";
     // El método trim elimina espacios al inicio y al final
     println!("{}", cadena.trim());
 
     let mut input = String::new();
-    print("Ingresa un numero: ".to_string());
-    io::stdin().read_line(&mut input).expect("Error no se pudo leer dato");
-    print("Dato ingresado: ".to_string());
+    print(mensaje!("leer_de_consola.ingresa_numero_sin_salto"));
+    entrada::leer_linea(&mut input).expect("Error no se pudo leer dato");
+    print(mensaje!("leer_de_consola.dato_ingresado"));
     print(input);
+
+    // pedir.rs tiene otras formas de preguntar que vuelven a intentar
+    // cuando la respuesta no sirve
+    let edad: u8 = pedir::pedir_en_rango(mensajes::texto("leer_de_consola.edad"), 1..=120)?;
+    // Si sólo se da enter se usa el valor entre corchetes
+    let veces: u32 = pedir::pedir_o(mensajes::texto("leer_de_consola.veces"), 1)?;
+    let gritar = pedir::confirmar(mensajes::texto("leer_de_consola.gritar"), false)?;
+    let colores = [
+        mensajes::texto("leer_de_consola.rojo"),
+        mensajes::texto("leer_de_consola.verde"),
+        mensajes::texto("leer_de_consola.azul"),
+    ];
+    let color = pedir::elegir(mensajes::texto("leer_de_consola.color"), &colores)?;
+
+    for _ in 0..veces {
+        if gritar {
+            println!("{}", mensaje!("leer_de_consola.saludo_gritando", edad, colores[color].to_uppercase()));
+        } else {
+            println!("{}", mensaje!("leer_de_consola.saludo", edad, colores[color]));
+        }
+    }
+
+    Ok(())
 }
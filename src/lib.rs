@@ -0,0 +1,32 @@
+/* Las lecciones y todo lo que se fue construyendo alrededor de ellas.
+ * main.rs sólo lee la línea de comandos y llama a lo que está aquí, así
+ * las pruebas también pueden usar los módulos directamente
+ */
+
+pub mod collections;
+pub mod enums;
+pub mod estructuras_de_control;
+pub mod hashmaps;
+pub mod leer_de_consola;
+pub mod match_cf;
+pub mod modulos;
+pub mod monedas;
+pub mod operaciones;
+pub mod option;
+pub mod ownership;
+pub mod structs;
+pub mod tipos_datos;
+pub mod control_de_errores;
+pub mod generics;
+pub mod traits;
+pub mod lecciones;
+pub mod aislamiento;
+pub mod entrada;
+pub mod pedir;
+pub mod snapshots;
+pub mod ejercicios;
+pub mod progreso;
+pub mod aleatorio;
+pub mod quiz;
+pub mod exportar;
+pub mod mensajes;
@@ -1,42 +1,789 @@
-mod collections;
-mod enums;
-mod estructuras_de_control;
-mod hashmaps;
-mod leer_de_consola;
-mod match_cf;
-mod modulos;
-mod operaciones;
-mod option;
-mod ownership;
-mod structs;
-mod tipos_datos;
-mod control_de_errores;
-mod generics;
-mod traits;
-
-fn main() {
-    //En este orden se fueron realizando los archivos y es orden
-    //de las "clases"
-
-    // tipos_datos::main();
-    // leer_de_consola::main();
-    // estructuras_de_control::main();
-    // ownership::main();
-    // structs::main();
-    // enums::main();
-    // option::main();
-    // match_cf::main();
-    // aquí va lo de modulos.rs
-    // collections::main();
-
-    // Aquí iría la explicación detallada de las Strings
-
-    // hashmaps::main();
-
-    // ignorar unit o error que devuelve con el _ xd
-    // let _ = control_de_errores::main();
-
-    //generics::_main();
-    traits::_main();
+use std::env;
+use std::io;
+use std::path::Path;
+use std::process::ExitCode;
 
+use rust::{
+    aislamiento, aleatorio, ejercicios, entrada, exportar, lecciones, mensajes, monedas,
+    operaciones, progreso, quiz, snapshots,
+};
+
+const USO: &str = "Uso:
+    cargo run -- list              Muestra las lecciones en orden
+    cargo run -- run <leccion>     Ejecuta una lección por su nombre
+        --entrada <archivo>        Lee las respuestas de un archivo en vez
+                                   de la terminal
+        --grabar <archivo>         Guarda lo que se teclee para repetirlo
+                                   después con --entrada
+    cargo run -- run --all         Ejecuta todas las lecciones en orden, cada
+                                   una en su propio proceso
+    cargo run -- check <leccion>   Califica los ejercicios de la lección que
+                                   están en src/ejercicios/<leccion>.rs
+    cargo run -- progress          Muestra las lecciones vistas y los ejercicios
+                                   pasados (del alumno en la variable ALUMNO)
+    cargo run -- next              Sugiere la siguiente lección
+    cargo run -- quiz <leccion> [--semilla <n>]
+                                   Preguntas de opción múltiple de la lección,
+                                   con la misma semilla salen en el mismo orden
+    cargo run -- snapshot [<leccion>...] [--aceptar]
+                                   Compara la salida de las lecciones con la
+                                   guardada en tests/snapshots, con --aceptar
+                                   (o ACEPTAR_SNAPSHOTS=1) guarda la actual
+    cargo run -- export [--markdown] [--salida <carpeta>]
+                                   Genera un libro con las notas de todas las
+                                   lecciones, en HTML o Markdown (por defecto
+                                   en la carpeta libro)
+    cargo run -- calc [<expresion>]
+                                   Calcula una expresión, sin expresión abre
+                                   la calculadora interactiva (con let x = ...)
+    cargo run -- estadistica <archivo> [--histograma <cajas>] [--nan ignorar]
+                         [--streaming] [--poblacion]
+                                   Media, mediana, moda, desviación y
+                                   percentiles de un número por línea. Con
+                                   --streaming no guarda los datos en memoria
+                                   y con --poblacion la varianza divide entre
+                                   n en vez de n - 1
+    cargo run -- convertir <cantidad> <de> <a> [--tasas <archivo>]
+                                   Convierte entre divisas con las tasas del
+                                   archivo (.csv o .toml, por defecto
+                                   tasas/tasas.csv), aunque no haya tasa directa
+    cargo run -- convertir --revisar [--tasas <archivo>]
+                                   Busca tasas faltantes y ciclos de arbitraje
+    cargo run -- clasificar [--monedas <n>] [--semilla <n>]
+                                   Simula la máquina que ordena monedas con un
+                                   lote de monedas (1000 por defecto)
+    cargo run --release -- matrices [--tamanio <n>] [--semilla <n>]
+                                   Compara el tiempo de multiplicar matrices
+                                   guardadas en Vec<Vec<f64>> y en Matriz
+                                   (de 300 x 300 por defecto)
+    cargo run -- expendedora       Una máquina expendedora interactiva que
+                                   acepta monedas de cobre, plata y oro
+    cargo run -- mensajes          Revisa que los mensajes estén en español
+                                   y en inglés
+
+Con --idioma <es|en> (o la variable RUST_NOTAS_IDIOMA) se elige el idioma
+de lo que muestran las lecciones, por defecto español";
+
+fn main() -> ExitCode {
+    // En este orden se fueron realizando los archivos y es orden
+    // de las "clases", ahora cada archivo se registra en lecciones.rs
+    // y se elige cuál correr desde la terminal:
+    // cargo run -- run traits
+    aislamiento::preparar_hijo();
+
+    let args: Vec<String> = env::args().skip(1).collect();
+    let mut args: Vec<&str> = args.iter().map(String::as_str).collect();
+
+    // --idioma puede ir en cualquier lugar, se pasa a la variable para que
+    // también lo vean las lecciones que corren en otro proceso
+    if let Some(i) = args.iter().position(|arg| *arg == "--idioma") {
+        match args.get(i + 1).copied().and_then(mensajes::Idioma::desde_codigo) {
+            Some(_) => env::set_var(mensajes::VARIABLE, args[i + 1]),
+            None => {
+                eprintln!("El idioma debe ser es o en");
+                return ExitCode::from(2);
+            }
+        }
+        args.drain(i..=i + 1);
+    }
+
+    match args.as_slice() {
+        ["list"] => {
+            listar();
+            ExitCode::SUCCESS
+        }
+        ["run", "--all"] => ejecutar_todas(),
+        ["run", nombre, opciones @ ..] => match preparar_entrada(opciones) {
+            Ok(()) => ejecutar(nombre),
+            Err(error) => {
+                eprintln!("{error}");
+                ExitCode::from(2)
+            }
+        },
+        ["check", nombre] => calificar(nombre),
+        // Uso interno de check, corre un solo caso en un proceso aparte
+        ["__caso", leccion, ejercicio, indice] => correr_caso(leccion, ejercicio, indice),
+        ["progress"] => mostrar_progreso(),
+        ["next"] => sugerir_siguiente(),
+        ["quiz", nombre] => hacer_quiz(nombre, None),
+        ["quiz", nombre, "--semilla", semilla] => match semilla.parse() {
+            Ok(semilla) => hacer_quiz(nombre, Some(semilla)),
+            Err(_) => {
+                eprintln!("La semilla debe ser un número entero positivo");
+                ExitCode::from(2)
+            }
+        },
+        ["snapshot", opciones @ ..] => comprobar_snapshots(opciones),
+        ["export", opciones @ ..] => exportar_libro(opciones),
+        ["mensajes"] => revisar_mensajes(),
+        ["calc"] => match operaciones::calculadora::repl() {
+            Ok(()) => ExitCode::SUCCESS,
+            Err(error) => {
+                eprintln!("No se pudo leer la entrada: {error}");
+                ExitCode::FAILURE
+            }
+        },
+        ["calc", expresion @ ..] => calcular(&expresion.join(" ")),
+        ["expendedora"] => match monedas::expendedora::sesion() {
+            Ok(()) => ExitCode::SUCCESS,
+            Err(error) => {
+                eprintln!("No se pudo leer la entrada: {error}");
+                ExitCode::FAILURE
+            }
+        },
+        ["clasificar", opciones @ ..] => clasificar_monedas(opciones),
+        ["matrices", opciones @ ..] => comparar_matrices(opciones),
+        ["estadistica", archivo, opciones @ ..] => estadisticas(archivo, opciones),
+        ["convertir", "--revisar", opciones @ ..] => revisar_tasas(opciones),
+        ["convertir", cantidad, de, a, opciones @ ..] => convertir(cantidad, de, a, opciones),
+        _ => {
+            eprintln!("{USO}");
+            ExitCode::from(2)
+        }
+    }
+}
+
+fn listar() {
+    for leccion in lecciones::todas() {
+        println!("{:>2}. {:<24} {}", leccion.orden, leccion.nombre, leccion.titulo);
+    }
+}
+
+fn preparar_entrada(opciones: &[&str]) -> io::Result<()> {
+    match opciones {
+        [] => Ok(()),
+        ["--entrada", archivo] => {
+            let guion = entrada::Guion::desde_archivo(Path::new(archivo))?;
+            entrada::usar(Box::new(guion));
+            Ok(())
+        }
+        ["--grabar", archivo] => {
+            let grabadora = entrada::Grabadora::new(Box::new(entrada::Terminal), Path::new(archivo))?;
+            entrada::usar(Box::new(grabadora));
+            Ok(())
+        }
+        _ => Err(io::Error::new(io::ErrorKind::InvalidInput, USO)),
+    }
+}
+
+fn ejecutar(nombre: &str) -> ExitCode {
+    let Some(leccion) = lecciones::buscar(nombre) else {
+        eprintln!("No existe la lección \"{nombre}\", usa `list` para verlas");
+        return ExitCode::from(2);
+    };
+
+    match leccion.ejecutar() {
+        Ok(()) => {
+            // Los procesos hijos no cuentan, de eso se encarga quien los lanzó
+            if !aislamiento::es_hijo() {
+                progreso::registrar(|progreso, alumno| progreso.marcar_leccion(alumno, leccion.nombre));
+            }
+            ExitCode::SUCCESS
+        }
+        Err(error) => {
+            eprintln!("La lección {} terminó con error: {error}", leccion.nombre);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn ejecutar_todas() -> ExitCode {
+    // Cada lección corre en un proceso hijo, así un panic no detiene
+    // a las que siguen
+    let mut resultados = Vec::new();
+    for leccion in lecciones::todas() {
+        println!("\n== {}. {} ==\n", leccion.orden, leccion.titulo);
+        match aislamiento::ejecutar_aislada(leccion) {
+            Ok(resultado) => {
+                print!("{}", resultado.stdout);
+                eprint!("{}", resultado.stderr);
+                resultados.push(resultado);
+            }
+            Err(error) => {
+                eprintln!("No se pudo lanzar la lección {}: {error}", leccion.nombre);
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+
+    progreso::registrar(|progreso, alumno| {
+        for resultado in resultados.iter().filter(|resultado| resultado.exito()) {
+            progreso.marcar_leccion(alumno, resultado.leccion);
+        }
+    });
+
+    println!();
+    aislamiento::imprimir_resumen(&resultados);
+    if resultados.iter().all(aislamiento::Resultado::exito) {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+fn calificar(nombre: &str) -> ExitCode {
+    let Some(leccion) = lecciones::buscar(nombre) else {
+        eprintln!("No existe la lección \"{nombre}\", usa `list` para verlas");
+        return ExitCode::from(2);
+    };
+    if leccion.ejercicios.is_empty() {
+        println!("La lección {} todavía no tiene ejercicios", leccion.nombre);
+        return ExitCode::SUCCESS;
+    }
+
+    println!("Ejercicios de {}. {}", leccion.orden, leccion.titulo);
+    match ejercicios::calificar(leccion) {
+        Ok(calificacion) => {
+            progreso::registrar(|progreso, alumno| {
+                for ejercicio in &calificacion.ejercicios_pasados {
+                    progreso.marcar_ejercicio(alumno, leccion.nombre, ejercicio);
+                }
+            });
+            print!("\n{} de {} casos correctos", calificacion.correctos, calificacion.total);
+            match calificacion.sin_resolver {
+                0 => println!(),
+                pendientes => println!(", {pendientes} sin resolver"),
+            }
+            if calificacion.correctos == calificacion.total {
+                ExitCode::SUCCESS
+            } else {
+                ExitCode::FAILURE
+            }
+        }
+        Err(error) => {
+            eprintln!("No se pudieron calificar los ejercicios: {error}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn correr_caso(leccion: &str, ejercicio: &str, indice: &str) -> ExitCode {
+    let (Some(leccion), Ok(indice)) = (lecciones::buscar(leccion), indice.parse()) else {
+        eprintln!("{USO}");
+        return ExitCode::from(2);
+    };
+    match ejercicios::correr_caso(leccion, ejercicio, indice) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(motivo) => {
+            eprintln!("{motivo}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn mostrar_progreso() -> ExitCode {
+    match progreso::Progreso::cargar(&progreso::ruta()) {
+        Ok(progreso) => {
+            progreso::mostrar(&progreso, &progreso::alumno());
+            ExitCode::SUCCESS
+        }
+        Err(error) => {
+            eprintln!("{} ({})", error, progreso::ruta().display());
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn sugerir_siguiente() -> ExitCode {
+    let progreso = match progreso::Progreso::cargar(&progreso::ruta()) {
+        Ok(progreso) => progreso,
+        Err(error) => {
+            eprintln!("{} ({})", error, progreso::ruta().display());
+            return ExitCode::FAILURE;
+        }
+    };
+    match progreso::siguiente(&progreso, &progreso::alumno()) {
+        Some((leccion, comando)) => println!(
+            "Sigue {}. {}: cargo run -- {comando} {}",
+            leccion.orden, leccion.titulo, leccion.nombre
+        ),
+        None => println!("Ya viste todas las lecciones y pasaste todos los ejercicios"),
+    }
+    ExitCode::SUCCESS
+}
+
+fn hacer_quiz(nombre: &str, semilla: Option<u64>) -> ExitCode {
+    let Some(leccion) = lecciones::buscar(nombre) else {
+        eprintln!("No existe la lección \"{nombre}\", usa `list` para verlas");
+        return ExitCode::from(2);
+    };
+    let mut preguntas = match quiz::leer_preguntas(leccion.preguntas) {
+        Ok(preguntas) if preguntas.is_empty() => {
+            println!("La lección {} todavía no tiene preguntas", leccion.nombre);
+            return ExitCode::SUCCESS;
+        }
+        Ok(preguntas) => preguntas,
+        Err(error) => {
+            eprintln!("El banco de preguntas de {} tiene un error en la {error}", leccion.nombre);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let semilla = semilla.unwrap_or_else(aleatorio::Aleatorio::semilla_del_reloj);
+    println!("Quiz de {}. {} (semilla {semilla})", leccion.orden, leccion.titulo);
+    match quiz::jugar(leccion, &mut preguntas, semilla) {
+        Ok((aciertos, total)) => {
+            println!("\nPuntaje: {aciertos} de {total}");
+            ExitCode::SUCCESS
+        }
+        Err(error) => {
+            eprintln!("El quiz se interrumpió: {error}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn comprobar_snapshots(opciones: &[&str]) -> ExitCode {
+    let aceptar = opciones.contains(&"--aceptar") || env::var_os("ACEPTAR_SNAPSHOTS").is_some();
+    // Los snapshots guardan la salida en español, sin importar --idioma
+    env::set_var(mensajes::VARIABLE, "es");
+    let nombres: Vec<&str> = opciones.iter().copied().filter(|opcion| *opcion != "--aceptar").collect();
+
+    let lecciones = if nombres.is_empty() {
+        lecciones::todas()
+    } else {
+        let mut elegidas = Vec::new();
+        for nombre in nombres {
+            match lecciones::buscar(nombre) {
+                Some(leccion) => elegidas.push(leccion),
+                None => {
+                    eprintln!("No existe la lección \"{nombre}\", usa `list` para verlas");
+                    return ExitCode::from(2);
+                }
+            }
+        }
+        elegidas
+    };
+
+    match snapshots::comprobar(&lecciones, aceptar) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(error) => {
+            eprintln!("No se pudieron comprobar los snapshots: {error}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn exportar_libro(opciones: &[&str]) -> ExitCode {
+    let mut formato = exportar::Formato::Html;
+    let mut carpeta = "libro";
+    let mut resto = opciones;
+    loop {
+        match resto {
+            [] => break,
+            ["--markdown", siguientes @ ..] => {
+                formato = exportar::Formato::Markdown;
+                resto = siguientes;
+            }
+            ["--salida", destino, siguientes @ ..] => {
+                carpeta = destino;
+                resto = siguientes;
+            }
+            _ => {
+                eprintln!("{USO}");
+                return ExitCode::from(2);
+            }
+        }
+    }
+
+    match exportar::exportar(Path::new(carpeta), formato) {
+        Ok(()) => {
+            let indice = match formato {
+                exportar::Formato::Html => "index.html",
+                exportar::Formato::Markdown => "README.md",
+            };
+            println!("Libro generado en {}", Path::new(carpeta).join(indice).display());
+            ExitCode::SUCCESS
+        }
+        Err(error) => {
+            eprintln!("No se pudo generar el libro en {carpeta}: {error}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn revisar_mensajes() -> ExitCode {
+    let problemas = mensajes::problemas();
+    if problemas.is_empty() {
+        println!("Todos los mensajes están en español y en inglés");
+        return ExitCode::SUCCESS;
+    }
+    for problema in &problemas {
+        println!("- {problema}");
+    }
+    println!("\n{} problemas en el catálogo de mensajes", problemas.len());
+    ExitCode::FAILURE
+}
+
+fn calcular(expresion: &str) -> ExitCode {
+    match operaciones::calculadora::Calculadora::new().evaluar(expresion) {
+        Ok(valor) => {
+            println!("{valor}");
+            ExitCode::SUCCESS
+        }
+        Err(error) => {
+            eprintln!("{expresion}\n{}\nError en la {error}", error.senalar());
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn cargar_tasas(opciones: &[&str]) -> Result<monedas::divisas::TablaDeCambio, ExitCode> {
+    let archivo = match opciones {
+        [] => "tasas/tasas.csv",
+        ["--tasas", archivo] => archivo,
+        _ => {
+            eprintln!("{USO}");
+            return Err(ExitCode::from(2));
+        }
+    };
+    monedas::divisas::TablaDeCambio::desde_archivo(Path::new(archivo)).map_err(|error| {
+        eprintln!("{archivo}: {error}");
+        ExitCode::FAILURE
+    })
+}
+
+// 17.25 en vez de 69/4, con hasta 6 decimales
+fn tasa_decimal(tasa: &monedas::divisas::Tasa) -> String {
+    let texto = format!("{:.6}", tasa.a_f64());
+    texto.trim_end_matches('0').trim_end_matches('.').to_string()
+}
+
+fn convertir(cantidad: &str, de: &str, a: &str, opciones: &[&str]) -> ExitCode {
+    let tabla = match cargar_tasas(opciones) {
+        Ok(tabla) => tabla,
+        Err(codigo) => return codigo,
+    };
+    let datos = format!("{cantidad} {de}")
+        .parse::<monedas::Dinero>()
+        .and_then(|dinero| Ok((dinero, a.parse::<monedas::Divisa>()?)));
+    let (dinero, destino) = match datos {
+        Ok(datos) => datos,
+        Err(error) => {
+            eprintln!("{error}");
+            return ExitCode::from(2);
+        }
+    };
+
+    for arbitraje in tabla.arbitrajes(0.001) {
+        eprintln!("Aviso: las tasas no son coherentes, {arbitraje}");
+    }
+    match tabla.convertir(dinero, destino) {
+        Ok(conversion) => {
+            println!("{} = {}", dinero.con_codigo(), conversion.cantidad.con_codigo());
+            println!(
+                "Ruta: {}, tasa {}",
+                monedas::divisas::unir(&conversion.ruta),
+                tasa_decimal(&conversion.tasa)
+            );
+            ExitCode::SUCCESS
+        }
+        Err(error) => {
+            eprintln!("No se pudo convertir: {error}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn revisar_tasas(opciones: &[&str]) -> ExitCode {
+    let tabla = match cargar_tasas(opciones) {
+        Ok(tabla) => tabla,
+        Err(codigo) => return codigo,
+    };
+    let grupos = tabla.grupos();
+    let arbitrajes = tabla.arbitrajes(0.001);
+    if grupos.len() <= 1 && arbitrajes.is_empty() {
+        println!("Las tasas de {} divisas son coherentes", tabla.divisas().len());
+        return ExitCode::SUCCESS;
+    }
+
+    if grupos.len() > 1 {
+        println!("Faltan tasas, estos grupos no se pueden convertir entre sí:");
+        for grupo in &grupos {
+            let grupo: Vec<String> = grupo.iter().map(ToString::to_string).collect();
+            println!("- {}", grupo.join(", "));
+        }
+    }
+    for arbitraje in &arbitrajes {
+        println!("Arbitraje: {arbitraje}");
+    }
+    ExitCode::FAILURE
+}
+
+fn clasificar_monedas(mut opciones: &[&str]) -> ExitCode {
+    let mut cantidad = 1000;
+    let mut semilla = None;
+    while let [opcion, valor, resto @ ..] = opciones {
+        let valido = match *opcion {
+            "--monedas" => valor.parse().map(|valor| cantidad = valor).is_ok(),
+            "--semilla" => valor.parse().map(|valor| semilla = Some(valor)).is_ok(),
+            _ => false,
+        };
+        if !valido {
+            break;
+        }
+        opciones = resto;
+    }
+    if !opciones.is_empty() {
+        eprintln!("{USO}");
+        return ExitCode::from(2);
+    }
+
+    let semilla = semilla.unwrap_or_else(aleatorio::Aleatorio::semilla_del_reloj);
+    let mut aleatorio = aleatorio::Aleatorio::new(semilla);
+    let lote = monedas::clasificadora::Fabrica::default().fabricar(cantidad, &mut aleatorio);
+    println!("Clasificando {cantidad} monedas (semilla {semilla})\n");
+    println!("{}", monedas::clasificadora::Clasificadora::default().procesar(&lote));
+    ExitCode::SUCCESS
+}
+
+fn estadisticas(archivo: &str, mut opciones: &[&str]) -> ExitCode {
+    use operaciones::estadistica::{Acumulador, Muestra, PoliticaNan};
+
+    let mut cajas = None;
+    let mut politica = PoliticaNan::Error;
+    let mut streaming = false;
+    let mut poblacion = false;
+    loop {
+        opciones = match opciones {
+            ["--histograma", n, resto @ ..] if n.parse::<usize>().is_ok() => {
+                cajas = n.parse().ok();
+                resto
+            }
+            ["--nan", "ignorar", resto @ ..] => {
+                politica = PoliticaNan::Ignorar;
+                resto
+            }
+            ["--streaming", resto @ ..] => {
+                streaming = true;
+                resto
+            }
+            ["--poblacion", resto @ ..] => {
+                poblacion = true;
+                resto
+            }
+            [] => break,
+            _ => {
+                eprintln!("{USO}");
+                return ExitCode::from(2);
+            }
+        };
+    }
+
+    let lector = match std::fs::File::open(archivo) {
+        Ok(archivo) => io::BufReader::new(archivo),
+        Err(error) => {
+            eprintln!("No se pudo abrir {archivo}: {error}");
+            return ExitCode::FAILURE;
+        }
+    };
+    // El acumulador siempre, los datos solo si hacen falta para la mediana
+    let mut acumulador = Acumulador::new(politica);
+    let mut datos = Vec::new();
+    for (i, linea) in io::BufRead::lines(lector).enumerate() {
+        let linea = match linea {
+            Ok(linea) => linea,
+            Err(error) => {
+                eprintln!("No se pudo leer {archivo}: {error}");
+                return ExitCode::FAILURE;
+            }
+        };
+        if linea.trim().is_empty() {
+            continue;
+        }
+        let Ok(numero) = linea.trim().parse::<f64>() else {
+            eprintln!("Línea {}: \"{}\" no es un número", i + 1, linea.trim());
+            return ExitCode::FAILURE;
+        };
+        if acumulador.agregar(numero).is_err() {
+            eprintln!("Línea {}: es NaN, usa --nan ignorar para descartarlos", i + 1);
+            return ExitCode::FAILURE;
+        }
+        if !streaming {
+            datos.push(numero);
+        }
+    }
+
+    let (Some(media), Some(minimo), Some(maximo)) =
+        (acumulador.media(), acumulador.minimo(), acumulador.maximo())
+    else {
+        eprintln!("{archivo} no tiene números");
+        return ExitCode::FAILURE;
+    };
+    let muestra = if streaming {
+        None
+    } else {
+        match Muestra::new(&datos, politica) {
+            Ok(muestra) => Some(muestra),
+            Err(error) => {
+                eprintln!("{error}");
+                return ExitCode::FAILURE;
+            }
+        }
+    };
+    // Con los datos guardados la varianza se calcula en dos pasadas, sin ellos con Welford
+    let (media, varianza, desviacion) = match (&muestra, poblacion) {
+        (Some(muestra), true) => (
+            muestra.media(),
+            Some(muestra.varianza_poblacional()),
+            Some(muestra.desviacion_poblacional()),
+        ),
+        (Some(muestra), false) => (
+            muestra.media(),
+            muestra.varianza_muestral(),
+            muestra.desviacion_muestral(),
+        ),
+        (None, true) => {
+            let varianza = acumulador.varianza_poblacional();
+            (media, varianza, varianza.map(f64::sqrt))
+        }
+        (None, false) => (
+            media,
+            acumulador.varianza_muestral(),
+            acumulador.desviacion_muestral(),
+        ),
+    };
+
+    println!("Datos: {}", acumulador.cantidad());
+    if acumulador.nan() > 0 {
+        println!("NaN ignorados: {}", acumulador.nan());
+    }
+    println!("Media: {media:.4}");
+    if let (Some(varianza), Some(desviacion)) = (varianza, desviacion) {
+        println!("Varianza: {varianza:.4}");
+        println!("Desviación estándar: {desviacion:.4}");
+    }
+    println!("Mínimo: {minimo}");
+    println!("Máximo: {maximo}");
+    let Some(muestra) = muestra else {
+        return ExitCode::SUCCESS;
+    };
+
+    println!("Suma: {}", muestra.suma());
+    println!("Mediana: {:.4}", muestra.mediana());
+    let modas: Vec<String> = muestra.modas().iter().map(ToString::to_string).collect();
+    println!("Moda: {}", modas.join(", "));
+    for p in [25.0, 75.0, 90.0] {
+        if let Ok(valor) = muestra.percentil(p) {
+            println!("Percentil {p}: {valor:.4}");
+        }
+    }
+    if let Some(cajas) = cajas {
+        match muestra.histograma(cajas) {
+            Ok(histograma) => print!("\n{histograma}"),
+            Err(error) => {
+                eprintln!("{error}");
+                return ExitCode::from(2);
+            }
+        }
+    }
+    ExitCode::SUCCESS
+}
+
+/* Multiplica las mismas matrices aleatorias guardadas como Vec<Vec<f64>>
+ * y como Matriz, directo y por bloques. Las dos usan el orden i-k-j, que
+ * recorre b fila por fila, para que la diferencia sea solo cómo se
+ * guardan. El triple ciclo de siempre (i-j-k, b por columnas) va aparte
+ * para ver cuánto cuesta el orden. En modo debug los tiempos no dicen
+ * mucho, mejor con --release
+ */
+fn comparar_matrices(mut opciones: &[&str]) -> ExitCode {
+    use operaciones::matriz::Matriz;
+    use std::time::{Duration, Instant};
+
+    let mut n: usize = 300;
+    let mut semilla = None;
+    while let [opcion, valor, resto @ ..] = opciones {
+        let valido = match *opcion {
+            "--tamanio" => valor.parse().map(|valor| n = valor).is_ok(),
+            "--semilla" => valor.parse().map(|valor| semilla = Some(valor)).is_ok(),
+            _ => false,
+        };
+        if !valido {
+            break;
+        }
+        opciones = resto;
+    }
+    if !opciones.is_empty() || n == 0 {
+        eprintln!("{USO}");
+        return ExitCode::from(2);
+    }
+
+    let semilla = semilla.unwrap_or_else(aleatorio::Aleatorio::semilla_del_reloj);
+    let mut aleatorio = aleatorio::Aleatorio::new(semilla);
+    let mut anidada = || -> Vec<Vec<f64>> {
+        (0..n)
+            .map(|_| (0..n).map(|_| aleatorio.normal(0.0, 1.0)).collect())
+            .collect()
+    };
+    let (a, b) = (anidada(), anidada());
+    let cronometrar = |multiplicar: &dyn Fn() -> Vec<f64>| -> (Duration, Vec<f64>) {
+        let inicio = Instant::now();
+        let resultado = multiplicar();
+        (inicio.elapsed(), resultado)
+    };
+
+    let (tiempo_anidado, esperado) = cronometrar(&|| {
+        let mut c = vec![vec![0.0; n]; n];
+        for i in 0..n {
+            for k in 0..n {
+                let aik = a[i][k];
+                for (cij, bkj) in c[i].iter_mut().zip(&b[k]) {
+                    *cij += aik * bkj;
+                }
+            }
+        }
+        c.concat()
+    });
+    let (tiempo_ingenuo, ingenuo) = cronometrar(&|| {
+        let mut c = vec![vec![0.0; n]; n];
+        for i in 0..n {
+            for j in 0..n {
+                for k in 0..n {
+                    c[i][j] += a[i][k] * b[k][j];
+                }
+            }
+        }
+        c.concat()
+    });
+    let (ma, mb) = (
+        Matriz::desde_filas(a.clone()).expect("las filas son iguales"),
+        Matriz::desde_filas(b.clone()).expect("las filas son iguales"),
+    );
+    let (tiempo_directo, directo) = cronometrar(&|| {
+        ma.multiplicar_directo(&mb).expect("son cuadradas").datos().to_vec()
+    });
+    let (tiempo_bloques, bloques) = cronometrar(&|| {
+        ma.multiplicar_por_bloques(&mb).expect("son cuadradas").datos().to_vec()
+    });
+
+    let diferencia = |obtenido: &[f64]| {
+        obtenido
+            .iter()
+            .zip(&esperado)
+            .map(|(x, y)| (x - y).abs())
+            .fold(0.0, f64::max)
+    };
+    println!("Multiplicando matrices de {n} x {n} (semilla {semilla})\n");
+    println!("{:<24}{:>12}", "Vec<Vec<f64>>", format!("{:.2?}", tiempo_anidado));
+    println!(
+        "{:<24}{:>12}   diferencia {:.1e}",
+        "Vec<Vec<f64>> (i-j-k)",
+        format!("{:.2?}", tiempo_ingenuo),
+        diferencia(&ingenuo)
+    );
+    println!(
+        "{:<24}{:>12}   diferencia {:.1e}",
+        "Matriz directa",
+        format!("{:.2?}", tiempo_directo),
+        diferencia(&directo)
+    );
+    println!(
+        "{:<24}{:>12}   diferencia {:.1e}",
+        "Matriz por bloques",
+        format!("{:.2?}", tiempo_bloques),
+        diferencia(&bloques)
+    );
+    ExitCode::SUCCESS
 }
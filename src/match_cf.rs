@@ -11,7 +11,9 @@
  * donde cada una va a donde corresponde
  */
 
-enum Monedas {
+// Las usa también src/monedas para contar dinero de verdad
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Monedas {
     _Cobre,
     _Plata,
     _Oro,
@@ -26,6 +28,7 @@ enum Monedas {
  *
  * A cada opción del match le llaman "arms"
  */
+#[allow(clippy::needless_return)]
 fn convertir_en_pesos(moneda: Monedas) -> u8 {
     let a = match moneda {
         Monedas::_Cobre => {
@@ -45,6 +48,8 @@ fn convertir_en_pesos(moneda: Monedas) -> u8 {
  */
 
 enum Mascotas {
+    // El match de abajo la necesita aunque main sólo pase un gato
+    #[allow(dead_code)]
     Ninguna,
     Gato(String),
 }
@@ -52,12 +57,14 @@ enum Mascotas {
 fn mostrar_mascota(mascota: Mascotas) {
     // Si omitimos algun enumerable el compilador nos va a dar error
     match mascota {
-        Mascotas::Ninguna => println!("Te conseguiremos un gato"),
-        Mascotas::Gato(color) => println!("Tienes un gato {}", color),
+        Mascotas::Ninguna => println!("{}", mensaje!("match_cf.sin_mascota")),
+        Mascotas::Gato(color) => println!("{}", mensaje!("match_cf.gato", color)),
     }
 }
 
-// De esta forma podemos obtener los datos genéricos de algun Option
+// De esta forma podemos obtener los datos genéricos de algun Option,
+// clippy sugiere x.map(|i| i + 1) pero aquí el punto es el match
+#[allow(clippy::manual_map)]
 fn plus_one(x: Option<i32>) -> Option<i32> {
     match x {
         None => None,
@@ -71,16 +78,29 @@ fn plus_one(x: Option<i32>) -> Option<i32> {
  */
 fn eres_millonario(moneda: Monedas){
     match moneda {
-        Monedas::_Oro => println!("Eres millonario"),
+        Monedas::_Oro => println!("{}", mensaje!("match_cf.millonario")),
         // other => println!("No eres millonario"),
-        _ => println!("No eres millonario"),
+        _ => println!("{}", mensaje!("match_cf.no_millonario")),
     }
 }
 
 
+use crate::lecciones::{Entrada, Leccion};
+use crate::mensajes::mensaje;
+
+pub const LECCION: Leccion = Leccion {
+    nombre: "match_cf",
+    titulo: "Control de flujo con match",
+    orden: 8,
+    entrada: Entrada::Simple(main),
+    ejercicios: &[],
+    preguntas: include_str!("preguntas/match_cf.txt"),
+    fuente: include_str!("match_cf.rs"),
+};
+
 pub fn main() {
     let moneda = Monedas::_Cobre;
-    println!("Conversión: {}", convertir_en_pesos(moneda));
+    println!("{}", mensaje!("match_cf.conversion", convertir_en_pesos(moneda)));
 
     let mascota = Mascotas::Gato(String::from("Amarillo"));
     mostrar_mascota(mascota);
@@ -95,6 +115,8 @@ pub fn main() {
     let var = "Hola";
     //Match también admite otros tipos, pero no pude hacer que
     //buscara match con String
+    // other no se usa, sólo muestra que el último arm atrapa lo demás
+    #[allow(unused_variables)]
     let var = match var {
         "Hola" => true,
         other => false,
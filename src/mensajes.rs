@@ -0,0 +1,427 @@
+/* Catálogo de mensajes en español e inglés. Los textos que muestran las
+ * lecciones ya no van escritos directo en el println!, se piden por una
+ * clave con la macro mensaje! y se buscan en la tabla del idioma elegido:
+ *
+ *   println!("{}", mensaje!("match_cf.gato", color));
+ *
+ * Los {} de cada texto se reemplazan en orden por los valores que se
+ * pasen. El idioma se elige con --idioma en la terminal o con la variable
+ * RUST_NOTAS_IDIOMA (es o en). Si a una tabla le falta una clave se usa
+ * el texto en español, y el comando `mensajes` dice qué claves faltan.
+ *
+ * Algunos textos de las lecciones ya estaban en inglés porque venían del
+ * libro de Rust, en la tabla en español se dejan igual para no cambiar la
+ * salida de siempre
+ */
+
+use std::env;
+use std::fmt::Display;
+use std::sync::OnceLock;
+
+pub const VARIABLE: &str = "RUST_NOTAS_IDIOMA";
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Idioma {
+    Es,
+    En,
+}
+
+impl Idioma {
+    pub fn desde_codigo(codigo: &str) -> Option<Self> {
+        match codigo.trim().to_lowercase().as_str() {
+            "es" => Some(Idioma::Es),
+            "en" => Some(Idioma::En),
+            _ => None,
+        }
+    }
+
+    fn tabla(self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Idioma::Es => ES,
+            Idioma::En => EN,
+        }
+    }
+}
+
+// Se lee una sola vez, main ya puso el valor de --idioma en la variable
+pub fn idioma() -> Idioma {
+    static IDIOMA: OnceLock<Idioma> = OnceLock::new();
+    *IDIOMA.get_or_init(|| {
+        env::var(VARIABLE)
+            .ok()
+            .and_then(|codigo| Idioma::desde_codigo(&codigo))
+            .unwrap_or(Idioma::Es)
+    })
+}
+
+fn buscar(tabla: &[(&str, &'static str)], clave: &str) -> Option<&'static str> {
+    tabla
+        .iter()
+        .find(|(otra, _)| *otra == clave)
+        .map(|(_, texto)| *texto)
+}
+
+// Si la clave no está en ninguna tabla se muestra la clave, así se nota
+pub fn texto(clave: &'static str) -> &'static str {
+    buscar(idioma().tabla(), clave)
+        .or_else(|| buscar(ES, clave))
+        .unwrap_or(clave)
+}
+
+pub fn formatear(plantilla: &str, valores: &[&dyn Display]) -> String {
+    let mut resultado = String::new();
+    let mut valores = valores.iter();
+    let mut partes = plantilla.split("{}");
+    resultado.push_str(partes.next().unwrap_or_default());
+    for parte in partes {
+        match valores.next() {
+            Some(valor) => resultado.push_str(&valor.to_string()),
+            None => resultado.push_str("{}"),
+        }
+        resultado.push_str(parte);
+    }
+    resultado
+}
+
+macro_rules! mensaje {
+    ($clave:expr) => {
+        $crate::mensajes::texto($clave).to_string()
+    };
+    ($clave:expr, $($valor:expr),+ $(,)?) => {
+        $crate::mensajes::formatear($crate::mensajes::texto($clave), &[$(&$valor),+])
+    };
+}
+pub(crate) use mensaje;
+
+/* Problemas del catálogo: claves que faltan en alguna tabla y textos
+ * cuya traducción no tiene la misma cantidad de {}
+ */
+pub fn problemas() -> Vec<String> {
+    let mut problemas = Vec::new();
+    for (tabla, nombre, otra, nombre_otra) in [(ES, "es", EN, "en"), (EN, "en", ES, "es")] {
+        for (clave, _) in tabla {
+            if buscar(otra, clave).is_none() {
+                problemas.push(format!(
+                    "falta la clave \"{clave}\" en {nombre_otra} (está en {nombre})"
+                ));
+            }
+        }
+    }
+    for (clave, texto) in ES {
+        if let Some(traduccion) = buscar(EN, clave) {
+            if texto.matches("{}").count() != traduccion.matches("{}").count() {
+                problemas.push(format!(
+                    "la clave \"{clave}\" no tiene los mismos {{}} en es y en"
+                ));
+            }
+        }
+    }
+    problemas
+}
+
+const ES: &[(&str, &str)] = &[
+    // pedir.rs
+    (
+        "pedir.fin_de_entrada",
+        "se terminó la entrada antes de obtener una respuesta",
+    ),
+    ("pedir.no_entendi", "No entendí \"{}\", intenta de nuevo"),
+    (
+        "pedir.fuera_de_rango",
+        "{} está fuera del rango, debe ser de {} a {}",
+    ),
+    ("pedir.si_por_defecto", "[S/n]"),
+    ("pedir.no_por_defecto", "[s/N]"),
+    ("pedir.responde_si_o_no", "Responde s o n"),
+    ("pedir.opcion", "Opción: "),
+    ("pedir.sin_opciones", "No hay opciones para elegir"),
+    // tipos_datos.rs
+    ("tipos_datos.cadena", "Cadena: {}"),
+    ("tipos_datos.push", "push: {}"),
+    ("tipos_datos.push_str", "Push str: {}"),
+    ("tipos_datos.pop", "eliminar caracteres con pop: {}"),
+    (
+        "tipos_datos.inicializar",
+        "Crear una cadena e inicializarla: {}",
+    ),
+    ("tipos_datos.desde_str", "Cadena String de un str: {}"),
+    // leer_de_consola.rs
+    ("leer_de_consola.ingresa_numero", "Ingresa un número: "),
+    (
+        "leer_de_consola.no_es_numero",
+        "Eso no es un número, ingresa un número: ",
+    ),
+    ("leer_de_consola.entrada", "Entrada: {}"),
+    (
+        "leer_de_consola.ingresa_numero_sin_salto",
+        "Ingresa un numero: ",
+    ),
+    ("leer_de_consola.dato_ingresado", "Dato ingresado: "),
+    ("leer_de_consola.edad", "¿Cuántos años tienes? "),
+    ("leer_de_consola.veces", "¿Cuántas veces te saludo? "),
+    ("leer_de_consola.gritar", "¿Te saludo gritando? "),
+    ("leer_de_consola.color", "¿Cuál es tu color favorito?"),
+    ("leer_de_consola.rojo", "Rojo"),
+    ("leer_de_consola.verde", "Verde"),
+    ("leer_de_consola.azul", "Azul"),
+    (
+        "leer_de_consola.saludo_gritando",
+        "¡HOLA! TIENES {} AÑOS Y TE GUSTA EL {}",
+    ),
+    (
+        "leer_de_consola.saludo",
+        "Hola, tienes {} años y te gusta el {}",
+    ),
+    // estructuras_de_control.rs
+    (
+        "estructuras_de_control.funcion_custom",
+        "Esta es una función custom",
+    ),
+    ("estructuras_de_control.suma", "Suma de arg1 y arg2: {}"),
+    ("estructuras_de_control.hola", "Hola"),
+    ("estructuras_de_control.funciones", "\nFUNCIONES\n"),
+    ("estructuras_de_control.valor_de_var", "Valor de var: {}"),
+    ("estructuras_de_control.expresion_if", "\nEXPRESIÓN IF\n"),
+    ("estructuras_de_control.si", "Si"),
+    ("estructuras_de_control.no", "No"),
+    (
+        "estructuras_de_control.divisible_entre_4",
+        "number is divisible by 4",
+    ),
+    (
+        "estructuras_de_control.divisible_entre_3",
+        "number is divisible by 3",
+    ),
+    (
+        "estructuras_de_control.divisible_entre_2",
+        "number is divisible by 2",
+    ),
+    (
+        "estructuras_de_control.no_divisible",
+        "number is not divisible by 4, 3, or 2",
+    ),
+    (
+        "estructuras_de_control.valor_de_number",
+        "The value of number is: {}",
+    ),
+    ("estructuras_de_control.bucle_loop", "\nBUCLE LOOP\n"),
+    ("estructuras_de_control.bucle_while", "\nBUCLE WHILE\n"),
+    ("estructuras_de_control.iteracion", "Iteracion {}: {}"),
+    ("estructuras_de_control.fuera_del_while", "Fuera del while"),
+    ("estructuras_de_control.bucle_for", "\nBUCLE FOR\n"),
+    // structs.rs
+    ("structs.vuela", "{} vuela a {} km/h"),
+    ("structs.presentacion", "Hola soy {} y tengo {} años"),
+    ("structs.color_rgb", "Color rgb: r: {}, g: {}, b: {}"),
+    // enums.rs
+    ("enums.hacer_algo", "Hacer algo"),
+    ("enums.ping", "haciendo ping"),
+    // match_cf.rs
+    ("match_cf.sin_mascota", "Te conseguiremos un gato"),
+    ("match_cf.gato", "Tienes un gato {}"),
+    ("match_cf.millonario", "Eres millonario"),
+    ("match_cf.no_millonario", "No eres millonario"),
+    ("match_cf.conversion", "Conversión: {}"),
+    // modulos.rs
+    ("modulos.suma", "Suma: {}, {}"),
+    ("modulos.suma_vector", "Suma: {}"),
+    // collections.rs
+    ("collections.vector", "Vector: {}"),
+    ("collections.obtener_datos", "\nObtener datos de un vector"),
+    ("collections.tercer_elemento", "The third element is {}"),
+    (
+        "collections.sin_tercer_elemento",
+        "There is no third element.",
+    ),
+    ("collections.for_en_vector", "\nFor en un vector"),
+    ("collections.vector_completo", "Vector {}\n"),
+    ("collections.elemento", "Elemento {}: {}"),
+    (
+        "collections.cambiar_en_for",
+        "\nCambiar valores en un for: ",
+    ),
+    (
+        "collections.varios_tipos",
+        "\nDiferentes tipos de dato en un vector",
+    ),
+    ("collections.entero", "El entero almacenado es: {}"),
+    ("collections.flotante", "El flotante almacenado es: {}"),
+    ("collections.texto", "El texto almacenado es: {}"),
+    // hashmaps.rs
+    ("hashmaps.dato", "Dato: {}"),
+    ("hashmaps.sin_dato", "No existe dato con ese hash"),
+    // control_de_errores.rs
+    (
+        "control_de_errores.sin_valor",
+        "No hay valor, proporcionando uno predeterminado",
+    ),
+    ("control_de_errores.resultado", "Resultado: {}"),
+    ("control_de_errores.maybe_value", "Maybe_Value: {}"),
+    // generics.rs
+    ("generics.haciendo_algo", "Haciendo algo"),
+    // traits.rs
+    ("traits.hace_sonido", "{} Hace sonido"),
+    ("traits.sonido_pinguino", "Sonido Pinguino"),
+    ("traits.secandose", "{} se está secando"),
+    ("traits.nadando", "{} está nadando"),
+    ("traits.corre", "{} corre rápidamente"),
+    ("traits.nombre", "Nombre: {}"),
+    ("traits.nombre_cientifico", "Nombre cientifico: {}"),
+    ("traits.peso_y_alas", "Peso: {}, Tamaño alas: {}"),
+    ("traits.dato", "Dato: {}"),
+];
+
+const EN: &[(&str, &str)] = &[
+    // pedir.rs
+    (
+        "pedir.fin_de_entrada",
+        "the input ended before getting an answer",
+    ),
+    ("pedir.no_entendi", "I didn't understand \"{}\", try again"),
+    (
+        "pedir.fuera_de_rango",
+        "{} is out of range, it must be from {} to {}",
+    ),
+    ("pedir.si_por_defecto", "[Y/n]"),
+    ("pedir.no_por_defecto", "[y/N]"),
+    ("pedir.responde_si_o_no", "Answer y or n"),
+    ("pedir.opcion", "Option: "),
+    ("pedir.sin_opciones", "There are no options to choose from"),
+    // tipos_datos.rs
+    ("tipos_datos.cadena", "String: {}"),
+    ("tipos_datos.push", "push: {}"),
+    ("tipos_datos.push_str", "Push str: {}"),
+    ("tipos_datos.pop", "removing characters with pop: {}"),
+    (
+        "tipos_datos.inicializar",
+        "Creating and initializing a string: {}",
+    ),
+    ("tipos_datos.desde_str", "String from a str: {}"),
+    // leer_de_consola.rs
+    ("leer_de_consola.ingresa_numero", "Enter a number: "),
+    (
+        "leer_de_consola.no_es_numero",
+        "That is not a number, enter a number: ",
+    ),
+    ("leer_de_consola.entrada", "Input: {}"),
+    (
+        "leer_de_consola.ingresa_numero_sin_salto",
+        "Enter a number: ",
+    ),
+    ("leer_de_consola.dato_ingresado", "You entered: "),
+    ("leer_de_consola.edad", "How old are you? "),
+    (
+        "leer_de_consola.veces",
+        "How many times should I greet you? ",
+    ),
+    ("leer_de_consola.gritar", "Should I shout? "),
+    ("leer_de_consola.color", "What is your favorite color?"),
+    ("leer_de_consola.rojo", "Red"),
+    ("leer_de_consola.verde", "Green"),
+    ("leer_de_consola.azul", "Blue"),
+    (
+        "leer_de_consola.saludo_gritando",
+        "HELLO! YOU ARE {} YEARS OLD AND YOU LIKE {}",
+    ),
+    (
+        "leer_de_consola.saludo",
+        "Hello, you are {} years old and you like {}",
+    ),
+    // estructuras_de_control.rs
+    (
+        "estructuras_de_control.funcion_custom",
+        "This is a custom function",
+    ),
+    ("estructuras_de_control.suma", "Sum of arg1 and arg2: {}"),
+    ("estructuras_de_control.hola", "Hello"),
+    ("estructuras_de_control.funciones", "\nFUNCTIONS\n"),
+    ("estructuras_de_control.valor_de_var", "Value of var: {}"),
+    ("estructuras_de_control.expresion_if", "\nIF EXPRESSION\n"),
+    ("estructuras_de_control.si", "Yes"),
+    ("estructuras_de_control.no", "No"),
+    (
+        "estructuras_de_control.divisible_entre_4",
+        "number is divisible by 4",
+    ),
+    (
+        "estructuras_de_control.divisible_entre_3",
+        "number is divisible by 3",
+    ),
+    (
+        "estructuras_de_control.divisible_entre_2",
+        "number is divisible by 2",
+    ),
+    (
+        "estructuras_de_control.no_divisible",
+        "number is not divisible by 4, 3, or 2",
+    ),
+    (
+        "estructuras_de_control.valor_de_number",
+        "The value of number is: {}",
+    ),
+    ("estructuras_de_control.bucle_loop", "\nLOOP\n"),
+    ("estructuras_de_control.bucle_while", "\nWHILE LOOP\n"),
+    ("estructuras_de_control.iteracion", "Iteration {}: {}"),
+    ("estructuras_de_control.fuera_del_while", "Out of the while"),
+    ("estructuras_de_control.bucle_for", "\nFOR LOOP\n"),
+    // structs.rs
+    ("structs.vuela", "{} flies at {} km/h"),
+    ("structs.presentacion", "Hi, I'm {} and I'm {} years old"),
+    ("structs.color_rgb", "RGB color: r: {}, g: {}, b: {}"),
+    // enums.rs
+    ("enums.hacer_algo", "Do something"),
+    ("enums.ping", "pinging"),
+    // match_cf.rs
+    ("match_cf.sin_mascota", "We'll get you a cat"),
+    ("match_cf.gato", "You have a {} cat"),
+    ("match_cf.millonario", "You're a millionaire"),
+    ("match_cf.no_millonario", "You're not a millionaire"),
+    ("match_cf.conversion", "Conversion: {}"),
+    // modulos.rs
+    ("modulos.suma", "Sum: {}, {}"),
+    ("modulos.suma_vector", "Sum: {}"),
+    // collections.rs
+    ("collections.vector", "Vector: {}"),
+    (
+        "collections.obtener_datos",
+        "\nReading values from a vector",
+    ),
+    ("collections.tercer_elemento", "The third element is {}"),
+    (
+        "collections.sin_tercer_elemento",
+        "There is no third element.",
+    ),
+    ("collections.for_en_vector", "\nFor over a vector"),
+    ("collections.vector_completo", "Vector {}\n"),
+    ("collections.elemento", "Element {}: {}"),
+    ("collections.cambiar_en_for", "\nChanging values in a for: "),
+    (
+        "collections.varios_tipos",
+        "\nDifferent data types in a vector",
+    ),
+    ("collections.entero", "The stored integer is: {}"),
+    ("collections.flotante", "The stored float is: {}"),
+    ("collections.texto", "The stored text is: {}"),
+    // hashmaps.rs
+    ("hashmaps.dato", "Value: {}"),
+    ("hashmaps.sin_dato", "There is no value with that hash"),
+    // control_de_errores.rs
+    (
+        "control_de_errores.sin_valor",
+        "There is no value, providing a default one",
+    ),
+    ("control_de_errores.resultado", "Result: {}"),
+    ("control_de_errores.maybe_value", "Maybe_Value: {}"),
+    // generics.rs
+    ("generics.haciendo_algo", "Doing something"),
+    // traits.rs
+    ("traits.hace_sonido", "{} makes a sound"),
+    ("traits.sonido_pinguino", "Penguin sound"),
+    ("traits.secandose", "{} is drying off"),
+    ("traits.nadando", "{} is swimming"),
+    ("traits.corre", "{} runs fast"),
+    ("traits.nombre", "Name: {}"),
+    ("traits.nombre_cientifico", "Scientific name: {}"),
+    ("traits.peso_y_alas", "Weight: {}, Wing size: {}"),
+    ("traits.dato", "Value: {}"),
+];
@@ -22,6 +22,8 @@ pub mod modulo1 {
     }
 }
 
+// Sólo se muestran las rutas, no se llama: llamarse a sí misma sería infinito
+#[allow(dead_code, unconditional_recursion)]
 pub fn hacer_algo1() {
     /* podemos llamar a una funcion de este modulo con una ruta absoluta
      * haciendo uso de la palabra reservada crate::, y como se puede ver
@@ -55,15 +57,19 @@ pub fn hacer_algo2() {
     _otrafn1();
 }
 
+// fun_random, coso1 y coso2 existen sólo para ver a dónde lleva super
+#[allow(dead_code)]
 pub fn fun_random() {}
 
 pub mod a {
+    #[allow(dead_code)]
     fn coso1() {
         // super te mueve un "nivel" arriba
         super::fun_random();
     }
 
     pub mod b{
+        #[allow(dead_code)]
         fn coso2(){
             // 1 super para salir de b, y otro para
             // salir de a
@@ -86,10 +92,31 @@ fn coso3() {
     let v2 = Vector2::new(2.0, 0.0);
     let suma_vect = operaciones::vectores::suma(v1, v2);
 
-    println!("Suma: {}, {}", suma_vect.x, suma_vect.y);
+    println!("{}", mensaje!("modulos.suma", suma_vect.x, suma_vect.y));
 
     let v1 = Vector2::new(1.0, 2.0);
     let v2 = Vector2::new(2.0, 0.0);
     let suma_vect = suma_de_vectores(v1, v2);
-    println!("Suma: {:?}", suma_vect);
+    println!("{}", mensaje!("modulos.suma_vector", format!("{:?}", suma_vect)));
+}
+
+use crate::lecciones::{Entrada, Leccion};
+use crate::mensajes::mensaje;
+
+pub const LECCION: Leccion = Leccion {
+    nombre: "modulos",
+    titulo: "Módulos",
+    orden: 9,
+    entrada: Entrada::Simple(main),
+    ejercicios: &[],
+    preguntas: "",
+    fuente: include_str!("modulos.rs"),
+};
+
+/* hacer_algo1 se llama a sí misma sin condición de salida, así que
+ * no se ejecuta aquí, sólo lo que no se queda en un ciclo infinito
+ */
+pub fn main() {
+    hacer_algo2();
+    coso3();
 }
@@ -0,0 +1,478 @@
+/* Dar cambio. La máquina de match_cf separa monedas pero no sabe
+ * devolverlas, aquí se calcula con qué monedas pagar una cantidad. Las
+ * cantidades van en la unidad más chica del sistema, para las Monedas
+ * de la lección son pesos: cobre 1, plata 10 y oro 100
+ *
+ * Hay dos estrategias:
+ *
+ * - Voraz: tomar siempre la moneda más grande que quepa. Es lo que hace
+ *   cualquiera en la tienda y con 1, 10, 100 siempre da lo mínimo, pero
+ *   con monedas de 1, 3 y 4 para pagar 6 da 4 + 1 + 1 y lo mínimo es 3 + 3
+ * - Programación dinámica: calcula lo mínimo para 0, 1, 2... hasta la
+ *   cantidad, reusando lo anterior. Siempre da lo mínimo pero usa una
+ *   tabla del tamaño de la cantidad
+ *
+ * Un sistema donde la voraz siempre da lo mínimo se llama canónico.
+ * Kozen y Zaks probaron que si no lo es hay un contraejemplo menor a la
+ * suma de las dos monedas más grandes, así que basta revisar hasta ahí
+ *
+ * Las tablas tienen un lugar por cada cantidad, con u64::MAX serían
+ * millones de GiB. Más de CANTIDAD_MAXIMA es un error antes de pedir la
+ * memoria, y ninguna moneda puede valer más de la mitad para que
+ * es_canonico quepa en la tabla
+ */
+use std::fmt;
+
+use crate::operaciones::entero_grande::BigInt;
+
+// Un millón de lugares son unos 16 MiB por tabla
+pub const CANTIDAD_MAXIMA: u64 = 1_000_000;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ErrorDenominaciones {
+    Vacias,
+    Cero,
+    Repetida(u64),
+    // El inventario debe tener una cantidad por denominación
+    InventarioIncompleto { esperadas: usize, recibidas: usize },
+    DenominacionMuyGrande(u64),
+    CantidadMuyGrande(u64),
+}
+
+impl fmt::Display for ErrorDenominaciones {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ErrorDenominaciones::Vacias => write!(f, "se necesita al menos una moneda"),
+            ErrorDenominaciones::Cero => write!(f, "no hay monedas de cero"),
+            ErrorDenominaciones::Repetida(valor) => write!(f, "la moneda de {valor} está repetida"),
+            ErrorDenominaciones::InventarioIncompleto {
+                esperadas,
+                recibidas,
+            } => write!(
+                f,
+                "el inventario tiene {recibidas} cantidades y hay {esperadas} monedas"
+            ),
+            ErrorDenominaciones::DenominacionMuyGrande(valor) => write!(
+                f,
+                "la moneda de {valor} vale más de {}",
+                CANTIDAD_MAXIMA / 2
+            ),
+            ErrorDenominaciones::CantidadMuyGrande(cantidad) => write!(
+                f,
+                "{cantidad} es más de {CANTIDAD_MAXIMA}, no cabe en la tabla"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ErrorDenominaciones {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Estrategia {
+    Voraz,
+    Dinamica,
+}
+
+/* Cuántas monedas de cada denominación, en el mismo orden que las
+ * denominaciones del sistema (de menor a mayor)
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cambio {
+    denominaciones: Vec<u64>,
+    cantidades: Vec<u64>,
+}
+
+impl Cambio {
+    pub fn cantidad_de(&self, denominacion: u64) -> u64 {
+        self.piezas()
+            .find(|&(valor, _)| valor == denominacion)
+            .map_or(0, |(_, cantidad)| cantidad)
+    }
+
+    // (denominación, cuántas) de la más grande a la más chica, sin las que no se usan
+    pub fn piezas(&self) -> impl Iterator<Item = (u64, u64)> + '_ {
+        self.denominaciones
+            .iter()
+            .zip(&self.cantidades)
+            .rev()
+            .filter(|(_, &cantidad)| cantidad > 0)
+            .map(|(&valor, &cantidad)| (valor, cantidad))
+    }
+
+    pub fn total_monedas(&self) -> u64 {
+        self.cantidades.iter().sum()
+    }
+
+    pub fn valor(&self) -> u64 {
+        self.piezas()
+            .map(|(valor, cantidad)| valor * cantidad)
+            .sum()
+    }
+}
+
+// "2 x 100 + 3 x 1", o "nada" para cero
+impl fmt::Display for Cambio {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let piezas: Vec<String> = self
+            .piezas()
+            .map(|(valor, cantidad)| format!("{cantidad} x {valor}"))
+            .collect();
+        if piezas.is_empty() {
+            write!(f, "nada")
+        } else {
+            write!(f, "{}", piezas.join(" + "))
+        }
+    }
+}
+
+// Una cantidad donde la voraz no da lo mínimo (o no encuentra cambio)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Contraejemplo {
+    pub cantidad: u64,
+    pub voraz: Option<Cambio>,
+    pub minimo: Cambio,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SistemaDeMonedas {
+    // De menor a mayor, sin repetir
+    denominaciones: Vec<u64>,
+}
+
+impl SistemaDeMonedas {
+    pub fn new(denominaciones: &[u64]) -> Result<Self, ErrorDenominaciones> {
+        let mut ordenadas = denominaciones.to_vec();
+        ordenadas.sort_unstable();
+        if ordenadas.is_empty() {
+            return Err(ErrorDenominaciones::Vacias);
+        }
+        if ordenadas[0] == 0 {
+            return Err(ErrorDenominaciones::Cero);
+        }
+        if let Some(par) = ordenadas.windows(2).find(|par| par[0] == par[1]) {
+            return Err(ErrorDenominaciones::Repetida(par[0]));
+        }
+        if let Some(&mayor) = ordenadas
+            .last()
+            .filter(|&&mayor| mayor > CANTIDAD_MAXIMA / 2)
+        {
+            return Err(ErrorDenominaciones::DenominacionMuyGrande(mayor));
+        }
+        Ok(Self {
+            denominaciones: ordenadas,
+        })
+    }
+
+    // Cobre, plata y oro: 1, 10 y 100 pesos
+    pub fn de_monedas() -> Self {
+        let pesos: Vec<u64> = super::TODAS
+            .iter()
+            .map(|&moneda| super::valor(moneda).centavos() as u64 / 100)
+            .collect();
+        Self::new(&pesos).expect("las monedas de match_cf son distintas y no valen cero")
+    }
+
+    pub fn denominaciones(&self) -> &[u64] {
+        &self.denominaciones
+    }
+
+    fn cambio(&self, cantidades: Vec<u64>) -> Cambio {
+        Cambio {
+            denominaciones: self.denominaciones.clone(),
+            cantidades,
+        }
+    }
+
+    pub fn dar_cambio(
+        &self,
+        cantidad: u64,
+        estrategia: Estrategia,
+    ) -> Result<Option<Cambio>, ErrorDenominaciones> {
+        match estrategia {
+            Estrategia::Voraz => Ok(self.voraz(cantidad)),
+            Estrategia::Dinamica => self.minimo(cantidad),
+        }
+    }
+
+    // Lo que usan las tablas de minimo, formas y con_inventario
+    fn tope(cantidad: u64) -> Result<usize, ErrorDenominaciones> {
+        if cantidad > CANTIDAD_MAXIMA {
+            return Err(ErrorDenominaciones::CantidadMuyGrande(cantidad));
+        }
+        Ok(cantidad as usize)
+    }
+
+    /* La más grande que quepa, luego la siguiente... Sin moneda de 1
+     * puede quedarse atorada aunque sí haya forma, como 6 con 4 y 3
+     */
+    pub fn voraz(&self, cantidad: u64) -> Option<Cambio> {
+        let mut resto = cantidad;
+        let mut cantidades = vec![0; self.denominaciones.len()];
+        for (i, &valor) in self.denominaciones.iter().enumerate().rev() {
+            cantidades[i] = resto / valor;
+            resto %= valor;
+        }
+        (resto == 0).then(|| self.cambio(cantidades))
+    }
+
+    /* minimas[x] es lo menos que se necesita para pagar x y ultima[x] la
+     * moneda que se usó al final, así se reconstruye yendo hacia atrás
+     */
+    pub fn minimo(&self, cantidad: u64) -> Result<Option<Cambio>, ErrorDenominaciones> {
+        let tope = Self::tope(cantidad)?;
+        let mut minimas = vec![u64::MAX; tope + 1];
+        let mut ultima = vec![0; tope + 1];
+        minimas[0] = 0;
+        for x in 1..=tope {
+            for (i, &valor) in self.denominaciones.iter().enumerate() {
+                let valor = valor as usize;
+                if valor <= x
+                    && minimas[x - valor] != u64::MAX
+                    && minimas[x - valor] + 1 < minimas[x]
+                {
+                    minimas[x] = minimas[x - valor] + 1;
+                    ultima[x] = i;
+                }
+            }
+        }
+        if minimas[tope] == u64::MAX {
+            return Ok(None);
+        }
+
+        let mut cantidades = vec![0; self.denominaciones.len()];
+        let mut x = tope;
+        while x > 0 {
+            cantidades[ultima[x]] += 1;
+            x -= self.denominaciones[ultima[x]] as usize;
+        }
+        Ok(Some(self.cambio(cantidades)))
+    }
+
+    /* De cuántas formas distintas se puede pagar, sin importar el orden.
+     * Crece muy rápido (con 1, 10 y 100 hay 561 formas de pagar 1000),
+     * por eso es un BigInt
+     */
+    pub fn formas(&self, cantidad: u64) -> Result<BigInt, ErrorDenominaciones> {
+        let tope = Self::tope(cantidad)?;
+        let mut formas = vec![BigInt::cero(); tope + 1];
+        formas[0] = BigInt::from(1u8);
+        // Una denominación a la vez para no contar 1 + 10 y 10 + 1 como distintas
+        for &valor in &self.denominaciones {
+            for x in valor as usize..=tope {
+                formas[x] = &formas[x] + &formas[x - valor as usize];
+            }
+        }
+        Ok(formas.swap_remove(tope))
+    }
+
+    /* Lo mínimo usando solo las monedas que hay, inventario[i] es cuántas
+     * hay de la denominación i (de menor a mayor). Se agrega una
+     * denominación a la vez probando usar 0, 1, 2... de ella
+     */
+    pub fn con_inventario(
+        &self,
+        cantidad: u64,
+        inventario: &[u64],
+    ) -> Result<Option<Cambio>, ErrorDenominaciones> {
+        if inventario.len() != self.denominaciones.len() {
+            return Err(ErrorDenominaciones::InventarioIncompleto {
+                esperadas: self.denominaciones.len(),
+                recibidas: inventario.len(),
+            });
+        }
+        let tope = Self::tope(cantidad)?;
+        let mut minimas = vec![u64::MAX; tope + 1];
+        minimas[0] = 0;
+        // usadas[i][x]: cuántas de la denominación i se usaron para pagar x
+        let mut usadas = Vec::with_capacity(self.denominaciones.len());
+        for (&valor, &hay) in self.denominaciones.iter().zip(inventario) {
+            let valor = valor as usize;
+            let mut nuevas = minimas.clone();
+            let mut uso = vec![0; tope + 1];
+            for x in 0..=tope {
+                for k in 1..=hay.min((x / valor) as u64) {
+                    let antes = minimas[x - k as usize * valor];
+                    if antes != u64::MAX && antes + k < nuevas[x] {
+                        nuevas[x] = antes + k;
+                        uso[x] = k;
+                    }
+                }
+            }
+            minimas = nuevas;
+            usadas.push(uso);
+        }
+        if minimas[tope] == u64::MAX {
+            return Ok(None);
+        }
+
+        let mut cantidades = vec![0; self.denominaciones.len()];
+        let mut x = tope;
+        for i in (0..self.denominaciones.len()).rev() {
+            cantidades[i] = usadas[i][x];
+            x -= usadas[i][x] as usize * self.denominaciones[i] as usize;
+        }
+        Ok(Some(self.cambio(cantidades)))
+    }
+
+    /* Ok si la voraz siempre da lo mínimo, si no el contraejemplo más
+     * chico. Solo hace falta revisar hasta la suma de las dos más grandes
+     */
+    pub fn es_canonico(&self) -> Result<(), Contraejemplo> {
+        let mut mayores = self.denominaciones.iter().rev();
+        let limite = mayores.next().copied().unwrap_or(0) + mayores.next().copied().unwrap_or(0);
+        for cantidad in 1..limite {
+            // new no deja monedas tan grandes que limite no quepa en la tabla
+            let Ok(Some(minimo)) = self.minimo(cantidad) else {
+                continue;
+            };
+            let voraz = self.voraz(cantidad);
+            if voraz.as_ref().map(Cambio::total_monedas) != Some(minimo.total_monedas()) {
+                return Err(Contraejemplo {
+                    cantidad,
+                    voraz,
+                    minimo,
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sistema(denominaciones: &[u64]) -> SistemaDeMonedas {
+        SistemaDeMonedas::new(denominaciones).unwrap()
+    }
+
+    // Todas las formas de pagar probando cada combinación, para comparar
+    fn fuerza_bruta(
+        denominaciones: &[u64],
+        cantidad: u64,
+        inventario: Option<&[u64]>,
+    ) -> (u64, Option<u64>) {
+        let Some((&valor, resto)) = denominaciones.split_last() else {
+            return if cantidad == 0 {
+                (1, Some(0))
+            } else {
+                (0, None)
+            };
+        };
+        let (inventario_resto, hay) = match inventario {
+            Some(inventario) => (Some(&inventario[..resto.len()]), inventario[resto.len()]),
+            None => (None, u64::MAX),
+        };
+        let mut formas = 0;
+        let mut minimo: Option<u64> = None;
+        for k in 0..=hay.min(cantidad / valor) {
+            let (f, m) = fuerza_bruta(resto, cantidad - k * valor, inventario_resto);
+            formas += f;
+            if let Some(m) = m {
+                minimo = Some(minimo.map_or(m + k, |actual| actual.min(m + k)));
+            }
+        }
+        (formas, minimo)
+    }
+
+    #[test]
+    fn las_monedas_de_match_cf() {
+        let monedas = SistemaDeMonedas::de_monedas();
+        assert_eq!(monedas.denominaciones(), [1, 10, 100]);
+        let cambio = monedas.voraz(273).unwrap();
+        assert_eq!(cambio.to_string(), "2 x 100 + 7 x 10 + 3 x 1");
+        assert_eq!(monedas.minimo(273), Ok(Some(cambio)));
+        assert_eq!(monedas.formas(1000), Ok(BigInt::from(561u32)));
+        assert_eq!(monedas.es_canonico(), Ok(()));
+        assert_eq!(monedas.minimo(0).unwrap().unwrap().to_string(), "nada");
+    }
+
+    #[test]
+    fn la_voraz_no_siempre_es_minima() {
+        let raro = sistema(&[4, 1, 3]);
+        assert_eq!(raro.voraz(6).unwrap().total_monedas(), 3);
+        assert_eq!(raro.minimo(6).unwrap().unwrap().to_string(), "2 x 3");
+        let contraejemplo = raro.es_canonico().unwrap_err();
+        assert_eq!(contraejemplo.cantidad, 6);
+
+        // Sin moneda de 1 la voraz se atora con 6, aunque 3 + 3 sí se puede
+        let sin_uno = sistema(&[3, 4]);
+        assert_eq!(sin_uno.voraz(6), None);
+        assert_eq!(
+            sin_uno
+                .dar_cambio(6, Estrategia::Dinamica)
+                .unwrap()
+                .unwrap()
+                .total_monedas(),
+            2
+        );
+        assert!(sin_uno.es_canonico().is_err());
+
+        assert_eq!(sistema(&[1, 5, 10, 25]).es_canonico(), Ok(()));
+        assert_eq!(
+            SistemaDeMonedas::new(&[5, 0]),
+            Err(ErrorDenominaciones::Cero)
+        );
+        assert_eq!(
+            SistemaDeMonedas::new(&[5, 5]),
+            Err(ErrorDenominaciones::Repetida(5))
+        );
+    }
+
+    #[test]
+    fn coincide_con_fuerza_bruta() {
+        let mut aleatorio = crate::aleatorio::Aleatorio::new(17);
+        for _ in 0..100 {
+            let mut denominaciones: Vec<u64> = (0..=aleatorio.hasta(3))
+                .map(|_| aleatorio.hasta(12) + 1)
+                .collect();
+            denominaciones.sort_unstable();
+            denominaciones.dedup();
+            let inventario: Vec<u64> = denominaciones.iter().map(|_| aleatorio.hasta(5)).collect();
+            let sistema = sistema(&denominaciones);
+            let cantidad = aleatorio.hasta(40);
+
+            let (formas, minimo) = fuerza_bruta(&denominaciones, cantidad, None);
+            assert_eq!(sistema.formas(cantidad), Ok(BigInt::from(formas)));
+            assert_eq!(
+                sistema.minimo(cantidad).unwrap().map(|c| c.total_monedas()),
+                minimo
+            );
+
+            let (_, limitado) = fuerza_bruta(&denominaciones, cantidad, Some(&inventario));
+            let cambio = sistema.con_inventario(cantidad, &inventario).unwrap();
+            assert_eq!(cambio.as_ref().map(Cambio::total_monedas), limitado);
+            if let Some(cambio) = cambio {
+                assert_eq!(cambio.valor(), cantidad);
+                for (i, &valor) in denominaciones.iter().enumerate() {
+                    assert!(cambio.cantidad_de(valor) <= inventario[i]);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn cantidades_que_no_caben_en_la_tabla() {
+        let monedas = SistemaDeMonedas::de_monedas();
+        let muy_grande = ErrorDenominaciones::CantidadMuyGrande(u64::MAX);
+        assert_eq!(monedas.minimo(u64::MAX), Err(muy_grande.clone()));
+        assert_eq!(monedas.formas(u64::MAX), Err(muy_grande.clone()));
+        assert_eq!(
+            monedas.con_inventario(u64::MAX, &[1, 1, 1]),
+            Err(muy_grande.clone())
+        );
+        assert_eq!(
+            monedas.dar_cambio(u64::MAX, Estrategia::Dinamica),
+            Err(muy_grande)
+        );
+        // La voraz no necesita tabla
+        let voraz = monedas.dar_cambio(u64::MAX, Estrategia::Voraz).unwrap();
+        assert_eq!(voraz.map(|cambio| cambio.valor()), Some(u64::MAX));
+
+        assert!(monedas.minimo(CANTIDAD_MAXIMA).unwrap().is_some());
+        assert_eq!(
+            SistemaDeMonedas::new(&[1, u64::MAX]),
+            Err(ErrorDenominaciones::DenominacionMuyGrande(u64::MAX))
+        );
+    }
+}
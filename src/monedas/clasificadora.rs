@@ -0,0 +1,399 @@
+/* La máquina que ordena monedas del principio de match_cf, pero de a
+ * de veras: "todas las monedas entran por la misma entrada" y se miden
+ * su peso y su diámetro. Ninguna moneda sale de la fábrica exacta, así
+ * que cada regla acepta un rango alrededor de la medida oficial y un
+ * match con guardas decide a qué contenedor va. Lo que no cabe en
+ * ninguna regla se va al contenedor de rechazo
+ *
+ * Para probar la máquina se fabrican lotes con ruido (una normal con la
+ * semilla de Aleatorio) y algunas fichas falsas, y se compara a dónde
+ * fue cada moneda con lo que era en realidad
+ */
+use std::collections::HashMap;
+use std::fmt;
+
+use super::{Dinero, Divisa, TODAS};
+use crate::aleatorio::Aleatorio;
+use crate::match_cf::Monedas;
+
+// Lo que mide la máquina
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Medidas {
+    pub peso_gramos: f64,
+    pub diametro_mm: f64,
+}
+
+// Medidas oficiales, parecidas a las monedas de 1, 10 y 100 pesos
+pub fn especificacion(moneda: Monedas) -> Medidas {
+    let (peso_gramos, diametro_mm) = match moneda {
+        Monedas::_Cobre => (3.95, 21.0),
+        Monedas::_Plata => (10.33, 28.0),
+        Monedas::_Oro => (33.97, 39.0),
+    };
+    Medidas {
+        peso_gramos,
+        diametro_mm,
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Origen {
+    Autentica(Monedas),
+    Falsa,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MonedaFisica {
+    pub origen: Origen,
+    pub medidas: Medidas,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Contenedor {
+    Moneda(Monedas),
+    Rechazo,
+}
+
+impl fmt::Display for Contenedor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Contenedor::Moneda(moneda) => f.pad(super::nombre(*moneda)),
+            Contenedor::Rechazo => f.pad("rechazo"),
+        }
+    }
+}
+
+/* Acepta lo que esté a una tolerancia relativa de la medida oficial,
+ * 0.05 es ±5%
+ */
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Regla {
+    pub moneda: Monedas,
+    pub tolerancia_peso: f64,
+    pub tolerancia_diametro: f64,
+}
+
+impl Regla {
+    pub fn acepta(&self, medidas: &Medidas) -> bool {
+        let oficial = especificacion(self.moneda);
+        let cerca = |medido: f64, esperado: f64, tolerancia: f64| {
+            (medido - esperado).abs() <= esperado * tolerancia
+        };
+        cerca(
+            medidas.peso_gramos,
+            oficial.peso_gramos,
+            self.tolerancia_peso,
+        ) && cerca(
+            medidas.diametro_mm,
+            oficial.diametro_mm,
+            self.tolerancia_diametro,
+        )
+    }
+}
+
+pub struct Clasificadora {
+    cobre: Regla,
+    plata: Regla,
+    oro: Regla,
+}
+
+impl Clasificadora {
+    // La misma tolerancia para todas las monedas
+    pub fn new(tolerancia_peso: f64, tolerancia_diametro: f64) -> Self {
+        let regla = |moneda| Regla {
+            moneda,
+            tolerancia_peso,
+            tolerancia_diametro,
+        };
+        Self {
+            cobre: regla(Monedas::_Cobre),
+            plata: regla(Monedas::_Plata),
+            oro: regla(Monedas::_Oro),
+        }
+    }
+
+    pub fn clasificar(&self, medidas: &Medidas) -> Contenedor {
+        // Las monedas se revisan de la más valiosa a la menos valiosa
+        match medidas {
+            m if self.oro.acepta(m) => Contenedor::Moneda(Monedas::_Oro),
+            m if self.plata.acepta(m) => Contenedor::Moneda(Monedas::_Plata),
+            m if self.cobre.acepta(m) => Contenedor::Moneda(Monedas::_Cobre),
+            _ => Contenedor::Rechazo,
+        }
+    }
+
+    pub fn procesar(&self, lote: &[MonedaFisica]) -> Reporte {
+        let mut reporte = Reporte::default();
+        for moneda in lote {
+            let contenedor = self.clasificar(&moneda.medidas);
+            *reporte
+                .resultados
+                .entry((moneda.origen, contenedor))
+                .or_insert(0) += 1;
+        }
+        reporte
+    }
+}
+
+// ±5% de peso y ±3% de diámetro
+impl Default for Clasificadora {
+    fn default() -> Self {
+        Self::new(0.05, 0.03)
+    }
+}
+
+/* Cómo sale un lote de la fábrica: qué tanto varían las monedas (una
+ * desviación relativa, 0.02 es 2%) y qué parte son fichas falsas
+ */
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Fabrica {
+    pub ruido: f64,
+    pub proporcion_falsas: f64,
+}
+
+impl Default for Fabrica {
+    fn default() -> Self {
+        Self {
+            ruido: 0.015,
+            proporcion_falsas: 0.05,
+        }
+    }
+}
+
+impl Fabrica {
+    pub fn fabricar(&self, cantidad: usize, aleatorio: &mut Aleatorio) -> Vec<MonedaFisica> {
+        (0..cantidad)
+            .map(|_| {
+                if aleatorio.decimal() < self.proporcion_falsas {
+                    // Una ficha cualquiera, puede caer cerca de una moneda de verdad
+                    return MonedaFisica {
+                        origen: Origen::Falsa,
+                        medidas: Medidas {
+                            peso_gramos: 2.0 + aleatorio.decimal() * 38.0,
+                            diametro_mm: 15.0 + aleatorio.decimal() * 27.0,
+                        },
+                    };
+                }
+                let moneda = TODAS[aleatorio.hasta(TODAS.len() as u64) as usize];
+                let oficial = especificacion(moneda);
+                MonedaFisica {
+                    origen: Origen::Autentica(moneda),
+                    medidas: Medidas {
+                        peso_gramos: aleatorio
+                            .normal(oficial.peso_gramos, oficial.peso_gramos * self.ruido),
+                        diametro_mm: aleatorio
+                            .normal(oficial.diametro_mm, oficial.diametro_mm * self.ruido),
+                    },
+                }
+            })
+            .collect()
+    }
+}
+
+// Cuántas monedas de cada origen terminaron en cada contenedor
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Reporte {
+    resultados: HashMap<(Origen, Contenedor), usize>,
+}
+
+impl Reporte {
+    pub fn cuantas(&self, origen: Origen, contenedor: Contenedor) -> usize {
+        self.resultados
+            .get(&(origen, contenedor))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    fn sumar(&self, filtro: impl Fn(Origen, Contenedor) -> bool) -> usize {
+        self.resultados
+            .iter()
+            .filter(|((origen, contenedor), _)| filtro(*origen, *contenedor))
+            .map(|(_, cantidad)| cantidad)
+            .sum()
+    }
+
+    pub fn total_monedas(&self) -> usize {
+        self.sumar(|_, _| true)
+    }
+
+    pub fn en_contenedor(&self, contenedor: Contenedor) -> usize {
+        self.sumar(|_, destino| destino == contenedor)
+    }
+
+    // Auténticas en su contenedor y falsas en rechazo
+    pub fn correctas(&self) -> usize {
+        self.sumar(|origen, contenedor| match (origen, contenedor) {
+            (Origen::Autentica(moneda), Contenedor::Moneda(destino)) => moneda == destino,
+            (Origen::Falsa, Contenedor::Rechazo) => true,
+            _ => false,
+        })
+    }
+
+    pub fn autenticas_rechazadas(&self) -> usize {
+        self.sumar(|origen, contenedor| {
+            matches!(
+                (origen, contenedor),
+                (Origen::Autentica(_), Contenedor::Rechazo)
+            )
+        })
+    }
+
+    pub fn falsas_aceptadas(&self) -> usize {
+        self.sumar(|origen, contenedor| {
+            matches!((origen, contenedor), (Origen::Falsa, Contenedor::Moneda(_)))
+        })
+    }
+
+    // Auténticas que fueron a dar al contenedor de otra moneda
+    pub fn confundidas(&self) -> usize {
+        self.sumar(|origen, contenedor| match (origen, contenedor) {
+            (Origen::Autentica(moneda), Contenedor::Moneda(destino)) => moneda != destino,
+            _ => false,
+        })
+    }
+
+    // Lo que la máquina cree que juntó, según los contenedores
+    pub fn total_contado(&self) -> Dinero {
+        self.total(|_, contenedor| match contenedor {
+            Contenedor::Moneda(moneda) => Some(moneda),
+            Contenedor::Rechazo => None,
+        })
+    }
+
+    // Lo que en verdad entró, contando las auténticas que se rechazaron
+    pub fn total_real(&self) -> Dinero {
+        self.total(|origen, _| match origen {
+            Origen::Autentica(moneda) => Some(moneda),
+            Origen::Falsa => None,
+        })
+    }
+
+    fn total(&self, valor: impl Fn(Origen, Contenedor) -> Option<Monedas>) -> Dinero {
+        self.resultados
+            .iter()
+            .filter_map(|(&(origen, contenedor), &cantidad)| {
+                valor(origen, contenedor).map(|moneda| super::valor(moneda) * cantidad as i64)
+            })
+            .fold(Dinero::cero(Divisa::MXN), |suma, valor| suma + valor)
+    }
+}
+
+impl fmt::Display for Reporte {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // Falsas son las fichas que terminaron en cada contenedor
+        writeln!(
+            f,
+            "{:<10} {:>8} {:>7} {:>12}",
+            "Contenedor", "Monedas", "Falsas", "Pesos"
+        )?;
+        for moneda in TODAS {
+            let contenedor = Contenedor::Moneda(moneda);
+            let cantidad = self.en_contenedor(contenedor);
+            let falsas = self.cuantas(Origen::Falsa, contenedor);
+            let pesos = super::valor(moneda) * cantidad as i64;
+            writeln!(f, "{contenedor:<10} {cantidad:>8} {falsas:>7} {pesos:>12}")?;
+        }
+        writeln!(
+            f,
+            "{:<10} {:>8} {:>7}",
+            Contenedor::Rechazo,
+            self.en_contenedor(Contenedor::Rechazo),
+            self.cuantas(Origen::Falsa, Contenedor::Rechazo)
+        )?;
+
+        let total = self.total_monedas();
+        let porcentaje = if total == 0 {
+            0.0
+        } else {
+            self.correctas() as f64 * 100.0 / total as f64
+        };
+        writeln!(
+            f,
+            "\nTotal contado: {} (en realidad entraron {})",
+            self.total_contado(),
+            self.total_real()
+        )?;
+        writeln!(
+            f,
+            "Bien clasificadas: {} de {total} ({porcentaje:.1}%)",
+            self.correctas()
+        )?;
+        writeln!(f, "Auténticas rechazadas: {}", self.autenticas_rechazadas())?;
+        writeln!(f, "Falsas aceptadas: {}", self.falsas_aceptadas())?;
+        write!(f, "Confundidas con otra moneda: {}", self.confundidas())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clasifica_por_medidas() {
+        let maquina = Clasificadora::default();
+        for moneda in TODAS {
+            assert_eq!(
+                maquina.clasificar(&especificacion(moneda)),
+                Contenedor::Moneda(moneda)
+            );
+        }
+        // 10% más pesada que una de plata ya no pasa
+        let pesada = Medidas {
+            peso_gramos: 10.33 * 1.1,
+            diametro_mm: 28.0,
+        };
+        assert_eq!(maquina.clasificar(&pesada), Contenedor::Rechazo);
+        assert_eq!(
+            Clasificadora::new(0.2, 0.03).clasificar(&pesada),
+            Contenedor::Moneda(Monedas::_Plata)
+        );
+    }
+
+    #[test]
+    fn un_lote_con_la_misma_semilla_da_lo_mismo() {
+        let fabrica = Fabrica::default();
+        let lote = fabrica.fabricar(2000, &mut Aleatorio::new(18));
+        assert_eq!(lote, fabrica.fabricar(2000, &mut Aleatorio::new(18)));
+
+        let reporte = Clasificadora::default().procesar(&lote);
+        assert_eq!(reporte.total_monedas(), 2000);
+        let falsas: usize = lote.iter().filter(|m| m.origen == Origen::Falsa).count();
+        assert!((50..150).contains(&falsas), "{falsas}");
+        // Con 1.5% de ruido casi todo cae dentro de ±5% y ±3%
+        assert!(reporte.correctas() > 1900, "{reporte}");
+        assert_eq!(
+            reporte.correctas()
+                + reporte.autenticas_rechazadas()
+                + reporte.falsas_aceptadas()
+                + reporte.confundidas(),
+            2000
+        );
+        let autenticas: Vec<Monedas> = lote
+            .iter()
+            .filter_map(|moneda| match moneda.origen {
+                Origen::Autentica(moneda) => Some(moneda),
+                Origen::Falsa => None,
+            })
+            .collect();
+        assert_eq!(
+            reporte.total_real(),
+            super::super::total(&autenticas).unwrap()
+        );
+    }
+
+    #[test]
+    fn mas_ruido_mas_rechazos() {
+        let lote = |ruido| {
+            let fabrica = Fabrica {
+                ruido,
+                proporcion_falsas: 0.0,
+            };
+            Clasificadora::default().procesar(&fabrica.fabricar(1000, &mut Aleatorio::new(7)))
+        };
+        let poco = lote(0.005);
+        let mucho = lote(0.05);
+        assert_eq!(poco.autenticas_rechazadas(), 0);
+        assert!(mucho.autenticas_rechazadas() > 200);
+        assert_eq!(poco.total_contado(), poco.total_real());
+    }
+}
@@ -0,0 +1,632 @@
+/* Dinero con punto fijo. Con f64 0.10 + 0.20 no da 0.30 exacto y en
+ * dinero eso no se vale, así que la cantidad se guarda en centavos
+ * dentro de un i64 junto con el código de la divisa ("MXN", "USD"...).
+ * $1,234.50 MXN es Dinero { centavos: 123450, divisa: MXN }
+ *
+ * "Centavos" es la unidad más chica de la divisa: casi todas tienen dos
+ * decimales, pero el yen no tiene ninguno (150 JPY son 150 "centavos") y
+ * el dinar de Kuwait tiene tres. Divisa::decimales dice cuántos
+ *
+ * Sumar y restar es exacto, multiplicar por una cantidad entera también.
+ * Lo único que puede perder centavos es dividir: 10 pesos entre 3 no da
+ * un número exacto de centavos. Para eso hay dos caminos:
+ *
+ * - dividir redondea al par más cercano (redondeo bancario), así 0.5
+ *   centavos a veces sube y a veces baja y en promedio no se sesga
+ * - repartir da a cada parte su pedazo y los centavos que sobran se
+ *   dan de uno en uno, así la suma de las partes es el total exacto.
+ *   Cada parte es un Dinero en el Vec, así que más de PARTES_MAXIMAS es
+ *   un error antes de pedir la memoria
+ *
+ * Los métodos devuelven Result, los operadores + - * hacen panic si se
+ * mezclan divisas o se desborda, igual que Racional
+ */
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Mul, Neg, Sub};
+use std::str::FromStr;
+
+// Un millón de partes son unos 16 MiB
+pub const PARTES_MAXIMAS: usize = 1_000_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Divisa([u8; 3]);
+
+impl Divisa {
+    pub const MXN: Divisa = Divisa(*b"MXN");
+    pub const USD: Divisa = Divisa(*b"USD");
+    pub const EUR: Divisa = Divisa(*b"EUR");
+
+    // Un código ISO 4217 son tres letras mayúsculas
+    pub fn new(codigo: &str) -> Result<Self, ErrorDinero> {
+        match codigo.as_bytes() {
+            [a, b, c] if codigo.bytes().all(|letra| letra.is_ascii_uppercase()) => {
+                Ok(Divisa([*a, *b, *c]))
+            }
+            _ => Err(ErrorDinero::CodigoInvalido(codigo.to_string())),
+        }
+    }
+
+    // Según ISO 4217, las que no están aquí tienen dos
+    pub fn decimales(&self) -> u32 {
+        match &self.0 {
+            b"JPY" | b"KRW" | b"CLP" | b"ISK" | b"PYG" | b"VND" | b"UGX" | b"XAF" | b"XOF" => 0,
+            b"BHD" | b"IQD" | b"JOD" | b"KWD" | b"LYD" | b"OMR" | b"TND" => 3,
+            _ => 2,
+        }
+    }
+
+    // Cuántos centavos tiene una unidad: 100 para MXN, 1 para JPY
+    pub fn centavos_por_unidad(&self) -> i64 {
+        10_i64.pow(self.decimales())
+    }
+
+    pub fn codigo(&self) -> &str {
+        // Siempre son letras ASCII, new no deja pasar otra cosa
+        std::str::from_utf8(&self.0).unwrap_or("???")
+    }
+
+    // Cómo se escribe normalmente una cantidad en esta divisa
+    pub fn formato(&self) -> Formato {
+        match *self {
+            Divisa::MXN | Divisa::USD => Formato::DOLAR,
+            Divisa::EUR => Formato::EURO,
+            _ => Formato::CODIGO,
+        }
+    }
+}
+
+impl fmt::Display for Divisa {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(self.codigo())
+    }
+}
+
+impl FromStr for Divisa {
+    type Err = ErrorDinero;
+
+    fn from_str(texto: &str) -> Result<Self, Self::Err> {
+        Divisa::new(texto.trim())
+    }
+}
+
+/* Las costumbres de cada lugar para escribir cantidades: en México y
+ * Estados Unidos es $1,234.50 y en buena parte de Europa 1.234,50 €
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Formato {
+    pub simbolo: &'static str,
+    pub simbolo_al_final: bool,
+    pub separador_miles: char,
+    pub separador_decimal: char,
+}
+
+impl Formato {
+    pub const DOLAR: Formato = Formato {
+        simbolo: "$",
+        simbolo_al_final: false,
+        separador_miles: ',',
+        separador_decimal: '.',
+    };
+    pub const EURO: Formato = Formato {
+        simbolo: "€",
+        simbolo_al_final: true,
+        separador_miles: '.',
+        separador_decimal: ',',
+    };
+    // Para divisas sin símbolo conocido, Display le pone el código al final
+    pub const CODIGO: Formato = Formato {
+        simbolo: "",
+        simbolo_al_final: true,
+        separador_miles: ',',
+        separador_decimal: '.',
+    };
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ErrorDinero {
+    DivisasDistintas(Divisa, Divisa),
+    Desborde,
+    DivisionEntreCero,
+    // Repartir entre cero partes o con proporciones que suman cero
+    SinPartes,
+    DemasiadasPartes(usize),
+    CodigoInvalido(String),
+    Formato(String),
+}
+
+impl fmt::Display for ErrorDinero {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ErrorDinero::DivisasDistintas(a, b) => {
+                write!(f, "no se pueden mezclar {a} con {b}")
+            }
+            ErrorDinero::Desborde => write!(f, "la cantidad no cabe en un i64 de centavos"),
+            ErrorDinero::DivisionEntreCero => write!(f, "no se puede dividir entre cero"),
+            ErrorDinero::SinPartes => write!(f, "no hay entre quién repartir"),
+            ErrorDinero::DemasiadasPartes(partes) => {
+                write!(f, "{partes} partes son más de {PARTES_MAXIMAS}")
+            }
+            ErrorDinero::CodigoInvalido(codigo) => write!(
+                f,
+                "\"{codigo}\" no es un código de divisa, deben ser tres letras como MXN"
+            ),
+            ErrorDinero::Formato(texto) => write!(
+                f,
+                "\"{texto}\" no es una cantidad, debe ser como 1234.50, $1,234.50 o 12 USD"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ErrorDinero {}
+
+/* Divide redondeando al par más cercano. La división de enteros corta
+ * hacia el cero, después se ve si lo que sobró es más, menos o justo la
+ * mitad del divisor para decidir si hay que alejarse un paso del cero
+ */
+pub fn dividir_redondeando(numerador: i128, divisor: i128) -> Option<i128> {
+    let cociente = numerador.checked_div(divisor)?;
+    let residuo = numerador % divisor;
+    if residuo == 0 {
+        return Some(cociente);
+    }
+    let paso = if (numerador < 0) == (divisor < 0) {
+        1
+    } else {
+        -1
+    };
+    let alejarse = match (2 * residuo.unsigned_abs()).cmp(&divisor.unsigned_abs()) {
+        Ordering::Greater => true,
+        Ordering::Less => false,
+        Ordering::Equal => cociente % 2 != 0,
+    };
+    Some(if alejarse { cociente + paso } else { cociente })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Dinero {
+    centavos: i64,
+    divisa: Divisa,
+}
+
+impl Dinero {
+    pub fn new(centavos: i64, divisa: Divisa) -> Self {
+        Self { centavos, divisa }
+    }
+
+    pub fn cero(divisa: Divisa) -> Self {
+        Self::new(0, divisa)
+    }
+
+    // Cantidades enteras: Dinero::unidades(5, Divisa::MXN) son $5.00
+    pub fn unidades(unidades: i64, divisa: Divisa) -> Result<Self, ErrorDinero> {
+        let centavos = unidades
+            .checked_mul(divisa.centavos_por_unidad())
+            .ok_or(ErrorDinero::Desborde)?;
+        Ok(Self::new(centavos, divisa))
+    }
+
+    pub fn centavos(&self) -> i64 {
+        self.centavos
+    }
+
+    pub fn divisa(&self) -> Divisa {
+        self.divisa
+    }
+
+    pub fn es_cero(&self) -> bool {
+        self.centavos == 0
+    }
+
+    pub fn es_negativo(&self) -> bool {
+        self.centavos < 0
+    }
+
+    fn misma_divisa(&self, otro: &Self) -> Result<(), ErrorDinero> {
+        if self.divisa == otro.divisa {
+            Ok(())
+        } else {
+            Err(ErrorDinero::DivisasDistintas(self.divisa, otro.divisa))
+        }
+    }
+
+    pub fn sumar(&self, otro: &Self) -> Result<Self, ErrorDinero> {
+        self.misma_divisa(otro)?;
+        let centavos = self
+            .centavos
+            .checked_add(otro.centavos)
+            .ok_or(ErrorDinero::Desborde)?;
+        Ok(Self::new(centavos, self.divisa))
+    }
+
+    pub fn restar(&self, otro: &Self) -> Result<Self, ErrorDinero> {
+        self.misma_divisa(otro)?;
+        let centavos = self
+            .centavos
+            .checked_sub(otro.centavos)
+            .ok_or(ErrorDinero::Desborde)?;
+        Ok(Self::new(centavos, self.divisa))
+    }
+
+    // Tres cafés de $12.50 son $37.50, no hay nada que redondear
+    pub fn multiplicar(&self, cantidad: i64) -> Result<Self, ErrorDinero> {
+        let centavos = self
+            .centavos
+            .checked_mul(cantidad)
+            .ok_or(ErrorDinero::Desborde)?;
+        Ok(Self::new(centavos, self.divisa))
+    }
+
+    /* Cuánto le toca a cada quien, con redondeo bancario. Ojo: el
+     * resultado por el divisor puede no dar el total, para no perder
+     * centavos está repartir
+     */
+    pub fn dividir(&self, divisor: i64) -> Result<Self, ErrorDinero> {
+        if divisor == 0 {
+            return Err(ErrorDinero::DivisionEntreCero);
+        }
+        self.escalar(1, divisor as i128)
+    }
+
+    // self * numerador / denominador redondeando al par, sin desbordes a medio camino
+    pub fn escalar(&self, numerador: i128, denominador: i128) -> Result<Self, ErrorDinero> {
+        if denominador == 0 {
+            return Err(ErrorDinero::DivisionEntreCero);
+        }
+        let producto = (self.centavos as i128)
+            .checked_mul(numerador)
+            .ok_or(ErrorDinero::Desborde)?;
+        let centavos = dividir_redondeando(producto, denominador)
+            .and_then(|centavos| i64::try_from(centavos).ok())
+            .ok_or(ErrorDinero::Desborde)?;
+        Ok(Self::new(centavos, self.divisa))
+    }
+
+    /* $10.00 entre 3 da [$3.34, $3.33, $3.33]: todos reciben lo mismo y
+     * los centavos que sobran se los llevan los primeros. Da lo mismo que
+     * repartir_en_proporcion con puros 1, pero sin armar las proporciones
+     */
+    pub fn repartir(&self, partes: usize) -> Result<Vec<Self>, ErrorDinero> {
+        if partes == 0 {
+            return Err(ErrorDinero::SinPartes);
+        }
+        if partes > PARTES_MAXIMAS {
+            return Err(ErrorDinero::DemasiadasPartes(partes));
+        }
+        // Igual que en repartir_en_proporcion, el signo se pone al final
+        let total = self.centavos.unsigned_abs();
+        let cada_uno = total / partes as u64;
+        let sobran = (total % partes as u64) as usize;
+        let signo: i128 = if self.centavos < 0 { -1 } else { 1 };
+        Ok((0..partes)
+            .map(|i| {
+                let centavos = cada_uno + u64::from(i < sobran);
+                Self::new((signo * centavos as i128) as i64, self.divisa)
+            })
+            .collect())
+    }
+
+    /* Reparte según proporciones, [1, 2, 1] es 25%, 50% y 25%. Cada parte
+     * recibe lo que le toca cortando los centavos y lo que sobra se da de
+     * uno en uno a las partes con el residuo más grande (el método del
+     * resto mayor), en empate gana la que va antes
+     */
+    pub fn repartir_en_proporcion(&self, proporciones: &[u64]) -> Result<Vec<Self>, ErrorDinero> {
+        let suma: u128 = proporciones.iter().map(|&p| p as u128).sum();
+        if suma == 0 {
+            return Err(ErrorDinero::SinPartes);
+        }
+        // Se trabaja con el valor absoluto y al final se pone el signo
+        let total = self.centavos.unsigned_abs() as u128;
+        let signo: i128 = if self.centavos < 0 { -1 } else { 1 };
+
+        let mut partes = Vec::with_capacity(proporciones.len());
+        let mut residuos = Vec::with_capacity(proporciones.len());
+        for (i, &proporcion) in proporciones.iter().enumerate() {
+            // total < 2^64 y proporcion < 2^64, el producto cabe en u128
+            let producto = total * proporcion as u128;
+            partes.push(producto / suma);
+            residuos.push((producto % suma, i));
+        }
+        let repartido: u128 = partes.iter().sum();
+        residuos.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+        for &(_, i) in residuos.iter().take((total - repartido) as usize) {
+            partes[i] += 1;
+        }
+
+        Ok(partes
+            .into_iter()
+            .map(|centavos| Self::new((signo * centavos as i128) as i64, self.divisa))
+            .collect())
+    }
+
+    pub fn formatear(&self, formato: &Formato) -> String {
+        let absoluto = self.centavos.unsigned_abs();
+        let por_unidad = self.divisa.centavos_por_unidad() as u64;
+        let enteros = (absoluto / por_unidad).to_string();
+        let mut cantidad = String::new();
+        for (i, digito) in enteros.chars().enumerate() {
+            if i > 0 && (enteros.len() - i).is_multiple_of(3) {
+                cantidad.push(formato.separador_miles);
+            }
+            cantidad.push(digito);
+        }
+        let decimales = self.divisa.decimales() as usize;
+        if decimales > 0 {
+            cantidad.push(formato.separador_decimal);
+            cantidad.push_str(&format!("{:0decimales$}", absoluto % por_unidad));
+        }
+
+        let signo = if self.centavos < 0 { "-" } else { "" };
+        match (formato.simbolo, formato.simbolo_al_final) {
+            ("", _) => format!("{signo}{cantidad}"),
+            (simbolo, true) => format!("{signo}{cantidad} {simbolo}"),
+            (simbolo, false) => format!("{signo}{simbolo}{cantidad}"),
+        }
+    }
+
+    // $ lo usan varias divisas, así que se agrega el código: "$100.00 USD"
+    pub fn con_codigo(&self) -> String {
+        match self.divisa.formato().simbolo {
+            "$" => format!("{self} {}", self.divisa),
+            _ => self.to_string(),
+        }
+    }
+}
+
+// $1,234.50 para MXN y USD, 1.234,50 € para EUR y 1,234.50 XYZ para las demás
+impl fmt::Display for Dinero {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let formato = self.divisa.formato();
+        let mut texto = self.formatear(&formato);
+        if formato.simbolo.is_empty() {
+            texto = format!("{texto} {}", self.divisa);
+        }
+        f.pad(&texto)
+    }
+}
+
+/* Acepta "1234.5", "-$1,234.50", "12 USD" o "$3.07 MXN". Sin código es
+ * MXN, que es la divisa de las monedas de match_cf. Más decimales de los
+ * que tiene la divisa ("1.5 JPY") es un error en lugar de redondear a
+ * escondidas. Con € al final se lee
+ * como lo escribe Display, con punto para los miles y coma decimal:
+ * "-1.234,50 €". Display y FromStr van y vienen para MXN, EUR y las
+ * divisas sin símbolo; USD se escribe "$..." igual que MXN, para esa hay
+ * que usar con_codigo
+ */
+impl FromStr for Dinero {
+    type Err = ErrorDinero;
+
+    fn from_str(texto: &str) -> Result<Self, Self::Err> {
+        let error = || ErrorDinero::Formato(texto.to_string());
+        let limpio = texto.trim();
+        let (cantidad, divisa, formato) = match limpio.rsplit_once(char::is_whitespace) {
+            Some((cantidad, "€")) => (cantidad.trim_end(), Divisa::EUR, Formato::EURO),
+            Some((cantidad, codigo)) => (
+                cantidad.trim_end(),
+                codigo.parse::<Divisa>()?,
+                Formato::DOLAR,
+            ),
+            None => (limpio, Divisa::MXN, Formato::DOLAR),
+        };
+        let (negativo, cantidad) = match cantidad.strip_prefix('-') {
+            Some(resto) => (true, resto),
+            None => (false, cantidad),
+        };
+        let cantidad = if formato.simbolo_al_final {
+            cantidad
+        } else {
+            cantidad.strip_prefix(formato.simbolo).unwrap_or(cantidad)
+        };
+        let (enteros, decimales) = cantidad
+            .split_once(formato.separador_decimal)
+            .unwrap_or((cantidad, ""));
+
+        // Los separadores de miles solo pueden separar grupos de tres dígitos
+        let grupos: Vec<&str> = enteros.split(formato.separador_miles).collect();
+        let grupos_validos = grupos.len() == 1
+            || (!grupos[0].is_empty()
+                && grupos[0].len() <= 3
+                && grupos[1..].iter().all(|grupo| grupo.len() == 3));
+        let digitos = grupos.concat();
+        if digitos.is_empty()
+            || !grupos_validos
+            || decimales.len() > divisa.decimales() as usize
+            || !digitos
+                .chars()
+                .chain(decimales.chars())
+                .all(|c| c.is_ascii_digit())
+        {
+            return Err(error());
+        }
+
+        let unidades: i64 = digitos.parse().map_err(|_| ErrorDinero::Desborde)?;
+        let ancho = divisa.decimales() as usize;
+        let fraccion: i64 = match ancho {
+            0 => 0,
+            _ => format!("{decimales:0<ancho$}").parse().map_err(|_| error())?,
+        };
+        let centavos = unidades
+            .checked_mul(divisa.centavos_por_unidad())
+            .and_then(|centavos| centavos.checked_add(fraccion))
+            .ok_or(ErrorDinero::Desborde)?;
+        Ok(Self::new(
+            if negativo { -centavos } else { centavos },
+            divisa,
+        ))
+    }
+}
+
+// Solo se pueden comparar cantidades de la misma divisa
+impl PartialOrd for Dinero {
+    fn partial_cmp(&self, otro: &Self) -> Option<Ordering> {
+        if self.divisa == otro.divisa {
+            Some(self.centavos.cmp(&otro.centavos))
+        } else {
+            None
+        }
+    }
+}
+
+fn o_panic(resultado: Result<Dinero, ErrorDinero>) -> Dinero {
+    resultado.unwrap_or_else(|error| panic!("{error}"))
+}
+
+impl Add for Dinero {
+    type Output = Dinero;
+
+    fn add(self, otro: Dinero) -> Dinero {
+        o_panic(self.sumar(&otro))
+    }
+}
+
+impl Sub for Dinero {
+    type Output = Dinero;
+
+    fn sub(self, otro: Dinero) -> Dinero {
+        o_panic(self.restar(&otro))
+    }
+}
+
+impl Mul<i64> for Dinero {
+    type Output = Dinero;
+
+    fn mul(self, cantidad: i64) -> Dinero {
+        o_panic(self.multiplicar(cantidad))
+    }
+}
+
+impl Neg for Dinero {
+    type Output = Dinero;
+
+    fn neg(self) -> Dinero {
+        o_panic(self.multiplicar(-1))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn d(texto: &str) -> Dinero {
+        texto.parse().unwrap()
+    }
+
+    #[test]
+    fn lee_y_escribe_cantidades() {
+        assert_eq!(d("1234.5"), Dinero::new(123450, Divisa::MXN));
+        assert_eq!(d("$1,234.50").to_string(), "$1,234.50");
+        assert_eq!(d("-0.07 USD").to_string(), "-$0.07");
+        assert_eq!(d("1234567.89 EUR").to_string(), "1.234.567,89 €");
+        assert_eq!(d("3 JPY").to_string(), "3 JPY");
+        assert_eq!(d("3 JPY").centavos(), 3);
+        assert_eq!(d("1.5 KWD").to_string(), "1.500 KWD");
+        assert_eq!(d("1.5 KWD").centavos(), 1500);
+        assert_eq!(format!("[{:>8}]", d("5")), "[   $5.00]");
+        for malo in ["", "1.234", "12,34", "$", "1.2.3", "5 pesos", "1,2345", "1,234.50 €", "1.5 JPY", "1.2345 KWD"] {
+            assert!(malo.parse::<Dinero>().is_err(), "{malo:?}");
+        }
+    }
+
+    #[test]
+    fn display_y_from_str_van_y_vienen() {
+        assert_eq!(d("-1.234,50 €"), Dinero::new(-123450, Divisa::EUR));
+        assert_eq!(d("0,07 €"), Dinero::new(7, Divisa::EUR));
+        let cantidades = [0, 7, -123450, 123456789, i64::MAX, i64::MIN + 1];
+        let divisas = ["MXN", "EUR", "JPY", "KWD"].map(|codigo| Divisa::new(codigo).unwrap());
+        for divisa in divisas {
+            for centavos in cantidades {
+                let dinero = Dinero::new(centavos, divisa);
+                assert_eq!(d(&dinero.to_string()), dinero);
+            }
+        }
+        // "$1.00" es MXN, para no perder que es USD se escribe con el código
+        let dolar = d("1 USD");
+        assert_ne!(d(&dolar.to_string()), dolar);
+        assert_eq!(d(&dolar.con_codigo()), dolar);
+    }
+
+    #[test]
+    fn suma_resta_y_multiplica_exacto() {
+        // Con f64 esto da 0.30000000000000004
+        assert_eq!(d("0.10") + d("0.20"), d("0.30"));
+        assert_eq!(d("12.50") * 3, d("37.50"));
+        assert_eq!(d("5") - d("7.25"), d("-2.25"));
+        assert_eq!(
+            d("1 USD").sumar(&d("1 MXN")),
+            Err(ErrorDinero::DivisasDistintas(Divisa::USD, Divisa::MXN))
+        );
+        assert_eq!(
+            Dinero::new(i64::MAX, Divisa::MXN).multiplicar(2),
+            Err(ErrorDinero::Desborde)
+        );
+    }
+
+    #[test]
+    fn divide_con_redondeo_bancario() {
+        // 0.5 centavos redondea al par: 0.125 -> 0.12 y 0.135 -> 0.14
+        assert_eq!(d("0.25").dividir(2), Ok(d("0.12")));
+        assert_eq!(d("0.27").dividir(2), Ok(d("0.14")));
+        assert_eq!(d("-0.25").dividir(2), Ok(d("-0.12")));
+        assert_eq!(d("10").dividir(3), Ok(d("3.33")));
+        assert_eq!(d("20").dividir(3), Ok(d("6.67")));
+        assert_eq!(d("1").dividir(0), Err(ErrorDinero::DivisionEntreCero));
+        for n in -50..=50 {
+            let esperado = (n as f64 / 4.0).round_ties_even() as i128;
+            assert_eq!(dividir_redondeando(n, 4), Some(esperado), "{n}/4");
+            assert_eq!(dividir_redondeando(-n, -4), Some(esperado), "{n}/4");
+        }
+    }
+
+    #[test]
+    fn repartir_no_pierde_centavos() {
+        assert_eq!(
+            d("10").repartir(3),
+            Ok(vec![d("3.34"), d("3.33"), d("3.33")])
+        );
+        assert_eq!(d("-0.05").repartir(2), Ok(vec![d("-0.03"), d("-0.02")]));
+        assert_eq!(
+            d("100").repartir_en_proporcion(&[1, 2, 1]),
+            Ok(vec![d("25"), d("50"), d("25")])
+        );
+        assert_eq!(d("1").repartir(0), Err(ErrorDinero::SinPartes));
+        assert_eq!(
+            d("10").repartir(100_000_000_000_000),
+            Err(ErrorDinero::DemasiadasPartes(100_000_000_000_000))
+        );
+        assert_eq!(
+            d("0.03").repartir(PARTES_MAXIMAS).unwrap().len(),
+            PARTES_MAXIMAS
+        );
+
+        let mut aleatorio = crate::aleatorio::Aleatorio::new(15);
+        for _ in 0..200 {
+            let total = Dinero::new(aleatorio.hasta(200_001) as i64 - 100_000, Divisa::USD);
+            let proporciones: Vec<u64> = (0..=aleatorio.hasta(6))
+                .map(|_| aleatorio.hasta(10) + 1)
+                .collect();
+            let partes = total.repartir_en_proporcion(&proporciones).unwrap();
+            let suma = partes
+                .iter()
+                .fold(Dinero::cero(Divisa::USD), |suma, parte| suma + *parte);
+            assert_eq!(suma, total);
+
+            let iguales = vec![1; proporciones.len()];
+            assert_eq!(
+                total.repartir(iguales.len()),
+                total.repartir_en_proporcion(&iguales)
+            );
+        }
+    }
+
+    #[test]
+    fn repartir_el_minimo_de_i64() {
+        // |i64::MIN| no cabe en i64, pero cada parte sí
+        let minimo = Dinero::new(i64::MIN, Divisa::MXN);
+        assert_eq!(minimo.repartir_en_proporcion(&[1]), Ok(vec![minimo]));
+        let mitad = Dinero::new(i64::MIN / 2, Divisa::MXN);
+        assert_eq!(minimo.repartir(2), Ok(vec![mitad, mitad]));
+    }
+}
@@ -0,0 +1,511 @@
+/* Tipos de cambio entre divisas. En match_cf una moneda de oro valía
+ * 100 pesos, aquí la idea se extiende a cualquier divisa: 1 USD vale
+ * 17.25 MXN, 1 EUR vale 1.08 USD, etc. Las tasas se leen de un archivo
+ * local, nunca de internet, en CSV:
+ *
+ *     de,a,tasa
+ *     USD,MXN,17.25
+ *
+ * o en TOML, con una sección por divisa de origen:
+ *
+ *     [USD]
+ *     MXN = 17.25
+ *
+ * Si solo está USD -> MXN se usa 1 / 17.25 para MXN -> USD. Cuando no
+ * hay tasa directa se busca una ruta por otras divisas (triangular), por
+ * ejemplo EUR -> USD -> MXN, y se usa la que tenga menos pasos
+ *
+ * Las tasas se guardan como Racional para que 17.25 sea exacto y el
+ * redondeo a centavos pase una sola vez, al final, con el redondeo
+ * bancario de Dinero
+ *
+ * Si se da vuelta a un ciclo de divisas (USD -> MXN -> EUR -> USD) y se
+ * termina con más dinero del que se tenía, las tasas no son coherentes:
+ * eso es arbitraje y se detecta con Bellman-Ford sobre -ln(tasa), donde
+ * un ciclo que gana dinero es un ciclo de peso negativo
+ */
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use super::dinero::{Dinero, Divisa, ErrorDinero};
+use crate::operaciones::racional::Racional;
+
+pub type Tasa = Racional<i128>;
+
+#[derive(Debug)]
+pub enum ErrorCambio {
+    Archivo(io::Error),
+    // El archivo no termina en .csv ni en .toml
+    FormatoDesconocido(String),
+    Linea { numero: usize, mensaje: String },
+    // Tasas negativas o en cero o de una divisa a sí misma
+    TasaInvalida(Divisa, Divisa),
+    SinRuta(Divisa, Divisa),
+    Desborde,
+}
+
+impl fmt::Display for ErrorCambio {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ErrorCambio::Archivo(error) => {
+                write!(f, "no se pudo leer el archivo de tasas: {error}")
+            }
+            ErrorCambio::FormatoDesconocido(ruta) => {
+                write!(f, "{ruta} debe ser un archivo .csv o .toml")
+            }
+            ErrorCambio::Linea { numero, mensaje } => write!(f, "línea {numero}: {mensaje}"),
+            ErrorCambio::TasaInvalida(de, a) => write!(
+                f,
+                "la tasa de {de} a {a} debe ser positiva y entre divisas distintas"
+            ),
+            ErrorCambio::SinRuta(de, a) => write!(f, "no hay tasas para ir de {de} a {a}"),
+            ErrorCambio::Desborde => write!(f, "la conversión no cabe en los enteros"),
+        }
+    }
+}
+
+impl std::error::Error for ErrorCambio {}
+
+impl From<io::Error> for ErrorCambio {
+    fn from(error: io::Error) -> Self {
+        ErrorCambio::Archivo(error)
+    }
+}
+
+impl From<ErrorDinero> for ErrorCambio {
+    fn from(_: ErrorDinero) -> Self {
+        ErrorCambio::Desborde
+    }
+}
+
+fn error_en(numero: usize, mensaje: impl Into<String>) -> ErrorCambio {
+    ErrorCambio::Linea {
+        numero,
+        mensaje: mensaje.into(),
+    }
+}
+
+// El resultado de convertir con la ruta y la tasa total que se usó
+#[derive(Debug, Clone, PartialEq)]
+pub struct Conversion {
+    pub cantidad: Dinero,
+    pub ruta: Vec<Divisa>,
+    pub tasa: Tasa,
+}
+
+/* Un ciclo de divisas que termina con más de lo que empezó. La ruta
+ * empieza y termina en la misma divisa y ganancia es el producto de
+ * las tasas, 1.02 quiere decir que cada vuelta gana 2%
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct Arbitraje {
+    pub ciclo: Vec<Divisa>,
+    pub ganancia: Tasa,
+}
+
+impl fmt::Display for Arbitraje {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let porcentaje = (self.ganancia.a_f64() - 1.0) * 100.0;
+        write!(f, "{} gana {porcentaje:.2}% en cada vuelta", unir(&self.ciclo))
+    }
+}
+
+// USD -> MXN -> JPY
+pub fn unir(ruta: &[Divisa]) -> String {
+    let codigos: Vec<&str> = ruta.iter().map(Divisa::codigo).collect();
+    codigos.join(" -> ")
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct TablaDeCambio {
+    // Solo las tasas que vienen en el archivo, las inversas se calculan
+    tasas: BTreeMap<(Divisa, Divisa), Tasa>,
+}
+
+impl TablaDeCambio {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn desde_archivo(ruta: &Path) -> Result<Self, ErrorCambio> {
+        let texto = fs::read_to_string(ruta)?;
+        match ruta.extension().and_then(|extension| extension.to_str()) {
+            Some("csv") => Self::desde_csv(&texto),
+            Some("toml") => Self::desde_toml(&texto),
+            _ => Err(ErrorCambio::FormatoDesconocido(ruta.display().to_string())),
+        }
+    }
+
+    // Una tasa por línea, de,a,tasa. La primera línea puede ser el encabezado
+    pub fn desde_csv(texto: &str) -> Result<Self, ErrorCambio> {
+        let mut tabla = Self::new();
+        for (i, linea) in texto.lines().enumerate() {
+            let linea = linea.trim();
+            if linea.is_empty() || linea.starts_with('#') {
+                continue;
+            }
+            let campos: Vec<&str> = linea.split(',').map(str::trim).collect();
+            let [de, a, tasa] = campos[..] else {
+                return Err(error_en(i + 1, "debe tener tres campos: de,a,tasa"));
+            };
+            if tabla.tasas.is_empty() && de.parse::<Divisa>().is_err() && de.to_lowercase() == de {
+                continue;
+            }
+            tabla.agregar_texto(i + 1, de, a, tasa)?;
+        }
+        Ok(tabla)
+    }
+
+    /* Solo la parte de TOML que hace falta: secciones [USD] y líneas
+     * MXN = 17.25 (el número puede ir entre comillas) y comentarios
+     */
+    pub fn desde_toml(texto: &str) -> Result<Self, ErrorCambio> {
+        let mut tabla = Self::new();
+        let mut seccion = None;
+        for (i, linea) in texto.lines().enumerate() {
+            let linea = match linea.split_once('#') {
+                Some((antes, _)) => antes.trim(),
+                None => linea.trim(),
+            };
+            if linea.is_empty() {
+                continue;
+            }
+            if let Some(nombre) = linea
+                .strip_prefix('[')
+                .and_then(|resto| resto.strip_suffix(']'))
+            {
+                seccion = Some(nombre.trim());
+                continue;
+            }
+            let Some((a, tasa)) = linea.split_once('=') else {
+                return Err(error_en(i + 1, "se esperaba [DIVISA] o DIVISA = tasa"));
+            };
+            let Some(de) = seccion else {
+                return Err(error_en(
+                    i + 1,
+                    "la tasa debe ir dentro de una sección [DIVISA]",
+                ));
+            };
+            tabla.agregar_texto(i + 1, de, a.trim(), tasa.trim().trim_matches('"'))?;
+        }
+        Ok(tabla)
+    }
+
+    fn agregar_texto(
+        &mut self,
+        numero: usize,
+        de: &str,
+        a: &str,
+        tasa: &str,
+    ) -> Result<(), ErrorCambio> {
+        let de: Divisa = de
+            .parse()
+            .map_err(|error| error_en(numero, format!("{error}")))?;
+        let a: Divisa = a
+            .parse()
+            .map_err(|error| error_en(numero, format!("{error}")))?;
+        let tasa: Tasa = tasa
+            .parse()
+            .map_err(|error| error_en(numero, format!("{error}")))?;
+        if self.tasas.contains_key(&(de, a)) {
+            return Err(error_en(
+                numero,
+                format!("la tasa de {de} a {a} está repetida"),
+            ));
+        }
+        self.agregar(de, a, tasa)
+            .map_err(|error| error_en(numero, error.to_string()))
+    }
+
+    // 1 de = tasa a
+    pub fn agregar(&mut self, de: Divisa, a: Divisa, tasa: Tasa) -> Result<(), ErrorCambio> {
+        if de == a || tasa <= Tasa::entero(0) {
+            return Err(ErrorCambio::TasaInvalida(de, a));
+        }
+        self.tasas.insert((de, a), tasa);
+        Ok(())
+    }
+
+    pub fn divisas(&self) -> BTreeSet<Divisa> {
+        self.tasas.keys().flat_map(|&(de, a)| [de, a]).collect()
+    }
+
+    // La tasa del archivo o, si no está, la inversa de la contraria
+    pub fn tasa_directa(&self, de: Divisa, a: Divisa) -> Option<Tasa> {
+        if let Some(tasa) = self.tasas.get(&(de, a)) {
+            return Some(*tasa);
+        }
+        self.tasas
+            .get(&(a, de))
+            .and_then(|tasa| tasa.reciproco().ok())
+    }
+
+    // Todas las tasas que salen de cada divisa, con las inversas incluidas
+    fn aristas(&self) -> BTreeMap<Divisa, Vec<(Divisa, Tasa)>> {
+        let mut aristas: BTreeMap<Divisa, Vec<(Divisa, Tasa)>> = BTreeMap::new();
+        for de in self.divisas() {
+            aristas.entry(de).or_default();
+        }
+        for &(de, a) in self.tasas.keys() {
+            for (origen, destino) in [(de, a), (a, de)] {
+                let vecinos = aristas.entry(origen).or_default();
+                if !vecinos.iter().any(|(vecino, _)| *vecino == destino) {
+                    if let Some(tasa) = self.tasa_directa(origen, destino) {
+                        vecinos.push((destino, tasa));
+                    }
+                }
+            }
+        }
+        aristas
+    }
+
+    // La ruta con menos pasos, con búsqueda en anchura
+    pub fn ruta(&self, de: Divisa, a: Divisa) -> Option<Vec<Divisa>> {
+        if de == a {
+            return Some(vec![de]);
+        }
+        let aristas = self.aristas();
+        let mut anterior: BTreeMap<Divisa, Divisa> = BTreeMap::new();
+        let mut pendientes = VecDeque::from([de]);
+        while let Some(actual) = pendientes.pop_front() {
+            for &(vecino, _) in aristas.get(&actual).into_iter().flatten() {
+                if vecino == de || anterior.contains_key(&vecino) {
+                    continue;
+                }
+                anterior.insert(vecino, actual);
+                if vecino == a {
+                    let mut ruta = vec![a];
+                    while let Some(&paso) = anterior.get(ruta.last()?) {
+                        ruta.push(paso);
+                    }
+                    ruta.reverse();
+                    return Some(ruta);
+                }
+                pendientes.push_back(vecino);
+            }
+        }
+        None
+    }
+
+    fn tasa_de_ruta(&self, ruta: &[Divisa]) -> Result<Tasa, ErrorCambio> {
+        ruta.windows(2).try_fold(Tasa::entero(1), |total, par| {
+            let tasa = self
+                .tasa_directa(par[0], par[1])
+                .ok_or(ErrorCambio::SinRuta(par[0], par[1]))?;
+            total
+                .multiplicacion_verificada(&tasa)
+                .map_err(|_| ErrorCambio::Desborde)
+        })
+    }
+
+    pub fn convertir(&self, cantidad: Dinero, destino: Divisa) -> Result<Conversion, ErrorCambio> {
+        let ruta = self
+            .ruta(cantidad.divisa(), destino)
+            .ok_or(ErrorCambio::SinRuta(cantidad.divisa(), destino))?;
+        let tasa = self.tasa_de_ruta(&ruta)?;
+        // La tasa es por unidad y Dinero cuenta centavos: de EUR a JPY 100
+        // centavos de euro se vuelven 161 yenes, no 16146
+        let numerador = tasa
+            .numerador()
+            .checked_mul(destino.centavos_por_unidad() as i128)
+            .ok_or(ErrorCambio::Desborde)?;
+        let denominador = tasa
+            .denominador()
+            .checked_mul(cantidad.divisa().centavos_por_unidad() as i128)
+            .ok_or(ErrorCambio::Desborde)?;
+        let convertida = cantidad.escalar(numerador, denominador)?;
+        Ok(Conversion {
+            cantidad: Dinero::new(convertida.centavos(), destino),
+            ruta,
+            tasa,
+        })
+    }
+
+    /* Grupos de divisas conectadas entre sí. Si hay más de uno faltan
+     * tasas: no hay forma de convertir de un grupo a otro
+     */
+    pub fn grupos(&self) -> Vec<Vec<Divisa>> {
+        let aristas = self.aristas();
+        let mut vistas = BTreeSet::new();
+        let mut grupos = Vec::new();
+        for &inicio in aristas.keys() {
+            if !vistas.insert(inicio) {
+                continue;
+            }
+            let mut grupo = vec![inicio];
+            let mut pendientes = vec![inicio];
+            while let Some(actual) = pendientes.pop() {
+                for &(vecino, _) in &aristas[&actual] {
+                    if vistas.insert(vecino) {
+                        grupo.push(vecino);
+                        pendientes.push(vecino);
+                    }
+                }
+            }
+            grupo.sort();
+            grupos.push(grupo);
+        }
+        grupos
+    }
+
+    /* Ciclos con ganancia mayor a 1 + tolerancia. Bellman-Ford trabaja con
+     * f64 para encontrar los ciclos y después la ganancia se calcula exacta
+     * con las tasas. Una tolerancia de 0.001 ignora diferencias de 0.1%,
+     * que pueden salir de redondear las tasas en el archivo
+     */
+    pub fn arbitrajes(&self, tolerancia: f64) -> Vec<Arbitraje> {
+        let divisas: Vec<Divisa> = self.divisas().into_iter().collect();
+        let indice = |divisa: Divisa| divisas.binary_search(&divisa).unwrap_or(0);
+        let mut aristas = Vec::new();
+        for (de, vecinos) in self.aristas() {
+            for (a, tasa) in vecinos {
+                aristas.push((indice(de), indice(a), -tasa.a_f64().ln()));
+            }
+        }
+
+        // Empezar todas en 0 es como tener un origen conectado a todas
+        let n = divisas.len();
+        let mut distancia = vec![0.0; n];
+        let mut anterior: Vec<Option<usize>> = vec![None; n];
+        for _ in 1..n.max(1) {
+            for &(de, a, peso) in &aristas {
+                if distancia[de] + peso < distancia[a] - 1e-12 {
+                    distancia[a] = distancia[de] + peso;
+                    anterior[a] = Some(de);
+                }
+            }
+        }
+
+        let mut ciclos: BTreeSet<Vec<usize>> = BTreeSet::new();
+        for &(de, a, peso) in &aristas {
+            if distancia[de] + peso >= distancia[a] - 1e-12 {
+                continue;
+            }
+            let mut anterior = anterior.clone();
+            anterior[a] = Some(de);
+            // Después de n pasos hacia atrás seguro se está dentro del ciclo
+            let mut actual = a;
+            for _ in 0..n {
+                actual = anterior[actual].unwrap_or(actual);
+            }
+            let mut ciclo = vec![actual];
+            let mut paso = anterior[actual];
+            while let Some(previo) = paso {
+                if previo == actual || ciclo.len() > n {
+                    break;
+                }
+                ciclo.push(previo);
+                paso = anterior[previo];
+            }
+            ciclo.reverse();
+            // Se rota para que empiece en la menor y no salga repetido
+            let menor = (0..ciclo.len()).min_by_key(|&i| ciclo[i]).unwrap_or(0);
+            ciclo.rotate_left(menor);
+            ciclos.insert(ciclo);
+        }
+
+        ciclos
+            .into_iter()
+            .filter_map(|ciclo| {
+                let mut ruta: Vec<Divisa> = ciclo.iter().map(|&i| divisas[i]).collect();
+                ruta.push(ruta[0]);
+                let ganancia = self.tasa_de_ruta(&ruta).ok()?;
+                (ganancia.a_f64() > 1.0 + tolerancia).then_some(Arbitraje {
+                    ciclo: ruta,
+                    ganancia,
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CSV: &str = "de,a,tasa
+        # Tasas de ejemplo
+        USD,MXN,17.25
+        EUR,USD,1.08
+        MXN,JPY,8.5
+        ";
+
+    fn divisa(codigo: &str) -> Divisa {
+        codigo.parse().unwrap()
+    }
+
+    #[test]
+    fn convierte_directo_e_inverso() {
+        let tabla = TablaDeCambio::desde_csv(CSV).unwrap();
+        let cien = Dinero::unidades(100, Divisa::USD).unwrap();
+        let conversion = tabla.convertir(cien, Divisa::MXN).unwrap();
+        assert_eq!(conversion.cantidad, "1725 MXN".parse().unwrap());
+        assert_eq!(conversion.ruta, [Divisa::USD, Divisa::MXN]);
+
+        // 100 / 17.25 = 5.7971... se redondea a 5.80
+        let pesos = Dinero::unidades(100, Divisa::MXN).unwrap();
+        let dolares = tabla.convertir(pesos, Divisa::USD).unwrap();
+        assert_eq!(dolares.cantidad, "5.80 USD".parse().unwrap());
+        assert_eq!(dolares.tasa, "4/69".parse().unwrap());
+    }
+
+    #[test]
+    fn triangula_por_otras_divisas() {
+        let tabla = TablaDeCambio::desde_csv(CSV).unwrap();
+        let euros = Dinero::unidades(10, Divisa::EUR).unwrap();
+        let conversion = tabla.convertir(euros, divisa("JPY")).unwrap();
+        assert_eq!(
+            conversion.ruta,
+            [Divisa::EUR, Divisa::USD, Divisa::MXN, divisa("JPY")]
+        );
+        // 10 * 1.08 * 17.25 * 8.5 = 1583.55, y el yen no tiene decimales
+        assert_eq!(conversion.cantidad, "1584 JPY".parse().unwrap());
+        assert_eq!(conversion.cantidad.centavos(), 1584);
+    }
+
+    #[test]
+    fn detecta_tasas_faltantes() {
+        let mut tabla = TablaDeCambio::desde_csv(CSV).unwrap();
+        tabla
+            .agregar(divisa("GBP"), divisa("CHF"), "1.12".parse().unwrap())
+            .unwrap();
+        assert_eq!(tabla.grupos().len(), 2);
+        let libras = Dinero::unidades(1, divisa("GBP")).unwrap();
+        assert!(matches!(
+            tabla.convertir(libras, Divisa::MXN),
+            Err(ErrorCambio::SinRuta(_, _))
+        ));
+        assert!(matches!(
+            TablaDeCambio::desde_csv("USD,MXN,17\nUSD,MXN,18"),
+            Err(ErrorCambio::Linea { numero: 2, .. })
+        ));
+        assert!(TablaDeCambio::desde_csv("USD,MXN,-1").is_err());
+    }
+
+    #[test]
+    fn detecta_arbitraje() {
+        let tabla = TablaDeCambio::desde_csv(CSV).unwrap();
+        assert!(tabla.arbitrajes(0.0).is_empty());
+
+        // EUR -> MXN directo da 19.5 pero por USD da 1.08 * 17.25 = 18.63
+        let toml = "
+            [USD]
+            MXN = 17.25
+            [EUR]
+            USD = 1.08
+            MXN = \"19.5\" # Demasiado alta
+        ";
+        let tabla = TablaDeCambio::desde_toml(toml).unwrap();
+        let arbitrajes = tabla.arbitrajes(0.001);
+        assert_eq!(arbitrajes.len(), 1);
+        let Arbitraje { ciclo, ganancia } = &arbitrajes[0];
+        assert_eq!(ciclo.first(), ciclo.last());
+        assert_eq!(ciclo.len(), 4);
+        assert!((ganancia.a_f64() - 19.5 / 18.63).abs() < 1e-9);
+        // Con 5% de tolerancia la diferencia de 4.7% se acepta
+        assert!(tabla.arbitrajes(0.05).is_empty());
+    }
+}
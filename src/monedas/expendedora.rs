@@ -0,0 +1,579 @@
+/* Una máquina expendedora que recibe las Monedas de match_cf. Es una
+ * máquina de estados: en cada momento está en uno solo y cada acción
+ * solo tiene sentido en algunos
+ *
+ *   Inactiva --moneda--> RecibiendoMonedas --elegir--> Entregando
+ *                          |                             |
+ *                       cancelar                   tomar producto
+ *                          v                             v
+ *   Inactiva <--tomar cambio-- DevolviendoCambio <-------+
+ *
+ * (si no hay cambio que dar, después de tomar el producto vuelve directo
+ * a Inactiva). Lo que cada estado necesita va dentro de su variante,
+ * así no puede haber crédito en una máquina inactiva ni cambio pendiente
+ * mientras se reciben monedas. Una acción en el estado equivocado
+ * devuelve ErrorExpendedora::TransicionInvalida en vez de hacer algo raro
+ *
+ * El cambio sale de la caja con las monedas que de verdad hay (ver
+ * cambio.rs). Cuando la caja tiene tan pocas monedas que no se puede
+ * garantizar el cambio, la máquina entra en modo "solo cambio exacto"
+ */
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io;
+
+use super::cambio::SistemaDeMonedas;
+use super::{Dinero, Divisa, TODAS};
+use crate::entrada;
+use crate::leer_de_consola::print;
+use crate::match_cf::Monedas;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Producto {
+    pub nombre: String,
+    pub precio: Dinero,
+    pub existencias: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Estado {
+    Inactiva,
+    RecibiendoMonedas {
+        credito: Vec<Monedas>,
+    },
+    Entregando {
+        codigo: String,
+        cambio: Vec<Monedas>,
+    },
+    DevolviendoCambio {
+        monedas: Vec<Monedas>,
+    },
+}
+
+impl fmt::Display for Estado {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Estado::Inactiva => write!(f, "inactiva"),
+            Estado::RecibiendoMonedas { credito } => {
+                write!(f, "recibiendo monedas, crédito {}", suma(credito))
+            }
+            Estado::Entregando { codigo, .. } => write!(f, "entregando {codigo}"),
+            Estado::DevolviendoCambio { monedas } => {
+                write!(f, "devolviendo {}", suma(monedas))
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Accion {
+    Insertar,
+    Elegir,
+    Cancelar,
+    TomarProducto,
+    TomarCambio,
+}
+
+impl fmt::Display for Accion {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let texto = match self {
+            Accion::Insertar => "insertar una moneda",
+            Accion::Elegir => "elegir un producto",
+            Accion::Cancelar => "cancelar",
+            Accion::TomarProducto => "tomar el producto",
+            Accion::TomarCambio => "tomar el cambio",
+        };
+        write!(f, "{texto}")
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ErrorExpendedora {
+    TransicionInvalida { estado: String, accion: Accion },
+    ProductoDesconocido(String),
+    Agotado(String),
+    CreditoInsuficiente { falta: Dinero },
+    SoloCambioExacto { sobra: Dinero },
+    SinCambio { cambio: Dinero },
+    // Con monedas de 1, 10 y 100 pesos solo se pueden cobrar pesos enteros
+    PrecioInvalido(Dinero),
+}
+
+impl fmt::Display for ErrorExpendedora {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ErrorExpendedora::TransicionInvalida { estado, accion } => {
+                write!(f, "no se puede {accion} cuando la máquina está {estado}")
+            }
+            ErrorExpendedora::ProductoDesconocido(codigo) => {
+                write!(f, "no hay ningún producto {codigo}")
+            }
+            ErrorExpendedora::Agotado(codigo) => write!(f, "el producto {codigo} está agotado"),
+            ErrorExpendedora::CreditoInsuficiente { falta } => write!(f, "faltan {falta}"),
+            ErrorExpendedora::SoloCambioExacto { sobra } => write!(
+                f,
+                "solo se acepta cambio exacto y sobran {sobra}, cancela para que te los devuelva"
+            ),
+            ErrorExpendedora::SinCambio { cambio } => write!(
+                f,
+                "no hay monedas para dar {cambio} de cambio, cancela para que te devuelva tu dinero"
+            ),
+            ErrorExpendedora::PrecioInvalido(precio) => {
+                write!(f, "{precio} no se puede pagar con monedas de 1, 10 y 100")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ErrorExpendedora {}
+
+// Lo que queda en el registro de transacciones
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Evento {
+    Moneda(Monedas),
+    Venta {
+        codigo: String,
+        precio: Dinero,
+        cambio: Dinero,
+    },
+    Devolucion(Dinero),
+    Rechazo(ErrorExpendedora),
+}
+
+impl fmt::Display for Evento {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Evento::Moneda(moneda) => write!(f, "moneda de {}", super::nombre(*moneda)),
+            Evento::Venta {
+                codigo,
+                precio,
+                cambio,
+            } => write!(f, "venta de {codigo} por {precio}, cambio {cambio}"),
+            Evento::Devolucion(dinero) => write!(f, "devolución de {dinero}"),
+            Evento::Rechazo(error) => write!(f, "rechazo: {error}"),
+        }
+    }
+}
+
+fn suma(monedas: &[Monedas]) -> Dinero {
+    super::total(monedas).unwrap_or_else(|error| panic!("{error}"))
+}
+
+fn indice(moneda: Monedas) -> usize {
+    TODAS.iter().position(|&otra| otra == moneda).unwrap_or(0)
+}
+
+fn pesos(dinero: Dinero) -> u64 {
+    dinero.centavos().unsigned_abs() / 100
+}
+
+pub struct Expendedora {
+    productos: BTreeMap<String, Producto>,
+    // Cuántas monedas de cobre, plata y oro hay para dar cambio
+    caja: [u64; 3],
+    estado: Estado,
+    registro: Vec<Evento>,
+    sistema: SistemaDeMonedas,
+}
+
+impl Expendedora {
+    pub fn new(caja: [u64; 3]) -> Self {
+        Self {
+            productos: BTreeMap::new(),
+            caja,
+            estado: Estado::Inactiva,
+            registro: Vec::new(),
+            sistema: SistemaDeMonedas::de_monedas(),
+        }
+    }
+
+    pub fn agregar_producto(
+        &mut self,
+        codigo: &str,
+        nombre: &str,
+        precio: Dinero,
+        existencias: u32,
+    ) -> Result<(), ErrorExpendedora> {
+        if precio.divisa() != Divisa::MXN || precio.centavos() % 100 != 0 || precio.es_negativo() {
+            return Err(ErrorExpendedora::PrecioInvalido(precio));
+        }
+        self.productos.insert(
+            codigo.to_string(),
+            Producto {
+                nombre: nombre.to_string(),
+                precio,
+                existencias,
+            },
+        );
+        Ok(())
+    }
+
+    pub fn productos(&self) -> impl Iterator<Item = (&str, &Producto)> {
+        self.productos
+            .iter()
+            .map(|(codigo, producto)| (codigo.as_str(), producto))
+    }
+
+    pub fn estado(&self) -> &Estado {
+        &self.estado
+    }
+
+    pub fn caja(&self) -> [u64; 3] {
+        self.caja
+    }
+
+    pub fn registro(&self) -> &[Evento] {
+        &self.registro
+    }
+
+    pub fn credito(&self) -> Dinero {
+        match &self.estado {
+            Estado::RecibiendoMonedas { credito } => suma(credito),
+            _ => Dinero::cero(Divisa::MXN),
+        }
+    }
+
+    /* Si hay algún cambio menor a la moneda más grande que la caja no
+     * pueda dar, no se puede prometer cambio para cualquier compra
+     */
+    pub fn solo_cambio_exacto(&self) -> bool {
+        let mayor = *self.sistema.denominaciones().last().unwrap_or(&1);
+        (1..mayor).any(|cantidad| {
+            !matches!(
+                self.sistema.con_inventario(cantidad, &self.caja),
+                Ok(Some(_))
+            )
+        })
+    }
+
+    fn invalida(&self, accion: Accion) -> ErrorExpendedora {
+        ErrorExpendedora::TransicionInvalida {
+            estado: self.estado.to_string(),
+            accion,
+        }
+    }
+
+    // Guarda los errores en el registro antes de devolverlos
+    fn anotar<T>(&mut self, resultado: Result<T, ErrorExpendedora>) -> Result<T, ErrorExpendedora> {
+        if let Err(error) = &resultado {
+            self.registro.push(Evento::Rechazo(error.clone()));
+        }
+        resultado
+    }
+
+    pub fn insertar(&mut self, moneda: Monedas) -> Result<Dinero, ErrorExpendedora> {
+        match &mut self.estado {
+            Estado::Inactiva => {
+                self.estado = Estado::RecibiendoMonedas {
+                    credito: vec![moneda],
+                }
+            }
+            Estado::RecibiendoMonedas { credito } => credito.push(moneda),
+            _ => {
+                let error = self.invalida(Accion::Insertar);
+                return self.anotar(Err(error));
+            }
+        }
+        self.registro.push(Evento::Moneda(moneda));
+        Ok(self.credito())
+    }
+
+    pub fn elegir(&mut self, codigo: &str) -> Result<(), ErrorExpendedora> {
+        let resultado = self.vender(codigo);
+        self.anotar(resultado)
+    }
+
+    fn vender(&mut self, codigo: &str) -> Result<(), ErrorExpendedora> {
+        let credito = match &self.estado {
+            Estado::Inactiva => Vec::new(),
+            Estado::RecibiendoMonedas { credito } => credito.clone(),
+            _ => return Err(self.invalida(Accion::Elegir)),
+        };
+        let producto = self
+            .productos
+            .get(codigo)
+            .ok_or_else(|| ErrorExpendedora::ProductoDesconocido(codigo.to_string()))?;
+        if producto.existencias == 0 {
+            return Err(ErrorExpendedora::Agotado(codigo.to_string()));
+        }
+        let precio = producto.precio;
+        let pagado = suma(&credito);
+        if pagado < precio {
+            return Err(ErrorExpendedora::CreditoInsuficiente {
+                falta: precio - pagado,
+            });
+        }
+
+        // Las monedas que se acaban de meter también sirven para el cambio
+        let sobra = pagado - precio;
+        let mut caja = self.caja;
+        for &moneda in &credito {
+            caja[indice(moneda)] += 1;
+        }
+        let mut cambio = Vec::new();
+        if !sobra.es_cero() {
+            if self.solo_cambio_exacto() {
+                return Err(ErrorExpendedora::SoloCambioExacto { sobra });
+            }
+            let Ok(Some(monedas)) = self.sistema.con_inventario(pesos(sobra), &caja) else {
+                return Err(ErrorExpendedora::SinCambio { cambio: sobra });
+            };
+            for moneda in TODAS.into_iter().rev() {
+                let cuantas = monedas.cantidad_de(pesos(super::valor(moneda)));
+                caja[indice(moneda)] -= cuantas;
+                cambio.extend((0..cuantas).map(|_| moneda));
+            }
+        }
+
+        self.caja = caja;
+        if let Some(producto) = self.productos.get_mut(codigo) {
+            producto.existencias -= 1;
+        }
+        self.registro.push(Evento::Venta {
+            codigo: codigo.to_string(),
+            precio,
+            cambio: sobra,
+        });
+        self.estado = Estado::Entregando {
+            codigo: codigo.to_string(),
+            cambio,
+        };
+        Ok(())
+    }
+
+    // Devuelve las monedas tal como se metieron
+    pub fn cancelar(&mut self) -> Result<(), ErrorExpendedora> {
+        let Estado::RecibiendoMonedas { credito } = &self.estado else {
+            let error = self.invalida(Accion::Cancelar);
+            return self.anotar(Err(error));
+        };
+        let monedas = credito.clone();
+        self.registro.push(Evento::Devolucion(suma(&monedas)));
+        self.estado = Estado::DevolviendoCambio { monedas };
+        Ok(())
+    }
+
+    pub fn tomar_producto(&mut self) -> Result<String, ErrorExpendedora> {
+        let Estado::Entregando { codigo, cambio } = &self.estado else {
+            let error = self.invalida(Accion::TomarProducto);
+            return self.anotar(Err(error));
+        };
+        let nombre = self.productos[codigo].nombre.clone();
+        self.estado = if cambio.is_empty() {
+            Estado::Inactiva
+        } else {
+            Estado::DevolviendoCambio {
+                monedas: cambio.clone(),
+            }
+        };
+        Ok(nombre)
+    }
+
+    pub fn tomar_cambio(&mut self) -> Result<Vec<Monedas>, ErrorExpendedora> {
+        let Estado::DevolviendoCambio { monedas } = &self.estado else {
+            let error = self.invalida(Accion::TomarCambio);
+            return self.anotar(Err(error));
+        };
+        let monedas = monedas.clone();
+        self.estado = Estado::Inactiva;
+        Ok(monedas)
+    }
+}
+
+// La máquina con la que empieza la sesión interactiva
+pub fn de_ejemplo() -> Expendedora {
+    let mut maquina = Expendedora::new([20, 10, 2]);
+    let productos = [
+        ("A1", "Agua", 15, 5),
+        ("A2", "Papas", 22, 3),
+        ("B1", "Chocolate", 18, 4),
+        ("B2", "Refresco", 25, 1),
+    ];
+    for (codigo, nombre, precio, existencias) in productos {
+        let precio = Dinero::new(precio * 100, Divisa::MXN);
+        maquina
+            .agregar_producto(codigo, nombre, precio, existencias)
+            .expect("los precios de ejemplo son pesos enteros");
+    }
+    maquina
+}
+
+const AYUDA: &str = "Comandos:
+  cobre, plata, oro   Mete una moneda
+  elegir <código>     Compra un producto
+  cancelar            Devuelve las monedas
+  tomar               Toma el producto o el cambio
+  productos           Muestra los productos
+  registro            Muestra las transacciones
+  salir";
+
+fn mostrar_productos(maquina: &Expendedora) {
+    for (codigo, producto) in maquina.productos() {
+        println!(
+            "{codigo}  {:<10} {:>7}  quedan {}",
+            producto.nombre, producto.precio, producto.existencias
+        );
+    }
+    if maquina.solo_cambio_exacto() {
+        println!("Solo cambio exacto");
+    }
+}
+
+fn tomar(maquina: &mut Expendedora) -> Result<String, ErrorExpendedora> {
+    match maquina.estado() {
+        Estado::Entregando { .. } => maquina
+            .tomar_producto()
+            .map(|nombre| format!("Tomaste: {nombre}")),
+        _ => maquina.tomar_cambio().map(|monedas| {
+            let nombres: Vec<&str> = monedas
+                .iter()
+                .map(|&moneda| super::nombre(moneda))
+                .collect();
+            format!("Tomaste {} ({})", suma(&monedas), nombres.join(", "))
+        }),
+    }
+}
+
+pub fn sesion() -> io::Result<()> {
+    let mut maquina = de_ejemplo();
+    println!("{AYUDA}\n");
+    mostrar_productos(&maquina);
+    loop {
+        print(format!("[{}] > ", maquina.estado()));
+        let mut linea = String::new();
+        if entrada::leer_linea(&mut linea)? == 0 {
+            println!();
+            return Ok(());
+        }
+        let palabras: Vec<&str> = linea.split_whitespace().collect();
+        let resultado = match palabras.as_slice() {
+            [] => continue,
+            ["salir"] => return Ok(()),
+            ["ayuda"] => Ok(AYUDA.to_string()),
+            ["productos"] => {
+                mostrar_productos(&maquina);
+                continue;
+            }
+            ["registro"] => {
+                for (i, evento) in maquina.registro().iter().enumerate() {
+                    println!("{:>3}. {evento}", i + 1);
+                }
+                continue;
+            }
+            [moneda @ ("cobre" | "plata" | "oro")] => {
+                let moneda = TODAS
+                    .into_iter()
+                    .find(|&otra| super::nombre(otra) == *moneda)
+                    .unwrap_or(Monedas::_Cobre);
+                maquina
+                    .insertar(moneda)
+                    .map(|credito| format!("Crédito: {credito}"))
+            }
+            ["elegir", codigo] => maquina
+                .elegir(&codigo.to_uppercase())
+                .map(|()| "Ya puedes tomar tu producto".to_string()),
+            ["cancelar"] => maquina
+                .cancelar()
+                .map(|()| "Ya puedes tomar tus monedas".to_string()),
+            ["tomar"] => tomar(&mut maquina),
+            _ => Ok(format!("No conozco ese comando\n{AYUDA}")),
+        };
+        match resultado {
+            Ok(mensaje) => println!("{mensaje}"),
+            Err(error) => println!("Error: {error}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pesos(cantidad: i64) -> Dinero {
+        Dinero::unidades(cantidad, Divisa::MXN).unwrap()
+    }
+
+    #[test]
+    fn una_compra_con_cambio() {
+        let mut maquina = de_ejemplo();
+        assert!(!maquina.solo_cambio_exacto());
+        maquina.insertar(Monedas::_Plata).unwrap();
+        maquina.insertar(Monedas::_Plata).unwrap();
+        assert_eq!(maquina.credito(), pesos(20));
+        maquina.elegir("B1").unwrap();
+        assert_eq!(maquina.tomar_producto(), Ok("Chocolate".to_string()));
+        assert_eq!(
+            maquina.tomar_cambio(),
+            Ok(vec![Monedas::_Cobre, Monedas::_Cobre])
+        );
+        assert_eq!(maquina.estado(), &Estado::Inactiva);
+        assert_eq!(maquina.caja(), [18, 12, 2]);
+        assert_eq!(maquina.productos["B1"].existencias, 3);
+    }
+
+    #[test]
+    fn las_transiciones_invalidas_son_errores() {
+        let mut maquina = de_ejemplo();
+        assert!(matches!(
+            maquina.cancelar(),
+            Err(ErrorExpendedora::TransicionInvalida {
+                accion: Accion::Cancelar,
+                ..
+            })
+        ));
+        assert_eq!(
+            maquina.elegir("A1"),
+            Err(ErrorExpendedora::CreditoInsuficiente { falta: pesos(15) })
+        );
+        maquina.insertar(Monedas::_Oro).unwrap();
+        maquina.elegir("B2").unwrap();
+        // Mientras entrega no se pueden meter monedas ni tomar cambio
+        assert!(maquina.insertar(Monedas::_Cobre).is_err());
+        assert!(maquina.tomar_cambio().is_err());
+        maquina.tomar_producto().unwrap();
+        assert_eq!(suma(&maquina.tomar_cambio().unwrap()), pesos(75));
+
+        maquina.insertar(Monedas::_Oro).unwrap();
+        assert_eq!(
+            maquina.elegir("B2"),
+            Err(ErrorExpendedora::Agotado("B2".to_string()))
+        );
+        maquina.cancelar().unwrap();
+        assert_eq!(maquina.tomar_cambio(), Ok(vec![Monedas::_Oro]));
+        assert_eq!(
+            maquina.registro().last(),
+            Some(&Evento::Devolucion(pesos(100)))
+        );
+        assert!(maquina
+            .registro()
+            .iter()
+            .any(|evento| matches!(evento, Evento::Rechazo(_))));
+    }
+
+    #[test]
+    fn con_poca_caja_solo_acepta_cambio_exacto() {
+        let mut maquina = Expendedora::new([2, 0, 0]);
+        maquina
+            .agregar_producto("A1", "Agua", pesos(12), 1)
+            .unwrap();
+        assert!(maquina.solo_cambio_exacto());
+        maquina.insertar(Monedas::_Plata).unwrap();
+        maquina.insertar(Monedas::_Plata).unwrap();
+        assert_eq!(
+            maquina.elegir("A1"),
+            Err(ErrorExpendedora::SoloCambioExacto { sobra: pesos(8) })
+        );
+        maquina.cancelar().unwrap();
+        maquina.tomar_cambio().unwrap();
+
+        maquina.insertar(Monedas::_Plata).unwrap();
+        maquina.insertar(Monedas::_Cobre).unwrap();
+        maquina.insertar(Monedas::_Cobre).unwrap();
+        maquina.elegir("A1").unwrap();
+        assert_eq!(maquina.caja(), [4, 1, 0]);
+        assert_eq!(
+            maquina.agregar_producto("Z9", "Chicle", "0.50".parse().unwrap(), 1),
+            Err(ErrorExpendedora::PrecioInvalido("0.50".parse().unwrap()))
+        );
+    }
+}
@@ -0,0 +1,41 @@
+/* Herramientas para las monedas de la lección de match. Allá una
+ * moneda solo sabía cuántos pesos valía, aquí se vuelve Dinero para
+ * poder sumar, repartir y mostrar cantidades sin errores de redondeo
+ */
+pub mod cambio;
+pub mod clasificadora;
+pub mod dinero;
+pub mod divisas;
+pub mod expendedora;
+
+pub use dinero::{Dinero, Divisa, ErrorDinero};
+
+use crate::match_cf::Monedas;
+
+pub const TODAS: [Monedas; 3] = [Monedas::_Cobre, Monedas::_Plata, Monedas::_Oro];
+
+// Lo mismo que convertir_en_pesos de match_cf pero sin imprimir nada
+pub fn valor(moneda: Monedas) -> Dinero {
+    let pesos = match moneda {
+        Monedas::_Cobre => 1,
+        Monedas::_Plata => 10,
+        Monedas::_Oro => 100,
+    };
+    Dinero::unidades(pesos, Divisa::MXN).expect("unos cuantos pesos caben en un i64")
+}
+
+pub fn nombre(moneda: Monedas) -> &'static str {
+    match moneda {
+        Monedas::_Cobre => "cobre",
+        Monedas::_Plata => "plata",
+        Monedas::_Oro => "oro",
+    }
+}
+
+pub fn total(monedas: &[Monedas]) -> Result<Dinero, ErrorDinero> {
+    monedas
+        .iter()
+        .try_fold(Dinero::cero(Divisa::MXN), |suma, &moneda| {
+            suma.sumar(&valor(moneda))
+        })
+}
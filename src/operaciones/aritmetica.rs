@@ -1,3 +1,518 @@
+/* Aritmética de enteros que no se desborda sin avisar. Con + un i8 que
+ * pasa de 127 hace panic en debug y en release le da la vuelta a -128
+ * sin decir nada. Aquí cada operación (suma, resta, multiplicación,
+ * división y potencia) viene en cuatro formas, igual que los métodos de
+ * la librería estándar:
+ *
+ * - verificada: devuelve un ErrorAritmetico que dice qué operandos se
+ *   desbordaron y hacia dónde (checked_*)
+ * - envolvente: le da la vuelta al rango, 127 + 1 = -128 (wrapping_*)
+ * - saturada: se queda en el máximo o el mínimo del tipo (saturating_*)
+ * - con_desborde: el resultado envolvente y si hubo desborde (overflowing_*)
+ *
+ * Funcionan con todos los enteros de tipos_datos.rs, de i8 a i128, de u8
+ * a u128, isize y usize, gracias al trait Entero
+ */
+use std::fmt;
+use std::str::FromStr;
+
+pub trait Entero: Copy + Ord + fmt::Display + fmt::Debug + FromStr {
+    const NOMBRE: &'static str;
+    const CERO: Self;
+    const UNO: Self;
+    const MIN: Self;
+    const MAX: Self;
+
+    fn checked_add(self, otro: Self) -> Option<Self>;
+    fn checked_sub(self, otro: Self) -> Option<Self>;
+    fn checked_mul(self, otro: Self) -> Option<Self>;
+    fn checked_div(self, otro: Self) -> Option<Self>;
+    fn checked_pow(self, exponente: u32) -> Option<Self>;
+    fn checked_rem(self, otro: Self) -> Option<Self>;
+    fn checked_neg(self) -> Option<Self>;
+
+    // Conversiones que necesita racional.rs, None si el valor no cabe
+    fn desde_i128(valor: i128) -> Option<Self>;
+    fn a_i128(self) -> Option<i128>;
+    fn a_f64(self) -> f64;
+
+    fn wrapping_add(self, otro: Self) -> Self;
+    fn wrapping_sub(self, otro: Self) -> Self;
+    fn wrapping_mul(self, otro: Self) -> Self;
+    fn wrapping_div(self, otro: Self) -> Self;
+    fn wrapping_pow(self, exponente: u32) -> Self;
+
+    fn saturating_add(self, otro: Self) -> Self;
+    fn saturating_sub(self, otro: Self) -> Self;
+    fn saturating_mul(self, otro: Self) -> Self;
+    fn saturating_div(self, otro: Self) -> Self;
+    fn saturating_pow(self, exponente: u32) -> Self;
+
+    fn overflowing_add(self, otro: Self) -> (Self, bool);
+    fn overflowing_sub(self, otro: Self) -> (Self, bool);
+    fn overflowing_mul(self, otro: Self) -> (Self, bool);
+    fn overflowing_div(self, otro: Self) -> (Self, bool);
+    fn overflowing_pow(self, exponente: u32) -> (Self, bool);
+}
+
+/* Cada tipo ya tiene estos métodos, el macro sólo escribe el impl que
+ * los llama para no repetirlo doce veces
+ */
+macro_rules! impl_entero {
+    ($($tipo:ty),*) => {$(
+        impl Entero for $tipo {
+            const NOMBRE: &'static str = stringify!($tipo);
+            const CERO: Self = 0;
+            const UNO: Self = 1;
+            const MIN: Self = <$tipo>::MIN;
+            const MAX: Self = <$tipo>::MAX;
+
+            fn checked_add(self, otro: Self) -> Option<Self> { <$tipo>::checked_add(self, otro) }
+            fn checked_sub(self, otro: Self) -> Option<Self> { <$tipo>::checked_sub(self, otro) }
+            fn checked_mul(self, otro: Self) -> Option<Self> { <$tipo>::checked_mul(self, otro) }
+            fn checked_div(self, otro: Self) -> Option<Self> { <$tipo>::checked_div(self, otro) }
+            fn checked_pow(self, exponente: u32) -> Option<Self> { <$tipo>::checked_pow(self, exponente) }
+            fn checked_rem(self, otro: Self) -> Option<Self> { <$tipo>::checked_rem(self, otro) }
+            fn checked_neg(self) -> Option<Self> { <$tipo>::checked_neg(self) }
+
+            fn desde_i128(valor: i128) -> Option<Self> { Self::try_from(valor).ok() }
+            fn a_i128(self) -> Option<i128> { i128::try_from(self).ok() }
+            fn a_f64(self) -> f64 { self as f64 }
+
+            fn wrapping_add(self, otro: Self) -> Self { <$tipo>::wrapping_add(self, otro) }
+            fn wrapping_sub(self, otro: Self) -> Self { <$tipo>::wrapping_sub(self, otro) }
+            fn wrapping_mul(self, otro: Self) -> Self { <$tipo>::wrapping_mul(self, otro) }
+            fn wrapping_div(self, otro: Self) -> Self { <$tipo>::wrapping_div(self, otro) }
+            fn wrapping_pow(self, exponente: u32) -> Self { <$tipo>::wrapping_pow(self, exponente) }
+
+            fn saturating_add(self, otro: Self) -> Self { <$tipo>::saturating_add(self, otro) }
+            fn saturating_sub(self, otro: Self) -> Self { <$tipo>::saturating_sub(self, otro) }
+            fn saturating_mul(self, otro: Self) -> Self { <$tipo>::saturating_mul(self, otro) }
+            fn saturating_div(self, otro: Self) -> Self { <$tipo>::saturating_div(self, otro) }
+            fn saturating_pow(self, exponente: u32) -> Self { <$tipo>::saturating_pow(self, exponente) }
+
+            fn overflowing_add(self, otro: Self) -> (Self, bool) { <$tipo>::overflowing_add(self, otro) }
+            fn overflowing_sub(self, otro: Self) -> (Self, bool) { <$tipo>::overflowing_sub(self, otro) }
+            fn overflowing_mul(self, otro: Self) -> (Self, bool) { <$tipo>::overflowing_mul(self, otro) }
+            fn overflowing_div(self, otro: Self) -> (Self, bool) { <$tipo>::overflowing_div(self, otro) }
+            fn overflowing_pow(self, exponente: u32) -> (Self, bool) { <$tipo>::overflowing_pow(self, exponente) }
+        }
+    )*};
+}
+
+impl_entero!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Operacion {
+    Suma,
+    Resta,
+    Multiplicacion,
+    Division,
+}
+
+impl Operacion {
+    fn simbolo(self) -> &'static str {
+        match self {
+            Operacion::Suma => "+",
+            Operacion::Resta => "-",
+            Operacion::Multiplicacion => "*",
+            Operacion::Division => "/",
+        }
+    }
+}
+
+// Hacia dónde se salió el resultado del rango del tipo
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Direccion {
+    // Más grande que MAX
+    Arriba,
+    // Más chico que MIN
+    Abajo,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ErrorAritmetico<T: Entero> {
+    Desborde {
+        operacion: Operacion,
+        izquierdo: T,
+        derecho: T,
+        direccion: Direccion,
+    },
+    // La potencia va aparte porque el exponente siempre es u32
+    PotenciaDesbordada {
+        base: T,
+        exponente: u32,
+        direccion: Direccion,
+    },
+    DivisionEntreCero {
+        dividendo: T,
+    },
+}
+
+impl<T: Entero> fmt::Display for ErrorAritmetico<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let limite = |direccion: &Direccion| match direccion {
+            Direccion::Arriba => format!("por arriba, el máximo es {}", T::MAX),
+            Direccion::Abajo => format!("por abajo, el mínimo es {}", T::MIN),
+        };
+        match self {
+            ErrorAritmetico::Desborde {
+                operacion,
+                izquierdo,
+                derecho,
+                direccion,
+            } => write!(
+                f,
+                "{izquierdo} {} {derecho} se desborda en {} {}",
+                operacion.simbolo(),
+                T::NOMBRE,
+                limite(direccion)
+            ),
+            ErrorAritmetico::PotenciaDesbordada {
+                base,
+                exponente,
+                direccion,
+            } => write!(
+                f,
+                "{base}^{exponente} se desborda en {} {}",
+                T::NOMBRE,
+                limite(direccion)
+            ),
+            ErrorAritmetico::DivisionEntreCero { dividendo } => {
+                write!(f, "{dividendo} / 0 no se puede, es una división entre cero")
+            }
+        }
+    }
+}
+
+impl<T: Entero> std::error::Error for ErrorAritmetico<T> {}
+
+/* Cuando checked_* devuelve None hay que averiguar hacia dónde se salió.
+ * Con los signos de los operandos alcanza: por ejemplo en una suma sólo
+ * se puede pasar de MAX si el derecho es positivo
+ */
+fn desborde<T: Entero>(operacion: Operacion, izquierdo: T, derecho: T) -> ErrorAritmetico<T> {
+    let direccion = match operacion {
+        Operacion::Suma if derecho > T::CERO => Direccion::Arriba,
+        Operacion::Suma => Direccion::Abajo,
+        Operacion::Resta if derecho > T::CERO => Direccion::Abajo,
+        Operacion::Resta => Direccion::Arriba,
+        Operacion::Multiplicacion if (izquierdo < T::CERO) == (derecho < T::CERO) => Direccion::Arriba,
+        Operacion::Multiplicacion => Direccion::Abajo,
+        // Sólo pasa con MIN / -1, que daría MAX + 1
+        Operacion::Division => Direccion::Arriba,
+    };
+    ErrorAritmetico::Desborde {
+        operacion,
+        izquierdo,
+        derecho,
+        direccion,
+    }
+}
+
+pub fn suma_verificada<T: Entero>(x: T, y: T) -> Result<T, ErrorAritmetico<T>> {
+    x.checked_add(y).ok_or_else(|| desborde(Operacion::Suma, x, y))
+}
+
+pub fn resta_verificada<T: Entero>(x: T, y: T) -> Result<T, ErrorAritmetico<T>> {
+    x.checked_sub(y).ok_or_else(|| desborde(Operacion::Resta, x, y))
+}
+
+pub fn multiplicacion_verificada<T: Entero>(x: T, y: T) -> Result<T, ErrorAritmetico<T>> {
+    x.checked_mul(y).ok_or_else(|| desborde(Operacion::Multiplicacion, x, y))
+}
+
+pub fn division_verificada<T: Entero>(x: T, y: T) -> Result<T, ErrorAritmetico<T>> {
+    if y == T::CERO {
+        return Err(ErrorAritmetico::DivisionEntreCero { dividendo: x });
+    }
+    x.checked_div(y).ok_or_else(|| desborde(Operacion::Division, x, y))
+}
+
+pub fn potencia_verificada<T: Entero>(base: T, exponente: u32) -> Result<T, ErrorAritmetico<T>> {
+    base.checked_pow(exponente).ok_or_else(|| {
+        // Una base negativa con exponente impar da un resultado negativo
+        let direccion = if base < T::CERO && exponente % 2 == 1 {
+            Direccion::Abajo
+        } else {
+            Direccion::Arriba
+        };
+        ErrorAritmetico::PotenciaDesbordada {
+            base,
+            exponente,
+            direccion,
+        }
+    })
+}
+
+/* Las divisiones envolvente, saturada y con desborde hacen panic al
+ * dividir entre cero, igual que las de la librería estándar, porque no
+ * hay ningún resultado que tenga sentido devolver
+ */
+
+pub fn suma_envolvente<T: Entero>(x: T, y: T) -> T {
+    x.wrapping_add(y)
+}
+
+pub fn resta_envolvente<T: Entero>(x: T, y: T) -> T {
+    x.wrapping_sub(y)
+}
+
+pub fn multiplicacion_envolvente<T: Entero>(x: T, y: T) -> T {
+    x.wrapping_mul(y)
+}
+
+pub fn division_envolvente<T: Entero>(x: T, y: T) -> T {
+    x.wrapping_div(y)
+}
+
+pub fn potencia_envolvente<T: Entero>(base: T, exponente: u32) -> T {
+    base.wrapping_pow(exponente)
+}
+
+pub fn suma_saturada<T: Entero>(x: T, y: T) -> T {
+    x.saturating_add(y)
+}
+
+pub fn resta_saturada<T: Entero>(x: T, y: T) -> T {
+    x.saturating_sub(y)
+}
+
+pub fn multiplicacion_saturada<T: Entero>(x: T, y: T) -> T {
+    x.saturating_mul(y)
+}
+
+pub fn division_saturada<T: Entero>(x: T, y: T) -> T {
+    x.saturating_div(y)
+}
+
+pub fn potencia_saturada<T: Entero>(base: T, exponente: u32) -> T {
+    base.saturating_pow(exponente)
+}
+
+pub fn suma_con_desborde<T: Entero>(x: T, y: T) -> (T, bool) {
+    x.overflowing_add(y)
+}
+
+pub fn resta_con_desborde<T: Entero>(x: T, y: T) -> (T, bool) {
+    x.overflowing_sub(y)
+}
+
+pub fn multiplicacion_con_desborde<T: Entero>(x: T, y: T) -> (T, bool) {
+    x.overflowing_mul(y)
+}
+
+pub fn division_con_desborde<T: Entero>(x: T, y: T) -> (T, bool) {
+    x.overflowing_div(y)
+}
+
+pub fn potencia_con_desborde<T: Entero>(base: T, exponente: u32) -> (T, bool) {
+    base.overflowing_pow(exponente)
+}
+
+/* La suma original. Con + hacía panic en debug y daba la vuelta en
+ * release, ahora en los dos casos hace panic con un mensaje que dice
+ * qué se desbordó
+ */
 pub fn suma(x: i32, y: i32) -> i32 {
-    x + y
+    suma_verificada(x, y).unwrap_or_else(|error| panic!("{error}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /* Cada variante contra el método de la librería estándar. Hacia dónde
+     * se desbordó se revisa con la saturada, que se queda en MAX o en MIN
+     */
+    fn direccion_de<T: Entero>(saturado: T) -> Direccion {
+        if saturado == T::MAX {
+            Direccion::Arriba
+        } else {
+            assert_eq!(saturado, T::MIN);
+            Direccion::Abajo
+        }
+    }
+
+    fn revisar<T: Entero>(x: T, y: T) {
+        type Verificada<T> = fn(T, T) -> Result<T, ErrorAritmetico<T>>;
+        type Saturada<T> = fn(T, T) -> T;
+        let operaciones: [(Operacion, Verificada<T>, Saturada<T>); 3] = [
+            (Operacion::Suma, suma_verificada, T::saturating_add),
+            (Operacion::Resta, resta_verificada, T::saturating_sub),
+            (
+                Operacion::Multiplicacion,
+                multiplicacion_verificada,
+                T::saturating_mul,
+            ),
+        ];
+        for (operacion, verificada, saturada) in operaciones {
+            let esperado = match operacion {
+                Operacion::Suma => x.checked_add(y),
+                Operacion::Resta => x.checked_sub(y),
+                _ => x.checked_mul(y),
+            };
+            match esperado {
+                Some(valor) => assert_eq!(verificada(x, y), Ok(valor)),
+                None => assert_eq!(
+                    verificada(x, y),
+                    Err(ErrorAritmetico::Desborde {
+                        operacion,
+                        izquierdo: x,
+                        derecho: y,
+                        direccion: direccion_de(saturada(x, y)),
+                    })
+                ),
+            }
+        }
+        assert_eq!(suma_envolvente(x, y), x.wrapping_add(y));
+        assert_eq!(resta_envolvente(x, y), x.wrapping_sub(y));
+        assert_eq!(multiplicacion_envolvente(x, y), x.wrapping_mul(y));
+        assert_eq!(suma_saturada(x, y), x.saturating_add(y));
+        assert_eq!(resta_saturada(x, y), x.saturating_sub(y));
+        assert_eq!(multiplicacion_saturada(x, y), x.saturating_mul(y));
+        assert_eq!(suma_con_desborde(x, y), x.overflowing_add(y));
+        assert_eq!(resta_con_desborde(x, y), x.overflowing_sub(y));
+        assert_eq!(multiplicacion_con_desborde(x, y), x.overflowing_mul(y));
+
+        if y == T::CERO {
+            assert_eq!(
+                division_verificada(x, y),
+                Err(ErrorAritmetico::DivisionEntreCero { dividendo: x })
+            );
+        } else {
+            match x.checked_div(y) {
+                Some(valor) => assert_eq!(division_verificada(x, y), Ok(valor)),
+                None => assert_eq!(
+                    division_verificada(x, y),
+                    Err(ErrorAritmetico::Desborde {
+                        operacion: Operacion::Division,
+                        izquierdo: x,
+                        derecho: y,
+                        direccion: direccion_de(x.saturating_div(y)),
+                    })
+                ),
+            }
+            assert_eq!(division_envolvente(x, y), x.wrapping_div(y));
+            assert_eq!(division_saturada(x, y), x.saturating_div(y));
+            assert_eq!(division_con_desborde(x, y), x.overflowing_div(y));
+        }
+    }
+
+    fn revisar_potencia<T: Entero>(base: T, exponente: u32) {
+        match base.checked_pow(exponente) {
+            Some(valor) => assert_eq!(potencia_verificada(base, exponente), Ok(valor)),
+            None => assert_eq!(
+                potencia_verificada(base, exponente),
+                Err(ErrorAritmetico::PotenciaDesbordada {
+                    base,
+                    exponente,
+                    direccion: direccion_de(base.saturating_pow(exponente)),
+                })
+            ),
+        }
+        assert_eq!(
+            potencia_envolvente(base, exponente),
+            base.wrapping_pow(exponente)
+        );
+        assert_eq!(
+            potencia_saturada(base, exponente),
+            base.saturating_pow(exponente)
+        );
+        assert_eq!(
+            potencia_con_desborde(base, exponente),
+            base.overflowing_pow(exponente)
+        );
+    }
+
+    const EXPONENTES: [u32; 9] = [0, 1, 2, 3, 7, 8, 63, 127, 128];
+
+    // Con 8 bits caben todas las parejas
+    #[test]
+    fn todos_los_i8_y_u8() {
+        for x in i8::MIN..=i8::MAX {
+            for y in i8::MIN..=i8::MAX {
+                revisar(x, y);
+            }
+            for exponente in EXPONENTES {
+                revisar_potencia(x, exponente);
+            }
+        }
+        for x in u8::MIN..=u8::MAX {
+            for y in u8::MIN..=u8::MAX {
+                revisar(x, y);
+            }
+            for exponente in EXPONENTES {
+                revisar_potencia(x, exponente);
+            }
+        }
+    }
+
+    #[test]
+    fn los_bordes_de_i128() {
+        let mut valores = vec![i128::MIN, i128::MIN + 1, i128::MAX, i128::MAX - 1];
+        valores.extend([-3, -2, -1, 0, 1, 2, 3]);
+        valores.extend([i128::MAX / 2, i128::MIN / 2, 1 << 64, -(1 << 64), 1 << 63]);
+        for &x in &valores {
+            for &y in &valores {
+                revisar(x, y);
+            }
+            for exponente in EXPONENTES {
+                revisar_potencia(x, exponente);
+            }
+        }
+    }
+
+    #[test]
+    fn minimo_entre_menos_uno() {
+        let error = division_verificada(i8::MIN, -1).unwrap_err();
+        assert_eq!(
+            error,
+            ErrorAritmetico::Desborde {
+                operacion: Operacion::Division,
+                izquierdo: -128,
+                derecho: -1,
+                direccion: Direccion::Arriba,
+            }
+        );
+        assert_eq!(division_envolvente(i128::MIN, -1), i128::MIN);
+        assert_eq!(division_saturada(i128::MIN, -1), i128::MAX);
+        assert_eq!(division_con_desborde(i128::MIN, -1), (i128::MIN, true));
+    }
+
+    #[test]
+    fn mensajes_de_error() {
+        assert_eq!(
+            suma_verificada(100i8, 28).unwrap_err().to_string(),
+            "100 + 28 se desborda en i8 por arriba, el máximo es 127"
+        );
+        assert_eq!(
+            resta_verificada(0u8, 1).unwrap_err().to_string(),
+            "0 - 1 se desborda en u8 por abajo, el mínimo es 0"
+        );
+        assert_eq!(
+            multiplicacion_verificada(i128::MAX, -2)
+                .unwrap_err()
+                .to_string(),
+            format!(
+                "{} * -2 se desborda en i128 por abajo, el mínimo es {}",
+                i128::MAX,
+                i128::MIN
+            )
+        );
+        assert_eq!(
+            potencia_verificada(-2i8, 9).unwrap_err().to_string(),
+            "-2^9 se desborda en i8 por abajo, el mínimo es -128"
+        );
+        assert_eq!(
+            division_verificada(7u8, 0).unwrap_err().to_string(),
+            "7 / 0 no se puede, es una división entre cero"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "2147483647 + 1 se desborda en i32 por arriba")]
+    fn suma_hace_panic_al_desbordarse() {
+        suma(i32::MAX, 1);
+    }
 }
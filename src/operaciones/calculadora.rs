@@ -0,0 +1,466 @@
+/* Calculadora de expresiones como "2 * (x + 3) ^ 2 - sqrt(16)". Primero
+ * el texto se parte en tokens (números, nombres, operadores, paréntesis)
+ * y luego se arma un árbol con "precedence climbing": se lee un operando
+ * y mientras el siguiente operador tenga la precedencia suficiente se
+ * sigue leyendo del lado derecho. De menor a mayor precedencia:
+ *
+ *   + -      izquierda a derecha
+ *   * / %    izquierda a derecha
+ *   - unario (así -2 ^ 2 es -(2 ^ 2), como en matemáticas)
+ *   ^        derecha a izquierda, 2 ^ 3 ^ 2 es 2 ^ (3 ^ 2)
+ *
+ * Se pueden usar variables guardadas con `let x = ...`, las constantes pi
+ * y e, y las funciones sqrt, abs, min y max. Un error nunca hace panic,
+ * dice en qué columna está el token que no se entendió. Tampoco se
+ * desborda la pila: el análisis es recursivo, así que una expresión con
+ * más de PROFUNDIDAD_MAXIMA niveles (paréntesis, menos o operadores
+ * encadenados) es un error en la columna donde se pasó del límite
+ */
+
+use std::collections::HashMap;
+use std::fmt;
+use std::io;
+
+use crate::entrada;
+use crate::leer_de_consola::print;
+
+#[derive(Debug, Clone, PartialEq)]
+enum TipoToken {
+    Numero(f64),
+    Nombre(String),
+    Operador(char),
+    AbreParentesis,
+    CierraParentesis,
+    Coma,
+    Igual,
+}
+
+#[derive(Debug, Clone)]
+struct Token {
+    tipo: TipoToken,
+    // Desde 1, contando caracteres y no bytes
+    columna: usize,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ErrorCalculo {
+    pub columna: usize,
+    pub mensaje: String,
+}
+
+impl ErrorCalculo {
+    fn new(columna: usize, mensaje: impl Into<String>) -> Self {
+        Self {
+            columna,
+            mensaje: mensaje.into(),
+        }
+    }
+
+    // Una línea con ^ debajo de la columna del error, para ponerla bajo la expresión
+    pub fn senalar(&self) -> String {
+        format!("{}^", " ".repeat(self.columna.saturating_sub(1)))
+    }
+}
+
+impl fmt::Display for ErrorCalculo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "columna {}: {}", self.columna, self.mensaje)
+    }
+}
+
+impl std::error::Error for ErrorCalculo {}
+
+fn tokenizar(texto: &str) -> Result<Vec<Token>, ErrorCalculo> {
+    let caracteres: Vec<char> = texto.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < caracteres.len() {
+        let c = caracteres[i];
+        let columna = i + 1;
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+
+        let tipo = if c.is_ascii_digit() || c == '.' {
+            let inicio = i;
+            while i < caracteres.len() && (caracteres[i].is_ascii_digit() || caracteres[i] == '.') {
+                i += 1;
+            }
+            let numero: String = caracteres[inicio..i].iter().collect();
+            match numero.parse() {
+                Ok(valor) => TipoToken::Numero(valor),
+                Err(_) => {
+                    return Err(ErrorCalculo::new(
+                        columna,
+                        format!("\"{numero}\" no es un número"),
+                    ))
+                }
+            }
+        } else if c.is_alphabetic() || c == '_' {
+            let inicio = i;
+            while i < caracteres.len() && (caracteres[i].is_alphanumeric() || caracteres[i] == '_')
+            {
+                i += 1;
+            }
+            TipoToken::Nombre(caracteres[inicio..i].iter().collect())
+        } else {
+            i += 1;
+            match c {
+                '+' | '-' | '*' | '/' | '%' | '^' => TipoToken::Operador(c),
+                '(' => TipoToken::AbreParentesis,
+                ')' => TipoToken::CierraParentesis,
+                ',' => TipoToken::Coma,
+                '=' => TipoToken::Igual,
+                _ => {
+                    return Err(ErrorCalculo::new(
+                        columna,
+                        format!("no se entiende el carácter '{c}'"),
+                    ))
+                }
+            }
+        };
+        tokens.push(Token { tipo, columna });
+    }
+    Ok(tokens)
+}
+
+#[derive(Debug)]
+enum Expresion {
+    Numero(f64),
+    Variable {
+        nombre: String,
+        columna: usize,
+    },
+    Negativo(Box<Expresion>),
+    Binaria {
+        operador: char,
+        izquierda: Box<Expresion>,
+        derecha: Box<Expresion>,
+        columna: usize,
+    },
+    Llamada {
+        funcion: String,
+        argumentos: Vec<Expresion>,
+        columna: usize,
+    },
+}
+
+const PRECEDENCIA_NEGATIVO: u8 = 3;
+
+/* Cada nivel es una llamada recursiva al analizar y otra al evaluar (y al
+ * soltar el árbol), con 256 sobra para cualquier cuenta escrita a mano y
+ * queda lejos de los 2 MB de pila de un hilo
+ */
+const PROFUNDIDAD_MAXIMA: usize = 256;
+
+// (precedencia, se agrupa a la derecha)
+fn precedencia(operador: char) -> (u8, bool) {
+    match operador {
+        '+' | '-' => (1, false),
+        '*' | '/' | '%' => (2, false),
+        _ => (4, true),
+    }
+}
+
+struct Analizador {
+    tokens: Vec<Token>,
+    posicion: usize,
+    // Columna justo después del texto, para los errores de "falta algo"
+    fin: usize,
+    profundidad: usize,
+}
+
+impl Analizador {
+    fn actual(&self) -> Option<&Token> {
+        self.tokens.get(self.posicion)
+    }
+
+    fn columna_actual(&self) -> usize {
+        self.actual().map_or(self.fin, |token| token.columna)
+    }
+
+    fn siguiente(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.posicion).cloned();
+        self.posicion += 1;
+        token
+    }
+
+    fn esperar(&mut self, tipo: TipoToken, descripcion: &str) -> Result<(), ErrorCalculo> {
+        match self.actual() {
+            Some(token) if token.tipo == tipo => {
+                self.posicion += 1;
+                Ok(())
+            }
+            _ => Err(ErrorCalculo::new(
+                self.columna_actual(),
+                format!("se esperaba {descripcion}"),
+            )),
+        }
+    }
+
+    fn entrar(&mut self) -> Result<(), ErrorCalculo> {
+        self.profundidad += 1;
+        if self.profundidad > PROFUNDIDAD_MAXIMA {
+            return Err(ErrorCalculo::new(
+                self.columna_actual(),
+                format!("la expresión tiene más de {PROFUNDIDAD_MAXIMA} niveles"),
+            ));
+        }
+        Ok(())
+    }
+
+    fn expresion(&mut self, minima: u8) -> Result<Expresion, ErrorCalculo> {
+        let profundidad = self.profundidad;
+        self.entrar()?;
+        let mut izquierda = self.unaria()?;
+
+        while let Some(Token {
+            tipo: TipoToken::Operador(operador),
+            columna,
+        }) = self.actual().cloned()
+        {
+            let (nivel, a_la_derecha) = precedencia(operador);
+            if nivel < minima {
+                break;
+            }
+            // Cada operador deja el árbol un nivel más hondo, aunque aquí no haya recursión
+            self.entrar()?;
+            self.posicion += 1;
+            let siguiente_minima = if a_la_derecha { nivel } else { nivel + 1 };
+            let derecha = self.expresion(siguiente_minima)?;
+            izquierda = Expresion::Binaria {
+                operador,
+                izquierda: Box::new(izquierda),
+                derecha: Box::new(derecha),
+                columna,
+            };
+        }
+        self.profundidad = profundidad;
+        Ok(izquierda)
+    }
+
+    fn unaria(&mut self) -> Result<Expresion, ErrorCalculo> {
+        if let Some(TipoToken::Operador('-')) = self.actual().map(|token| &token.tipo) {
+            self.posicion += 1;
+            // Sólo toma lo que tenga más precedencia que el menos, o sea las potencias
+            let operando = self.expresion(PRECEDENCIA_NEGATIVO + 1)?;
+            return Ok(Expresion::Negativo(Box::new(operando)));
+        }
+        self.primaria()
+    }
+
+    fn primaria(&mut self) -> Result<Expresion, ErrorCalculo> {
+        let columna = self.columna_actual();
+        let Some(token) = self.siguiente() else {
+            return Err(ErrorCalculo::new(
+                columna,
+                "falta un número o una variable al final",
+            ));
+        };
+        match token.tipo {
+            TipoToken::Numero(valor) => Ok(Expresion::Numero(valor)),
+            TipoToken::Nombre(nombre)
+                if self.actual().map(|token| &token.tipo) == Some(&TipoToken::AbreParentesis) =>
+            {
+                self.posicion += 1;
+                let mut argumentos = Vec::new();
+                if self.actual().map(|token| &token.tipo) != Some(&TipoToken::CierraParentesis) {
+                    argumentos.push(self.expresion(1)?);
+                    while self.actual().map(|token| &token.tipo) == Some(&TipoToken::Coma) {
+                        self.posicion += 1;
+                        argumentos.push(self.expresion(1)?);
+                    }
+                }
+                self.esperar(TipoToken::CierraParentesis, "un ')' para cerrar la función")?;
+                Ok(Expresion::Llamada {
+                    funcion: nombre,
+                    argumentos,
+                    columna,
+                })
+            }
+            TipoToken::Nombre(nombre) => Ok(Expresion::Variable { nombre, columna }),
+            TipoToken::AbreParentesis => {
+                let dentro = self.expresion(1)?;
+                self.esperar(TipoToken::CierraParentesis, "un ')'")?;
+                Ok(dentro)
+            }
+            _ => Err(ErrorCalculo::new(
+                columna,
+                "se esperaba un número, una variable o un '('",
+            )),
+        }
+    }
+}
+
+fn analizar(texto: &str) -> Result<Expresion, ErrorCalculo> {
+    let mut analizador = Analizador {
+        tokens: tokenizar(texto)?,
+        posicion: 0,
+        fin: texto.chars().count() + 1,
+        profundidad: 0,
+    };
+    let expresion = analizador.expresion(1)?;
+    if analizador.actual().is_some() {
+        return Err(ErrorCalculo::new(
+            analizador.columna_actual(),
+            "sobra esto al final de la expresión",
+        ));
+    }
+    Ok(expresion)
+}
+
+pub struct Calculadora {
+    variables: HashMap<String, f64>,
+}
+
+impl Default for Calculadora {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Calculadora {
+    pub fn new() -> Self {
+        let variables = HashMap::from([
+            (String::from("pi"), std::f64::consts::PI),
+            (String::from("e"), std::f64::consts::E),
+        ]);
+        Self { variables }
+    }
+
+    /* Evalúa una línea, que puede ser una expresión o `let x = expresión`.
+     * Devuelve el nombre de la variable si se guardó una y el valor
+     */
+    pub fn evaluar_linea(&mut self, linea: &str) -> Result<(Option<String>, f64), ErrorCalculo> {
+        let Some(resto) = linea.trim_start().strip_prefix("let ") else {
+            return Ok((None, self.evaluar(linea)?));
+        };
+
+        // Columna en la que empieza un pedazo que llega hasta el final de la línea
+        let columna_de = |pedazo: &str| linea.chars().count() - pedazo.chars().count() + 1;
+        let Some((nombre, expresion)) = resto.split_once('=') else {
+            return Err(ErrorCalculo::new(
+                columna_de(""),
+                "falta el '=' después del nombre",
+            ));
+        };
+
+        let espacios = nombre.chars().take_while(|c| c.is_whitespace()).count();
+        let nombre = nombre.trim();
+        let valido = nombre.starts_with(|c: char| c.is_alphabetic() || c == '_')
+            && nombre.chars().all(|c| c.is_alphanumeric() || c == '_');
+        if !valido {
+            return Err(ErrorCalculo::new(
+                columna_de(resto) + espacios,
+                "el nombre de la variable debe ser letras, números o _",
+            ));
+        }
+
+        // Los errores de la expresión se cuentan desde el inicio de la línea
+        let valor = self.evaluar(expresion).map_err(|mut error| {
+            error.columna += columna_de(expresion) - 1;
+            error
+        })?;
+        self.variables.insert(nombre.to_string(), valor);
+        Ok((Some(nombre.to_string()), valor))
+    }
+
+    pub fn evaluar(&self, texto: &str) -> Result<f64, ErrorCalculo> {
+        self.valor(&analizar(texto)?)
+    }
+
+    fn valor(&self, expresion: &Expresion) -> Result<f64, ErrorCalculo> {
+        match expresion {
+            Expresion::Numero(valor) => Ok(*valor),
+            Expresion::Variable { nombre, columna } => {
+                self.variables.get(nombre).copied().ok_or_else(|| {
+                    ErrorCalculo::new(*columna, format!("la variable \"{nombre}\" no existe"))
+                })
+            }
+            Expresion::Negativo(operando) => Ok(-self.valor(operando)?),
+            Expresion::Binaria {
+                operador,
+                izquierda,
+                derecha,
+                columna,
+            } => {
+                let x = self.valor(izquierda)?;
+                let y = self.valor(derecha)?;
+                match operador {
+                    '+' => Ok(x + y),
+                    '-' => Ok(x - y),
+                    '*' => Ok(x * y),
+                    '/' | '%' if y == 0.0 => {
+                        Err(ErrorCalculo::new(*columna, "división entre cero"))
+                    }
+                    '/' => Ok(x / y),
+                    '%' => Ok(x % y),
+                    _ => Ok(x.powf(y)),
+                }
+            }
+            Expresion::Llamada {
+                funcion,
+                argumentos,
+                columna,
+            } => {
+                let valores = argumentos
+                    .iter()
+                    .map(|argumento| self.valor(argumento))
+                    .collect::<Result<Vec<f64>, _>>()?;
+                let error = |mensaje: String| Err(ErrorCalculo::new(*columna, mensaje));
+                match (funcion.as_str(), valores.as_slice()) {
+                    ("sqrt", [x]) if *x < 0.0 => error(format!("sqrt de un número negativo ({x})")),
+                    ("sqrt", [x]) => Ok(x.sqrt()),
+                    ("abs", [x]) => Ok(x.abs()),
+                    ("min", [primero, resto @ ..]) => {
+                        Ok(resto.iter().fold(*primero, |a, b| a.min(*b)))
+                    }
+                    ("max", [primero, resto @ ..]) => {
+                        Ok(resto.iter().fold(*primero, |a, b| a.max(*b)))
+                    }
+                    ("sqrt" | "abs", _) => error(format!("{funcion} recibe un solo argumento")),
+                    ("min" | "max", _) => {
+                        error(format!("{funcion} necesita al menos un argumento"))
+                    }
+                    _ => error(format!(
+                        "la función \"{funcion}\" no existe, hay sqrt, abs, min y max"
+                    )),
+                }
+            }
+        }
+    }
+}
+
+/* Modo interactivo: lee líneas hasta que se escriba "salir" o se acabe
+ * la entrada. Usa entrada.rs, así que también se le puede dar un guion
+ */
+pub fn repl() -> io::Result<()> {
+    let mut calculadora = Calculadora::new();
+    println!("Escribe una expresión o `let x = ...`, \"salir\" para terminar");
+    loop {
+        print(String::from("> "));
+        let mut linea = String::new();
+        if entrada::leer_linea(&mut linea)? == 0 {
+            println!();
+            return Ok(());
+        }
+        let linea = linea.trim_end();
+        match linea.trim() {
+            "" => continue,
+            "salir" => return Ok(()),
+            _ => {}
+        }
+
+        match calculadora.evaluar_linea(linea) {
+            Ok((Some(nombre), valor)) => println!("{nombre} = {valor}"),
+            Ok((None, valor)) => println!("{valor}"),
+            Err(error) => {
+                // El ^ va debajo de la línea, que empieza después del "> "
+                println!("  {}", error.senalar());
+                println!("Error en la {error}");
+            }
+        }
+    }
+}
@@ -0,0 +1,702 @@
+/* Números complejos. En generics.rs Vector3<T> suma sus componentes con
+ * impl<T: Add<Output = T> + Copy>, Complejo<T> es la misma idea con dos
+ * componentes, la parte real y la imaginaria, y con todas las
+ * operaciones: + - * / y unario -, sus versiones con = (+=, -=...) y
+ * también con un número real del lado derecho
+ *
+ * Las operaciones básicas funcionan con cualquier T que las tenga (hasta
+ * con enteros: Complejo<i32> suma y multiplica exacto). Lo que necesita
+ * raíces, logaritmos o funciones trigonométricas pide T: Real, que son
+ * f32 y f64
+ *
+ * Al final está Durand-Kerner, que encuentra todas las raíces de un
+ * polinomio a la vez, aunque sean complejas
+ */
+
+use std::fmt;
+use std::iter::{Product, Sum};
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+pub trait Real:
+    Copy
+    + PartialOrd
+    + fmt::Display
+    + FromStr
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Neg<Output = Self>
+{
+    const CERO: Self;
+    const UNO: Self;
+    const EPSILON: Self;
+
+    fn desde_f64(valor: f64) -> Self;
+    fn abs(self) -> Self;
+    fn sqrt(self) -> Self;
+    fn hypot(self, otro: Self) -> Self;
+    fn atan2(self, otro: Self) -> Self;
+    fn exp(self) -> Self;
+    fn ln(self) -> Self;
+    fn sin(self) -> Self;
+    fn cos(self) -> Self;
+    fn powf(self, exponente: Self) -> Self;
+    fn es_finito(self) -> bool;
+}
+
+macro_rules! impl_real {
+    ($($tipo:ident),*) => {$(
+        impl Real for $tipo {
+            const CERO: Self = 0.0;
+            const UNO: Self = 1.0;
+            const EPSILON: Self = $tipo::EPSILON;
+
+            fn desde_f64(valor: f64) -> Self { valor as $tipo }
+            fn abs(self) -> Self { $tipo::abs(self) }
+            fn sqrt(self) -> Self { $tipo::sqrt(self) }
+            fn hypot(self, otro: Self) -> Self { $tipo::hypot(self, otro) }
+            fn atan2(self, otro: Self) -> Self { $tipo::atan2(self, otro) }
+            fn exp(self) -> Self { $tipo::exp(self) }
+            fn ln(self) -> Self { $tipo::ln(self) }
+            fn sin(self) -> Self { $tipo::sin(self) }
+            fn cos(self) -> Self { $tipo::cos(self) }
+            fn powf(self, exponente: Self) -> Self { $tipo::powf(self, exponente) }
+            fn es_finito(self) -> bool { $tipo::is_finite(self) }
+        }
+    )*};
+}
+
+impl_real!(f32, f64);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Complejo<T> {
+    pub re: T,
+    pub im: T,
+}
+
+impl<T> Complejo<T> {
+    pub const fn new(re: T, im: T) -> Self {
+        Self { re, im }
+    }
+}
+
+impl<T: Copy + Neg<Output = T>> Complejo<T> {
+    // a + bi -> a - bi
+    pub fn conjugado(&self) -> Self {
+        Self::new(self.re, -self.im)
+    }
+}
+
+impl<T: Copy + Add<Output = T> + Mul<Output = T>> Complejo<T> {
+    // |z|² = a² + b², sin raíz así que sirve para enteros
+    pub fn norma_cuadrada(&self) -> T {
+        self.re * self.re + self.im * self.im
+    }
+}
+
+impl<T: Real> Complejo<T> {
+    pub fn i() -> Self {
+        Self::new(T::CERO, T::UNO)
+    }
+
+    pub fn real(re: T) -> Self {
+        Self::new(re, T::CERO)
+    }
+
+    // r * (cos θ + i sen θ)
+    pub fn polar(modulo: T, argumento: T) -> Self {
+        Self::new(modulo * argumento.cos(), modulo * argumento.sin())
+    }
+
+    // La distancia al origen, hypot evita desbordar al elevar al cuadrado
+    pub fn modulo(&self) -> T {
+        self.re.hypot(self.im)
+    }
+
+    // El ángulo con el eje real, en (-π, π]
+    pub fn argumento(&self) -> T {
+        self.im.atan2(self.re)
+    }
+
+    pub fn a_polar(&self) -> (T, T) {
+        (self.modulo(), self.argumento())
+    }
+
+    // e^(a + bi) = e^a * (cos b + i sen b)
+    pub fn exp(&self) -> Self {
+        Self::polar(self.re.exp(), self.im)
+    }
+
+    // El logaritmo principal: ln|z| + i arg(z)
+    pub fn ln(&self) -> Self {
+        Self::new(self.modulo().ln(), self.argumento())
+    }
+
+    pub fn es_finito(&self) -> bool {
+        self.re.es_finito() && self.im.es_finito()
+    }
+
+    /* z^w = e^(w ln z). ln 0 no existe, con base 0 se hace como con los
+     * flotantes: 0^0 = 1 igual que en powi, 0^w = 0 si la parte real de w
+     * es positiva e infinito si es negativa. Si la parte real es 0 y la
+     * imaginaria no, no hay valor y da NaN
+     */
+    pub fn pow(&self, exponente: Self) -> Self {
+        if self.re == T::CERO && self.im == T::CERO {
+            return if exponente.im == T::CERO {
+                self.powf(exponente.re)
+            } else if exponente.re > T::CERO {
+                Self::real(T::CERO)
+            } else {
+                Self::real(T::desde_f64(f64::NAN))
+            };
+        }
+        (exponente * self.ln()).exp()
+    }
+
+    pub fn powf(&self, exponente: T) -> Self {
+        if self.re == T::CERO && self.im == T::CERO {
+            // 0.0f64.powf da 1 con 0, 0 con positivos e infinito con negativos
+            return Self::real(T::CERO.powf(exponente));
+        }
+        let (modulo, argumento) = self.a_polar();
+        Self::polar(modulo.powf(exponente), argumento * exponente)
+    }
+
+    // Multiplicando, así con exponentes enteros no se pierde precisión
+    pub fn powi(&self, exponente: i32) -> Self {
+        let mut resultado = Self::real(T::UNO);
+        let mut base = *self;
+        let mut n = exponente.unsigned_abs();
+        while n > 0 {
+            if n & 1 == 1 {
+                resultado *= base;
+            }
+            base *= base;
+            n >>= 1;
+        }
+        if exponente < 0 {
+            Self::real(T::UNO) / resultado
+        } else {
+            resultado
+        }
+    }
+
+    // La raíz principal, con la parte real >= 0
+    pub fn sqrt(&self) -> Self {
+        let dos = T::UNO + T::UNO;
+        let modulo = self.modulo();
+        let re = ((modulo + self.re) / dos).sqrt();
+        let im = ((modulo - self.re) / dos).sqrt();
+        Self::new(re, if self.im < T::CERO { -im } else { im })
+    }
+
+    // Las n soluciones de z^n = 1, repartidas en el círculo
+    pub fn raices_de_la_unidad(n: usize) -> Vec<Self> {
+        let vuelta = T::desde_f64(2.0 * std::f64::consts::PI / n as f64);
+        (0..n)
+            .map(|k| Self::polar(T::UNO, vuelta * T::desde_f64(k as f64)))
+            .collect()
+    }
+}
+
+impl<T: Add<Output = T>> Add for Complejo<T> {
+    type Output = Self;
+
+    fn add(self, otro: Self) -> Self {
+        Self::new(self.re + otro.re, self.im + otro.im)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Complejo<T> {
+    type Output = Self;
+
+    fn sub(self, otro: Self) -> Self {
+        Self::new(self.re - otro.re, self.im - otro.im)
+    }
+}
+
+// (a + bi)(c + di) = (ac - bd) + (ad + bc)i
+impl<T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T>> Mul for Complejo<T> {
+    type Output = Self;
+
+    fn mul(self, otro: Self) -> Self {
+        Self::new(
+            self.re * otro.re - self.im * otro.im,
+            self.re * otro.im + self.im * otro.re,
+        )
+    }
+}
+
+/* Se multiplica arriba y abajo por el conjugado del divisor para que
+ * abajo quede un real: (a + bi)/(c + di) = (a + bi)(c - di) / (c² + d²)
+ */
+impl<T> Div for Complejo<T>
+where
+    T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Div<Output = T>,
+{
+    type Output = Self;
+
+    fn div(self, otro: Self) -> Self {
+        let abajo = otro.norma_cuadrada();
+        Self::new(
+            (self.re * otro.re + self.im * otro.im) / abajo,
+            (self.im * otro.re - self.re * otro.im) / abajo,
+        )
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Complejo<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.re, -self.im)
+    }
+}
+
+// Con un real del lado derecho: z + 2.0, z * 3.0...
+impl<T: Add<Output = T>> Add<T> for Complejo<T> {
+    type Output = Self;
+
+    fn add(self, real: T) -> Self {
+        Self::new(self.re + real, self.im)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub<T> for Complejo<T> {
+    type Output = Self;
+
+    fn sub(self, real: T) -> Self {
+        Self::new(self.re - real, self.im)
+    }
+}
+
+impl<T: Copy + Mul<Output = T>> Mul<T> for Complejo<T> {
+    type Output = Self;
+
+    fn mul(self, real: T) -> Self {
+        Self::new(self.re * real, self.im * real)
+    }
+}
+
+impl<T: Copy + Div<Output = T>> Div<T> for Complejo<T> {
+    type Output = Self;
+
+    fn div(self, real: T) -> Self {
+        Self::new(self.re / real, self.im / real)
+    }
+}
+
+// Las versiones con = reusan las de arriba
+macro_rules! asignacion {
+    ($($asignar:ident $metodo:ident $operador:ident $funcion:ident),*) => {$(
+        impl<T> $asignar for Complejo<T>
+        where
+            Complejo<T>: Copy + $operador<Output = Complejo<T>>,
+        {
+            fn $metodo(&mut self, otro: Self) {
+                *self = $operador::$funcion(*self, otro);
+            }
+        }
+
+        impl<T> $asignar<T> for Complejo<T>
+        where
+            Complejo<T>: Copy + $operador<T, Output = Complejo<T>>,
+        {
+            fn $metodo(&mut self, real: T) {
+                *self = $operador::$funcion(*self, real);
+            }
+        }
+    )*};
+}
+
+asignacion!(
+    AddAssign add_assign Add add,
+    SubAssign sub_assign Sub sub,
+    MulAssign mul_assign Mul mul,
+    DivAssign div_assign Div div
+);
+
+// El cero de los números es su Default, así que también sirve para enteros
+impl<T: Default + Add<Output = T>> Sum for Complejo<T> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Complejo::default(), |total, z| total + z)
+    }
+}
+
+impl<T: Real> Product for Complejo<T> {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Complejo::real(T::UNO), |total, z| total * z)
+    }
+}
+
+impl<T: Real> From<T> for Complejo<T> {
+    fn from(re: T) -> Self {
+        Complejo::real(re)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ErrorComplejo {
+    Formato(String),
+    SinCoeficientes,
+    // El coeficiente del grado más alto no puede ser cero
+    PrincipalCero,
+    // Un coeficiente infinito o NaN
+    NoFinito,
+    NoConverge(usize),
+}
+
+impl fmt::Display for ErrorComplejo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ErrorComplejo::Formato(texto) => write!(
+                f,
+                "\"{texto}\" no es un complejo, debe ser como 3+4i, -2i o 5"
+            ),
+            ErrorComplejo::SinCoeficientes => write!(f, "el polinomio no tiene coeficientes"),
+            ErrorComplejo::PrincipalCero => {
+                write!(f, "el coeficiente del grado más alto no puede ser cero")
+            }
+            ErrorComplejo::NoFinito => {
+                write!(
+                    f,
+                    "los coeficientes deben ser finitos, sin NaN ni infinitos"
+                )
+            }
+            ErrorComplejo::NoConverge(iteraciones) => {
+                write!(f, "las raíces no se acomodaron en {iteraciones} vueltas")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ErrorComplejo {}
+
+/* Se escribe como 3+4i o 3-4i. La parte imaginaria se formatea primero
+ * para ver si trae el signo, así -0.0 no queda como 3+-0i. La precisión
+ * se pasa a las dos partes: {:.2} da 1.00+0.50i
+ */
+impl<T: fmt::Display> fmt::Display for Complejo<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (re, im) = match f.precision() {
+            Some(decimales) => (
+                format!("{:.*}", decimales, self.re),
+                format!("{:.*}", decimales, self.im),
+            ),
+            None => (self.re.to_string(), self.im.to_string()),
+        };
+        match im.strip_prefix('-') {
+            Some(sin_signo) => write!(f, "{re}-{sin_signo}i"),
+            None => write!(f, "{re}+{im}i"),
+        }
+    }
+}
+
+/* Acepta 3+4i, 3 - 4i, -2.5i, 5, i y -i. El signo que separa las partes
+ * es el último + o - que no esté al principio ni después de una e, para
+ * que 1e-3+2i funcione
+ */
+impl<T: FromStr + Default> FromStr for Complejo<T> {
+    type Err = ErrorComplejo;
+
+    fn from_str(texto: &str) -> Result<Self, Self::Err> {
+        let formato = || ErrorComplejo::Formato(texto.to_string());
+        let limpio: String = texto.chars().filter(|c| !c.is_whitespace()).collect();
+        if limpio.is_empty() {
+            return Err(formato());
+        }
+        let leer = |parte: &str| parte.parse::<T>().map_err(|_| formato());
+
+        let Some(cuerpo) = limpio.strip_suffix('i') else {
+            return Ok(Complejo::new(leer(&limpio)?, T::default()));
+        };
+        let bytes = cuerpo.as_bytes();
+        let corte = (1..bytes.len())
+            .rev()
+            .find(|&k| matches!(bytes[k], b'+' | b'-') && !matches!(bytes[k - 1], b'e' | b'E'));
+        let (re, im) = match corte {
+            Some(k) => (leer(&cuerpo[..k])?, &cuerpo[k..]),
+            None => (T::default(), cuerpo),
+        };
+        // i sola vale 1i
+        let im = match im {
+            "" | "+" => leer("1")?,
+            "-" => leer("-1")?,
+            numero => leer(numero)?,
+        };
+        Ok(Complejo::new(re, im))
+    }
+}
+
+// Horner con los coeficientes del grado más alto al más bajo
+pub fn evaluar<T: Real>(coeficientes: &[Complejo<T>], z: Complejo<T>) -> Complejo<T> {
+    coeficientes
+        .iter()
+        .fold(Complejo::real(T::CERO), |total, &c| total * z + c)
+}
+
+const MAXIMO_DE_VUELTAS: usize = 1000;
+
+/* Durand-Kerner: empieza con n puntos repartidos en un círculo que
+ * encierra todas las raíces y a cada uno lo mueve con
+ *
+ *     z_k <- z_k - p(z_k) / prod_{j != k} (z_k - z_j)
+ *
+ * hasta que ya casi no se mueven. Los coeficientes van del grado más
+ * alto al más bajo: [1, 0, -1] es z² - 1. Con raíces simples converge
+ * muy rápido; las repetidas salen con la mitad de los dígitos más o menos.
+ * Si algún paso se desborda a infinito o NaN ya no va a converger, y
+ * como NaN <= tolerancia es falso hay que revisarlo aparte
+ */
+pub fn raices<T: Real>(coeficientes: &[Complejo<T>]) -> Result<Vec<Complejo<T>>, ErrorComplejo> {
+    let Some(&principal) = coeficientes.first() else {
+        return Err(ErrorComplejo::SinCoeficientes);
+    };
+    if !coeficientes.iter().all(Complejo::es_finito) {
+        return Err(ErrorComplejo::NoFinito);
+    }
+    if principal.re == T::CERO && principal.im == T::CERO {
+        return Err(ErrorComplejo::PrincipalCero);
+    }
+    // Mónico, así p(z) = z^n + ...
+    let monico: Vec<_> = coeficientes.iter().map(|&c| c / principal).collect();
+    let grado = monico.len() - 1;
+
+    // Todas las raíces caben en |z| <= 1 + max |a_k| (cota de Cauchy)
+    let radio = monico[1..]
+        .iter()
+        .map(Complejo::modulo)
+        .fold(T::UNO, |mayor, m| if m > mayor { m } else { mayor })
+        + T::UNO;
+    // El giro de 0.4 evita empezar justo sobre el eje real, que es simétrico
+    let giro = T::desde_f64(0.4);
+    let mut actuales: Vec<Complejo<T>> = Complejo::<T>::raices_de_la_unidad(grado)
+        .into_iter()
+        .map(|w| w * Complejo::polar(radio, giro))
+        .collect();
+
+    let tolerancia = T::EPSILON.sqrt();
+    for vuelta in 1..=MAXIMO_DE_VUELTAS {
+        let mut mayor_paso = T::CERO;
+        for k in 0..grado {
+            let z = actuales[k];
+            let abajo: Complejo<T> = (0..grado)
+                .filter(|&j| j != k)
+                .map(|j| z - actuales[j])
+                .product();
+            let paso = evaluar(&monico, z) / abajo;
+            actuales[k] = z - paso;
+            // Relativo al tamaño de la raíz, con un mínimo de 1
+            let escala = T::UNO + z.modulo();
+            let relativo = paso.modulo() / escala;
+            if !relativo.es_finito() {
+                return Err(ErrorComplejo::NoConverge(vuelta));
+            }
+            if relativo > mayor_paso {
+                mayor_paso = relativo;
+            }
+        }
+        if mayor_paso <= tolerancia {
+            return Ok(actuales);
+        }
+    }
+    Err(ErrorComplejo::NoConverge(MAXIMO_DE_VUELTAS))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aleatorio::Aleatorio;
+    use std::f64::consts::PI;
+
+    fn cerca(a: Complejo<f64>, b: Complejo<f64>, tolerancia: f64) -> bool {
+        (a - b).modulo() <= tolerancia
+    }
+
+    #[test]
+    fn operaciones_con_enteros_y_reales() {
+        let a = Complejo::new(3, 4);
+        let b = Complejo::new(1, -2);
+        assert_eq!(a + b, Complejo::new(4, 2));
+        assert_eq!(a - b, Complejo::new(2, 6));
+        assert_eq!(a * b, Complejo::new(11, -2));
+        assert_eq!(-a, Complejo::new(-3, -4));
+        assert_eq!(a.conjugado(), Complejo::new(3, -4));
+        assert_eq!(a.norma_cuadrada(), 25);
+        assert_eq!(a * 2, Complejo::new(6, 8));
+        assert_eq!(
+            [a, b, a].into_iter().sum::<Complejo<i32>>(),
+            Complejo::new(7, 6)
+        );
+
+        let mut z = Complejo::new(1.0, 2.0);
+        z += Complejo::new(1.0, 1.0);
+        z *= Complejo::new(0.0, 1.0);
+        z -= 1.0;
+        z /= 2.0;
+        assert_eq!(z, Complejo::new(-2.0, 1.0));
+        z /= Complejo::new(-2.0, 1.0);
+        assert!(cerca(z, Complejo::real(1.0), 1e-15));
+        assert!(cerca(
+            Complejo::new(11.0, -2.0) / Complejo::new(1.0, -2.0),
+            Complejo::new(3.0, 4.0),
+            1e-12
+        ));
+    }
+
+    #[test]
+    fn funciones_y_forma_polar() {
+        let i = Complejo::<f64>::i();
+        // e^(iπ) + 1 = 0
+        assert!(cerca(
+            Complejo::new(0.0, PI).exp() + 1.0,
+            Complejo::real(0.0),
+            1e-15
+        ));
+        assert_eq!(Complejo::new(3.0, 4.0).modulo(), 5.0);
+        assert!((i.argumento() - PI / 2.0).abs() < 1e-15);
+        // i^i es real
+        assert!(cerca(i.pow(i), Complejo::real((-PI / 2.0).exp()), 1e-15));
+        assert!(cerca(
+            Complejo::real(-4.0).sqrt(),
+            Complejo::new(0.0, 2.0),
+            1e-15
+        ));
+        assert!(cerca(
+            Complejo::new(0.0, -4.0).sqrt(),
+            Complejo::new(2f64.sqrt(), -(2f64.sqrt())),
+            1e-12
+        ));
+
+        let mut aleatorio = Aleatorio::new(22);
+        for _ in 0..200 {
+            let z = Complejo::new(aleatorio.normal(0.0, 3.0), aleatorio.normal(0.0, 3.0));
+            let (r, theta) = z.a_polar();
+            assert!(cerca(Complejo::polar(r, theta), z, 1e-12));
+            assert!(cerca(
+                z.exp().ln().exp(),
+                z.exp(),
+                1e-9 * (1.0 + z.exp().modulo())
+            ));
+            assert!(cerca(z.ln().exp(), z, 1e-12 * (1.0 + r)));
+            assert!(cerca(z.sqrt() * z.sqrt(), z, 1e-12 * (1.0 + r)));
+            assert!(z.sqrt().re >= 0.0);
+            assert!(cerca(z.powi(3), z * z * z, 1e-10 * (1.0 + r).powi(3)));
+            assert!(cerca(z.powi(-2) * z * z, Complejo::real(1.0), 1e-12));
+            assert!(cerca(z.powf(0.5), z.sqrt(), 1e-12 * (1.0 + r)));
+        }
+    }
+
+    #[test]
+    fn escribir_y_leer() {
+        assert_eq!(Complejo::new(3, 4).to_string(), "3+4i");
+        assert_eq!(Complejo::new(3, -4).to_string(), "3-4i");
+        assert_eq!(Complejo::new(1.0, -0.0).to_string(), "1-0i");
+        assert_eq!(format!("{:.2}", Complejo::new(1.0, 0.5)), "1.00+0.50i");
+
+        let casos = [
+            ("3+4i", Complejo::new(3.0, 4.0)),
+            (" 3 - 4i ", Complejo::new(3.0, -4.0)),
+            ("-2.5i", Complejo::new(0.0, -2.5)),
+            ("5", Complejo::new(5.0, 0.0)),
+            ("i", Complejo::new(0.0, 1.0)),
+            ("-i", Complejo::new(0.0, -1.0)),
+            ("2-i", Complejo::new(2.0, -1.0)),
+            ("1e-3+2E+1i", Complejo::new(0.001, 20.0)),
+        ];
+        for (texto, esperado) in casos {
+            assert_eq!(texto.parse::<Complejo<f64>>(), Ok(esperado), "{texto}");
+        }
+        assert_eq!("-7+2i".parse::<Complejo<i32>>(), Ok(Complejo::new(-7, 2)));
+        for malo in ["", "3+4", "3+4j", "i3", "3++4i", "abc"] {
+            assert_eq!(
+                malo.parse::<Complejo<f64>>(),
+                Err(ErrorComplejo::Formato(malo.to_string())),
+                "{malo}"
+            );
+        }
+        let z = Complejo::new(-1.25, 3.5);
+        assert_eq!(z.to_string().parse::<Complejo<f64>>(), Ok(z));
+    }
+
+    #[test]
+    fn raices_de_polinomios() {
+        let c = Complejo::<f64>::real;
+        // z² + 1 = 0 da ±i
+        let mut halladas = raices(&[c(1.0), c(0.0), c(1.0)]).unwrap();
+        halladas.sort_by(|a, b| a.im.total_cmp(&b.im));
+        assert!(cerca(halladas[0], Complejo::new(0.0, -1.0), 1e-12));
+        assert!(cerca(halladas[1], Complejo::new(0.0, 1.0), 1e-12));
+
+        // z³ - 1 = 0 da las raíces cúbicas de la unidad
+        let halladas = raices(&[c(2.0), c(0.0), c(0.0), c(-2.0)]).unwrap();
+        for w in Complejo::raices_de_la_unidad(3) {
+            assert!(halladas.iter().any(|&z| cerca(z, w, 1e-12)));
+        }
+
+        // Se arma el polinomio multiplicando (z - r) y se buscan las r
+        let mut aleatorio = Aleatorio::new(7);
+        for grado in 1..=8 {
+            let originales: Vec<_> = (0..grado)
+                .map(|_| Complejo::new(aleatorio.normal(0.0, 2.0), aleatorio.normal(0.0, 2.0)))
+                .collect();
+            let mut coeficientes = vec![c(1.0)];
+            for &r in &originales {
+                let mut siguiente = coeficientes.clone();
+                siguiente.push(c(0.0));
+                for (k, &a) in coeficientes.iter().enumerate() {
+                    siguiente[k + 1] -= a * r;
+                }
+                coeficientes = siguiente;
+            }
+            let halladas = raices(&coeficientes).unwrap();
+            assert_eq!(halladas.len(), grado);
+            for r in &originales {
+                assert!(
+                    halladas.iter().any(|&z| cerca(z, *r, 1e-7)),
+                    "{r} grado {grado}"
+                );
+            }
+        }
+
+        // (z - 1)² tiene una raíz doble
+        for z in raices(&[c(1.0), c(-2.0), c(1.0)]).unwrap() {
+            assert!(cerca(z, c(1.0), 1e-6));
+        }
+        assert_eq!(raices(&[c(4.0)]), Ok(vec![]));
+        assert_eq!(raices::<f64>(&[]), Err(ErrorComplejo::SinCoeficientes));
+        assert_eq!(raices(&[c(0.0), c(1.0)]), Err(ErrorComplejo::PrincipalCero));
+        for malo in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            assert_eq!(raices(&[c(1.0), c(malo)]), Err(ErrorComplejo::NoFinito));
+            assert_eq!(raices(&[c(malo), c(1.0)]), Err(ErrorComplejo::NoFinito));
+            let imaginario = Complejo::new(1.0, malo);
+            assert_eq!(
+                raices(&[c(1.0), c(0.0), imaginario]),
+                Err(ErrorComplejo::NoFinito)
+            );
+        }
+        // Finitos pero tan grandes que z - r se desborda en la primera vuelta
+        assert!(matches!(
+            raices(&[c(1e-300), c(0.0), c(1e300)]),
+            Err(ErrorComplejo::NoConverge(_))
+        ));
+    }
+
+    #[test]
+    fn potencias_de_cero() {
+        let cero = Complejo::<f64>::real(0.0);
+        assert_eq!(cero.powi(0), Complejo::real(1.0));
+        assert_eq!(cero.powf(0.0), Complejo::real(1.0));
+        assert_eq!(cero.pow(cero), Complejo::real(1.0));
+        assert_eq!(cero.powf(2.5), cero);
+        assert_eq!(cero.pow(Complejo::new(2.0, 3.0)), cero);
+        assert!(!cero.powi(-1).es_finito());
+        assert!(!cero.powf(-0.5).es_finito());
+        assert!(!cero.pow(Complejo::real(-2.0)).es_finito());
+        assert!(!cero.pow(Complejo::new(-2.0, 1.0)).es_finito());
+        assert!(!cero.pow(Complejo::i()).es_finito());
+    }
+}
@@ -0,0 +1,604 @@
+/* Matrices dispersas: casi todos sus datos son cero, como una tabla de
+ * votos con miles de municipios y cientos de candidatos donde cada
+ * municipio sólo votó por unos cuantos. En una Matriz densa los ceros
+ * ocupan lo mismo que lo demás, aquí sólo se guardan los que no son cero
+ *
+ * - Coo (coordenadas): una lista de (fila, columna, valor) en cualquier
+ *   orden. Es fácil de armar de a poco y de leer de un archivo
+ * - Csr (filas comprimidas): los valores ordenados por fila y un índice
+ *   de dónde empieza cada fila. Sirve para multiplicar y sacar filas
+ *
+ * Lo normal es armar una Coo y pasarla a Csr para trabajar. Las dos se
+ * leen y se escriben en el formato de texto Matrix Market
+ * (https://math.nist.gov/MatrixMarket/formats.html)
+ */
+use std::fmt;
+use std::io::{self, BufRead, Write};
+use std::ops::Range;
+use std::str::FromStr;
+
+use super::matriz::{Matriz, Numero};
+
+#[derive(Debug)]
+pub enum ErrorDispersa {
+    FueraDeRango {
+        fila: usize,
+        columna: usize,
+        dimensiones: (usize, usize),
+    },
+    DimensionesIncompatibles {
+        izquierda: (usize, usize),
+        derecha: (usize, usize),
+    },
+    Archivo(io::Error),
+    // Un problema en el archivo Matrix Market, con el número de línea
+    MatrixMarket {
+        linea: usize,
+        mensaje: String,
+    },
+}
+
+impl fmt::Display for ErrorDispersa {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ErrorDispersa::FueraDeRango {
+                fila,
+                columna,
+                dimensiones,
+            } => write!(
+                f,
+                "({fila}, {columna}) está fuera de una matriz de {}x{}",
+                dimensiones.0, dimensiones.1
+            ),
+            ErrorDispersa::DimensionesIncompatibles { izquierda, derecha } => write!(
+                f,
+                "no se puede multiplicar una matriz de {}x{} por una de {}x{}",
+                izquierda.0, izquierda.1, derecha.0, derecha.1
+            ),
+            ErrorDispersa::Archivo(error) => write!(f, "no se pudo leer el archivo: {error}"),
+            ErrorDispersa::MatrixMarket { linea, mensaje } => write!(f, "línea {linea}: {mensaje}"),
+        }
+    }
+}
+
+impl std::error::Error for ErrorDispersa {}
+
+impl From<io::Error> for ErrorDispersa {
+    fn from(error: io::Error) -> Self {
+        ErrorDispersa::Archivo(error)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Coo<T> {
+    filas: usize,
+    columnas: usize,
+    entradas: Vec<(usize, usize, T)>,
+}
+
+impl<T: Numero> Coo<T> {
+    pub fn new(filas: usize, columnas: usize) -> Self {
+        Self {
+            filas,
+            columnas,
+            entradas: Vec::new(),
+        }
+    }
+
+    /* Si la misma posición se agrega dos veces los valores se suman al
+     * pasar a densa o a Csr, así se puede contar voto por voto
+     */
+    pub fn agregar(&mut self, fila: usize, columna: usize, valor: T) -> Result<(), ErrorDispersa> {
+        if fila >= self.filas || columna >= self.columnas {
+            return Err(ErrorDispersa::FueraDeRango {
+                fila,
+                columna,
+                dimensiones: self.dimensiones(),
+            });
+        }
+        self.entradas.push((fila, columna, valor));
+        Ok(())
+    }
+
+    pub fn desde_densa(densa: &Matriz<T>) -> Self {
+        let mut coo = Self::new(densa.filas(), densa.columnas());
+        for (i, fila) in densa.iter_filas().enumerate() {
+            for (j, &valor) in fila.iter().enumerate() {
+                if valor != T::CERO {
+                    coo.entradas.push((i, j, valor));
+                }
+            }
+        }
+        coo
+    }
+
+    pub fn dimensiones(&self) -> (usize, usize) {
+        (self.filas, self.columnas)
+    }
+
+    pub fn entradas(&self) -> &[(usize, usize, T)] {
+        &self.entradas
+    }
+
+    pub fn a_densa(&self) -> Matriz<T> {
+        let mut densa = Matriz::ceros(self.filas, self.columnas);
+        for &(i, j, valor) in &self.entradas {
+            densa[(i, j)] = densa[(i, j)] + valor;
+        }
+        densa
+    }
+
+    // Sólo cambia filas por columnas en cada entrada
+    pub fn traspuesta(&self) -> Self {
+        Self {
+            filas: self.columnas,
+            columnas: self.filas,
+            entradas: self.entradas.iter().map(|&(i, j, v)| (j, i, v)).collect(),
+        }
+    }
+
+    /* Se ordena por (fila, columna), se suman las repetidas y se quitan
+     * las que quedaron en cero
+     */
+    pub fn a_csr(&self) -> Csr<T> {
+        let mut ordenadas = self.entradas.clone();
+        ordenadas.sort_by_key(|&(i, j, _)| (i, j));
+        let mut unidas: Vec<(usize, usize, T)> = Vec::with_capacity(ordenadas.len());
+        for (i, j, valor) in ordenadas {
+            match unidas.last_mut() {
+                Some(ultima) if (ultima.0, ultima.1) == (i, j) => ultima.2 = ultima.2 + valor,
+                _ => unidas.push((i, j, valor)),
+            }
+        }
+        unidas.retain(|&(_, _, valor)| valor != T::CERO);
+
+        let mut inicios = vec![0; self.filas + 1];
+        for &(i, _, _) in &unidas {
+            inicios[i + 1] += 1;
+        }
+        for i in 0..self.filas {
+            inicios[i + 1] += inicios[i];
+        }
+        Csr {
+            filas: self.filas,
+            columnas: self.columnas,
+            inicios,
+            indices: unidas.iter().map(|&(_, j, _)| j).collect(),
+            valores: unidas.iter().map(|&(_, _, v)| v).collect(),
+        }
+    }
+}
+
+/* Csr guarda tres Vec:
+ * - valores: los que no son cero, fila por fila y de izquierda a derecha
+ * - indices: la columna de cada valor
+ * - inicios: la fila i son las posiciones inicios[i]..inicios[i + 1]
+ *
+ * Por ejemplo [[5, 0, 0], [0, 0, 0], [0, 7, 9]] queda como
+ * valores [5, 7, 9], indices [0, 1, 2] e inicios [0, 1, 1, 3]
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct Csr<T> {
+    filas: usize,
+    columnas: usize,
+    inicios: Vec<usize>,
+    indices: Vec<usize>,
+    valores: Vec<T>,
+}
+
+impl<T: Numero> Csr<T> {
+    pub fn desde_densa(densa: &Matriz<T>) -> Self {
+        Coo::desde_densa(densa).a_csr()
+    }
+
+    pub fn dimensiones(&self) -> (usize, usize) {
+        (self.filas, self.columnas)
+    }
+
+    pub fn no_ceros(&self) -> usize {
+        self.valores.len()
+    }
+
+    // Qué fracción de la matriz no es cero
+    pub fn densidad(&self) -> f64 {
+        let total = self.filas * self.columnas;
+        if total == 0 {
+            0.0
+        } else {
+            self.no_ceros() as f64 / total as f64
+        }
+    }
+
+    // Las columnas y los valores de una fila
+    pub fn fila(&self, fila: usize) -> (&[usize], &[T]) {
+        let rango = self.inicios[fila]..self.inicios[fila + 1];
+        (&self.indices[rango.clone()], &self.valores[rango])
+    }
+
+    // Las columnas de cada fila están ordenadas, se busca por bisección
+    pub fn get(&self, fila: usize, columna: usize) -> Option<T> {
+        if fila >= self.filas || columna >= self.columnas {
+            return None;
+        }
+        let (indices, valores) = self.fila(fila);
+        Some(match indices.binary_search(&columna) {
+            Ok(k) => valores[k],
+            Err(_) => T::CERO,
+        })
+    }
+
+    pub fn a_densa(&self) -> Matriz<T> {
+        let mut densa = Matriz::ceros(self.filas, self.columnas);
+        for i in 0..self.filas {
+            let (indices, valores) = self.fila(i);
+            for (&j, &valor) in indices.iter().zip(valores) {
+                densa[(i, j)] = valor;
+            }
+        }
+        densa
+    }
+
+    pub fn a_coo(&self) -> Coo<T> {
+        let mut coo = Coo::new(self.filas, self.columnas);
+        for i in 0..self.filas {
+            let (indices, valores) = self.fila(i);
+            coo.entradas.extend(
+                indices
+                    .iter()
+                    .zip(valores)
+                    .map(|(&j, &valor)| (i, j, valor)),
+            );
+        }
+        coo
+    }
+
+    // Una Csr nueva con sólo esas filas, sin tocar los demás datos
+    pub fn filas_en(&self, rango: Range<usize>) -> Csr<T> {
+        assert!(
+            rango.start <= rango.end && rango.end <= self.filas,
+            "las filas {rango:?} no están en una matriz de {} filas",
+            self.filas
+        );
+        let desde = self.inicios[rango.start];
+        let hasta = self.inicios[rango.end];
+        Csr {
+            filas: rango.len(),
+            columnas: self.columnas,
+            inicios: self.inicios[rango.start..=rango.end]
+                .iter()
+                .map(|&inicio| inicio - desde)
+                .collect(),
+            indices: self.indices[desde..hasta].to_vec(),
+            valores: self.valores[desde..hasta].to_vec(),
+        }
+    }
+
+    /* Se cuentan los datos de cada columna para saber dónde empieza cada
+     * fila de la traspuesta y luego se acomodan. Recorriendo las filas en
+     * orden, las columnas de la traspuesta salen ordenadas solas
+     */
+    pub fn traspuesta(&self) -> Csr<T> {
+        let mut inicios = vec![0; self.columnas + 1];
+        for &j in &self.indices {
+            inicios[j + 1] += 1;
+        }
+        for j in 0..self.columnas {
+            inicios[j + 1] += inicios[j];
+        }
+        let mut siguiente = inicios.clone();
+        let mut indices = vec![0; self.no_ceros()];
+        let mut valores = vec![T::CERO; self.no_ceros()];
+        for i in 0..self.filas {
+            let (columnas, datos) = self.fila(i);
+            for (&j, &valor) in columnas.iter().zip(datos) {
+                let k = siguiente[j];
+                indices[k] = i;
+                valores[k] = valor;
+                siguiente[j] += 1;
+            }
+        }
+        Csr {
+            filas: self.columnas,
+            columnas: self.filas,
+            inicios,
+            indices,
+            valores,
+        }
+    }
+
+    pub fn multiplicar_vector(&self, x: &[T]) -> Result<Vec<T>, ErrorDispersa> {
+        if x.len() != self.columnas {
+            return Err(ErrorDispersa::DimensionesIncompatibles {
+                izquierda: self.dimensiones(),
+                derecha: (x.len(), 1),
+            });
+        }
+        Ok((0..self.filas)
+            .map(|i| {
+                let (indices, valores) = self.fila(i);
+                indices
+                    .iter()
+                    .zip(valores)
+                    .fold(T::CERO, |total, (&j, &valor)| total + valor * x[j])
+            })
+            .collect())
+    }
+
+    // Cada dato que no es cero suma su fila de la densa, los ceros no cuestan
+    pub fn multiplicar_densa(&self, densa: &Matriz<T>) -> Result<Matriz<T>, ErrorDispersa> {
+        if densa.filas() != self.columnas {
+            return Err(ErrorDispersa::DimensionesIncompatibles {
+                izquierda: self.dimensiones(),
+                derecha: densa.dimensiones(),
+            });
+        }
+        let mut resultado = Matriz::ceros(self.filas, densa.columnas());
+        for i in 0..self.filas {
+            let (indices, valores) = self.fila(i);
+            for (&k, &valor) in indices.iter().zip(valores) {
+                for (c, &b) in resultado.fila_mut(i).iter_mut().zip(densa.fila(k)) {
+                    *c = *c + valor * b;
+                }
+            }
+        }
+        Ok(resultado)
+    }
+}
+
+/* Matrix Market en coordenadas se ve así:
+ *
+ *     %%MatrixMarket matrix coordinate real general
+ *     % los comentarios empiezan con %
+ *     3 3 2
+ *     1 1 5
+ *     3 2 7.5
+ *
+ * La primera línea de datos son filas, columnas y cuántos valores hay, y
+ * luego cada valor con su fila y columna contando desde 1. Se aceptan
+ * real e integer, y general o symmetric (donde sólo viene la mitad de
+ * abajo y la otra se copia)
+ */
+impl<T: Numero + FromStr> Coo<T> {
+    pub fn leer_matrix_market(lector: impl BufRead) -> Result<Self, ErrorDispersa> {
+        let mut lineas = lector.lines().enumerate();
+        let error = |linea: usize, mensaje: &str| ErrorDispersa::MatrixMarket {
+            linea: linea + 1,
+            mensaje: mensaje.to_string(),
+        };
+
+        let encabezado = match lineas.next() {
+            Some((_, linea)) => linea?.to_lowercase(),
+            None => return Err(error(0, "el archivo está vacío")),
+        };
+        let partes: Vec<&str> = encabezado.split_whitespace().collect();
+        let simetrica = match partes.as_slice() {
+            ["%%matrixmarket", "matrix", "coordinate", "real" | "integer", simetria] => {
+                match *simetria {
+                    "general" => false,
+                    "symmetric" => true,
+                    _ => return Err(error(0, "sólo se aceptan matrices general o symmetric")),
+                }
+            }
+            ["%%matrixmarket", "matrix", "coordinate", ..] => {
+                return Err(error(0, "sólo se aceptan valores real o integer"))
+            }
+            _ => {
+                return Err(error(
+                    0,
+                    "se esperaba %%MatrixMarket matrix coordinate <tipo> <simetría>",
+                ))
+            }
+        };
+
+        // Las demás líneas sin comentarios ni vacías, con su número
+        let mut datos = lineas.filter_map(|(numero, linea)| match linea {
+            Ok(linea) if linea.trim().is_empty() || linea.starts_with('%') => None,
+            otra => Some((numero, otra)),
+        });
+
+        let (linea_del_tamanio, linea) = datos
+            .next()
+            .ok_or_else(|| error(0, "falta la línea con filas, columnas y valores"))?;
+        let tamanio: Vec<usize> = linea?
+            .split_whitespace()
+            .map(str::parse)
+            .collect::<Result<_, _>>()
+            .map_err(|_| error(linea_del_tamanio, "el tamaño deben ser tres números"))?;
+        let [filas, columnas, cantidad] = tamanio[..] else {
+            return Err(error(linea_del_tamanio, "el tamaño deben ser tres números"));
+        };
+
+        let mut coo = Coo::new(filas, columnas);
+        let mut leidos = 0;
+        for (numero, linea) in datos {
+            let linea = linea?;
+            let partes: Vec<&str> = linea.split_whitespace().collect();
+            let [fila, columna, valor] = partes[..] else {
+                return Err(error(numero, "cada dato debe ser fila, columna y valor"));
+            };
+            let (Ok(fila), Ok(columna)) = (fila.parse::<usize>(), columna.parse::<usize>()) else {
+                return Err(error(numero, "la fila y la columna deben ser enteros"));
+            };
+            let valor: T = valor
+                .parse()
+                .map_err(|_| error(numero, &format!("\"{valor}\" no es un valor válido")))?;
+            if fila == 0 || columna == 0 || fila > filas || columna > columnas {
+                return Err(error(
+                    numero,
+                    &format!("({fila}, {columna}) no está en una matriz de {filas}x{columnas}"),
+                ));
+            }
+            coo.entradas.push((fila - 1, columna - 1, valor));
+            if simetrica && fila != columna {
+                coo.entradas.push((columna - 1, fila - 1, valor));
+            }
+            leidos += 1;
+        }
+        if leidos != cantidad {
+            // El error va en la línea que anunció cuántos eran
+            return Err(error(
+                linea_del_tamanio,
+                &format!("se anunciaron {cantidad} valores y hay {leidos}"),
+            ));
+        }
+        Ok(coo)
+    }
+
+    // Siempre general y real, que también sirve para enteros
+    pub fn escribir_matrix_market(&self, mut escritor: impl Write) -> io::Result<()> {
+        writeln!(escritor, "%%MatrixMarket matrix coordinate real general")?;
+        writeln!(
+            escritor,
+            "{} {} {}",
+            self.filas,
+            self.columnas,
+            self.entradas.len()
+        )?;
+        for (i, j, valor) in &self.entradas {
+            writeln!(escritor, "{} {} {valor}", i + 1, j + 1)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aleatorio::Aleatorio;
+
+    // Una matriz con más o menos un dato de cada diez que no es cero
+    fn dispersa_aleatoria(filas: usize, columnas: usize, aleatorio: &mut Aleatorio) -> Matriz<i64> {
+        Matriz::desde_funcion(filas, columnas, |_, _| {
+            if aleatorio.hasta(10) == 0 {
+                aleatorio.hasta(19) as i64 - 9
+            } else {
+                0
+            }
+        })
+    }
+
+    #[test]
+    fn conversiones_con_la_densa() {
+        let densa = Matriz::desde_filas(vec![vec![5, 0, 0], vec![0, 0, 0], vec![0, 7, 9]]).unwrap();
+        let csr = Csr::desde_densa(&densa);
+        assert_eq!(csr.fila(2), (&[1, 2][..], &[7, 9][..]));
+        assert_eq!(csr.fila(1), (&[][..], &[][..]));
+        assert_eq!(csr.get(2, 1), Some(7));
+        assert_eq!(csr.get(0, 1), Some(0));
+        assert_eq!(csr.get(3, 0), None);
+        assert_eq!(csr.a_densa(), densa);
+        assert_eq!(csr.a_coo().a_densa(), densa);
+
+        // Las repetidas se suman y si dan cero desaparecen
+        let mut coo = Coo::new(2, 2);
+        coo.agregar(1, 1, 3).unwrap();
+        coo.agregar(0, 1, 4).unwrap();
+        coo.agregar(1, 1, 2).unwrap();
+        coo.agregar(0, 0, 1).unwrap();
+        coo.agregar(0, 0, -1).unwrap();
+        assert!(matches!(
+            coo.agregar(2, 0, 1),
+            Err(ErrorDispersa::FueraDeRango { fila: 2, .. })
+        ));
+        assert_eq!(
+            coo.a_densa(),
+            Matriz::desde_filas(vec![vec![0, 4], vec![0, 5]]).unwrap()
+        );
+        assert_eq!(coo.a_csr().no_ceros(), 2);
+        assert_eq!(coo.traspuesta().a_densa(), coo.a_densa().traspuesta());
+
+        let mut aleatorio = Aleatorio::new(24);
+        for (filas, columnas) in [(1, 1), (7, 3), (40, 60), (100, 20)] {
+            let densa = dispersa_aleatoria(filas, columnas, &mut aleatorio);
+            let csr = Csr::desde_densa(&densa);
+            assert_eq!(csr.a_densa(), densa);
+            assert_eq!(Coo::desde_densa(&densa).a_densa(), densa);
+            assert_eq!(csr.traspuesta().a_densa(), densa.traspuesta());
+            assert_eq!(csr.traspuesta().traspuesta(), csr);
+            assert_eq!(
+                csr.no_ceros(),
+                densa.datos().iter().filter(|&&x| x != 0).count()
+            );
+        }
+    }
+
+    #[test]
+    fn multiplicar_y_cortar_como_la_densa() {
+        let mut aleatorio = Aleatorio::new(2024);
+        for (n, m, p) in [(5, 4, 3), (50, 80, 10), (120, 30, 1)] {
+            let a = dispersa_aleatoria(n, m, &mut aleatorio);
+            let b = Matriz::desde_funcion(m, p, |_, _| aleatorio.hasta(21) as i64 - 10);
+            let csr = Csr::desde_densa(&a);
+            assert_eq!(csr.multiplicar_densa(&b).unwrap(), &a * &b);
+
+            let x: Vec<i64> = b.columna(0).copied().collect();
+            let esperado = &a * &Matriz::desde_vec(m, 1, x.clone()).unwrap();
+            assert_eq!(csr.multiplicar_vector(&x).unwrap(), esperado.datos());
+
+            let corte = csr.filas_en(1..n - 1);
+            assert_eq!(corte.dimensiones(), (n - 2, m));
+            for i in 0..n - 2 {
+                assert_eq!(corte.fila(i), csr.fila(i + 1));
+            }
+            assert_eq!(csr.filas_en(2..2).no_ceros(), 0);
+        }
+
+        let csr = Csr::desde_densa(&Matriz::<i64>::identidad(3));
+        assert!(matches!(
+            csr.multiplicar_densa(&Matriz::ceros(2, 2)),
+            Err(ErrorDispersa::DimensionesIncompatibles {
+                izquierda: (3, 3),
+                derecha: (2, 2)
+            })
+        ));
+        assert!(csr.multiplicar_vector(&[1, 2]).is_err());
+    }
+
+    #[test]
+    fn matrix_market() {
+        let mut aleatorio = Aleatorio::new(3);
+        let densa = dispersa_aleatoria(30, 20, &mut aleatorio);
+        let coo = Coo::desde_densa(&densa);
+        let mut texto = Vec::new();
+        coo.escribir_matrix_market(&mut texto).unwrap();
+        let leida = Coo::<i64>::leer_matrix_market(texto.as_slice()).unwrap();
+        assert_eq!(leida, coo);
+
+        let simetrica = "%%MatrixMarket matrix coordinate real symmetric\n\
+                         % una matriz de 3x3\n\
+                         \n\
+                         3 3 3\n\
+                         1 1 2.5\n\
+                         3 1 -1\n\
+                         2 2 4\n";
+        let leida = Coo::<f64>::leer_matrix_market(simetrica.as_bytes()).unwrap();
+        assert_eq!(
+            leida.a_densa(),
+            Matriz::desde_filas(vec![
+                vec![2.5, 0.0, -1.0],
+                vec![0.0, 4.0, 0.0],
+                vec![-1.0, 0.0, 0.0]
+            ])
+            .unwrap()
+        );
+
+        let linea_del_error = |texto: &str| match Coo::<f64>::leer_matrix_market(texto.as_bytes()) {
+            Err(ErrorDispersa::MatrixMarket { linea, .. }) => linea,
+            otro => panic!("se esperaba un error y dio {otro:?}"),
+        };
+        let general = "%%MatrixMarket matrix coordinate real general\n";
+        assert_eq!(linea_del_error(""), 1);
+        assert_eq!(
+            linea_del_error("%%MatrixMarket matrix array real general\n"),
+            1
+        );
+        assert_eq!(
+            linea_del_error("%%MatrixMarket matrix coordinate complex general\n"),
+            1
+        );
+        assert_eq!(linea_del_error(&format!("{general}2 2\n")), 2);
+        assert_eq!(linea_del_error(&format!("{general}2 2 1\n3 1 1\n")), 3);
+        assert_eq!(linea_del_error(&format!("{general}2 2 1\n1 1 x\n")), 3);
+        assert_eq!(linea_del_error(&format!("{general}2 2 2\n1 1 1\n")), 2);
+    }
+}
@@ -0,0 +1,728 @@
+/* Enteros de tamaño arbitrario. En tipos_datos.rs el más grande es i128
+ * (de -(2^127) a 2^127-1), con el que ya no cabe ni el factorial de 35.
+ * BigInt guarda el signo aparte y el valor absoluto como una lista de
+ * "dígitos" en base 2^32 (u32), del menos al más significativo:
+ *
+ *   5_000_000_000 = 1 * 2^32 + 705_032_704  ->  [705_032_704, 1]
+ *
+ * Las operaciones son las de la primaria pero con dígitos de 32 bits, y
+ * para multiplicar números grandes se usa Karatsuba, que parte cada
+ * número en dos mitades y hace 3 multiplicaciones en vez de 4
+ */
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
+use std::str::FromStr;
+
+// Con menos dígitos que esto la multiplicación normal es más rápida
+const UMBRAL_KARATSUBA: usize = 32;
+
+#[derive(Clone, PartialEq, Eq, Hash, Default)]
+pub struct BigInt {
+    negativo: bool,
+    // Sin ceros al final, así cada número tiene una sola forma. El cero
+    // es la lista vacía y nunca es negativo
+    digitos: Vec<u32>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ErrorEnteroGrande {
+    Vacio,
+    DigitoInvalido { posicion: usize, caracter: char },
+}
+
+impl fmt::Display for ErrorEnteroGrande {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ErrorEnteroGrande::Vacio => write!(f, "no hay ningún dígito"),
+            ErrorEnteroGrande::DigitoInvalido { posicion, caracter } => {
+                write!(
+                    f,
+                    "'{caracter}' en la posición {posicion} no es un dígito válido"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for ErrorEnteroGrande {}
+
+impl BigInt {
+    pub fn cero() -> Self {
+        Self::default()
+    }
+
+    fn desde_partes(negativo: bool, mut digitos: Vec<u32>) -> Self {
+        recortar(&mut digitos);
+        let negativo = negativo && !digitos.is_empty();
+        Self { negativo, digitos }
+    }
+
+    pub fn es_cero(&self) -> bool {
+        self.digitos.is_empty()
+    }
+
+    pub fn es_negativo(&self) -> bool {
+        self.negativo
+    }
+
+    pub fn abs(&self) -> Self {
+        Self::desde_partes(false, self.digitos.clone())
+    }
+
+    // Elevar al cuadrado y multiplicar según los bits del exponente
+    pub fn pow(&self, mut exponente: u32) -> Self {
+        let mut resultado = BigInt::from(1u8);
+        let mut base = self.clone();
+        while exponente > 0 {
+            if exponente & 1 == 1 {
+                resultado = &resultado * &base;
+            }
+            exponente >>= 1;
+            if exponente > 0 {
+                base = &base * &base;
+            }
+        }
+        resultado
+    }
+
+    fn texto_en_base(&self, decimal: bool) -> String {
+        if self.es_cero() {
+            return String::from("0");
+        }
+        if !decimal {
+            let mut texto = format!("{:x}", self.digitos[self.digitos.len() - 1]);
+            for digito in self.digitos.iter().rev().skip(1) {
+                texto.push_str(&format!("{digito:08x}"));
+            }
+            return texto;
+        }
+
+        // Se divide entre 10^9 una y otra vez, cada residuo son 9 dígitos decimales
+        let mut pedazos = Vec::new();
+        let mut resto = self.digitos.clone();
+        while !resto.is_empty() {
+            let (cociente, residuo) = dividir_por_digito(&resto, 1_000_000_000);
+            pedazos.push(residuo);
+            resto = cociente;
+        }
+        let mut texto = pedazos.pop().unwrap_or(0).to_string();
+        for pedazo in pedazos.iter().rev() {
+            texto.push_str(&format!("{pedazo:09}"));
+        }
+        texto
+    }
+}
+
+fn recortar(digitos: &mut Vec<u32>) {
+    while digitos.last() == Some(&0) {
+        digitos.pop();
+    }
+}
+
+fn comparar_magnitud(a: &[u32], b: &[u32]) -> Ordering {
+    a.len()
+        .cmp(&b.len())
+        .then_with(|| a.iter().rev().cmp(b.iter().rev()))
+}
+
+fn sumar_magnitud(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let (largo, corto) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+    let mut resultado = Vec::with_capacity(largo.len() + 1);
+    let mut acarreo = 0u64;
+    for (i, &digito) in largo.iter().enumerate() {
+        let suma = digito as u64 + *corto.get(i).unwrap_or(&0) as u64 + acarreo;
+        resultado.push(suma as u32);
+        acarreo = suma >> 32;
+    }
+    if acarreo > 0 {
+        resultado.push(acarreo as u32);
+    }
+    resultado
+}
+
+// a - b, siempre con a >= b
+fn restar_magnitud(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut resultado = Vec::with_capacity(a.len());
+    let mut prestamo = 0i64;
+    for (i, &digito) in a.iter().enumerate() {
+        let mut resta = digito as i64 - *b.get(i).unwrap_or(&0) as i64 - prestamo;
+        prestamo = 0;
+        if resta < 0 {
+            resta += 1 << 32;
+            prestamo = 1;
+        }
+        resultado.push(resta as u32);
+    }
+    recortar(&mut resultado);
+    resultado
+}
+
+fn multiplicar_escolar(a: &[u32], b: &[u32]) -> Vec<u32> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+    let mut resultado = vec![0u32; a.len() + b.len()];
+    for (i, &x) in a.iter().enumerate() {
+        let mut acarreo = 0u64;
+        for (j, &y) in b.iter().enumerate() {
+            let producto = x as u64 * y as u64 + resultado[i + j] as u64 + acarreo;
+            resultado[i + j] = producto as u32;
+            acarreo = producto >> 32;
+        }
+        resultado[i + b.len()] = acarreo as u32;
+    }
+    recortar(&mut resultado);
+    resultado
+}
+
+/* Karatsuba: con a = a1 * B^m + a0 y b = b1 * B^m + b0,
+ *
+ *   a * b = z2 * B^2m + z1 * B^m + z0
+ *   z0 = a0 * b0,  z2 = a1 * b1,  z1 = (a0 + a1)(b0 + b1) - z0 - z2
+ *
+ * Cada mitad se multiplica de la misma forma hasta que son chicas
+ */
+fn multiplicar_magnitud(a: &[u32], b: &[u32]) -> Vec<u32> {
+    if a.len() < UMBRAL_KARATSUBA || b.len() < UMBRAL_KARATSUBA {
+        return multiplicar_escolar(a, b);
+    }
+    let m = a.len().max(b.len()) / 2;
+    let partir = |x: &[u32]| {
+        let (bajo, alto) = x.split_at(m.min(x.len()));
+        let mut bajo = bajo.to_vec();
+        recortar(&mut bajo);
+        (bajo, alto.to_vec())
+    };
+    let (a0, a1) = partir(a);
+    let (b0, b1) = partir(b);
+
+    let z0 = multiplicar_magnitud(&a0, &b0);
+    let z2 = multiplicar_magnitud(&a1, &b1);
+    let z1 = multiplicar_magnitud(&sumar_magnitud(&a0, &a1), &sumar_magnitud(&b0, &b1));
+    let z1 = restar_magnitud(&restar_magnitud(&z1, &z0), &z2);
+
+    let mut resultado = z0;
+    for (desplazamiento, parte) in [(m, z1), (2 * m, z2)] {
+        let mut recorrida = vec![0u32; desplazamiento];
+        recorrida.extend(parte);
+        resultado = sumar_magnitud(&resultado, &recorrida);
+    }
+    recortar(&mut resultado);
+    resultado
+}
+
+// Divide entre un solo dígito, devuelve (cociente, residuo)
+fn dividir_por_digito(a: &[u32], divisor: u32) -> (Vec<u32>, u32) {
+    let mut cociente = vec![0u32; a.len()];
+    let mut residuo = 0u64;
+    for i in (0..a.len()).rev() {
+        let actual = (residuo << 32) | a[i] as u64;
+        cociente[i] = (actual / divisor as u64) as u32;
+        residuo = actual % divisor as u64;
+    }
+    recortar(&mut cociente);
+    (cociente, residuo as u32)
+}
+
+fn recorrer_izquierda(a: &[u32], bits: u32) -> Vec<u32> {
+    if bits == 0 {
+        return a.to_vec();
+    }
+    let mut resultado = Vec::with_capacity(a.len() + 1);
+    let mut acarreo = 0u32;
+    for &digito in a {
+        resultado.push((digito << bits) | acarreo);
+        acarreo = digito >> (32 - bits);
+    }
+    resultado.push(acarreo);
+    resultado
+}
+
+fn recorrer_derecha(a: &[u32], bits: u32) -> Vec<u32> {
+    if bits == 0 {
+        return a.to_vec();
+    }
+    let mut resultado = vec![0u32; a.len()];
+    for i in 0..a.len() {
+        let siguiente = a.get(i + 1).map_or(0, |digito| digito << (32 - bits));
+        resultado[i] = (a[i] >> bits) | siguiente;
+    }
+    resultado
+}
+
+/* División larga (algoritmo D de Knuth). Como en la primaria se adivina
+ * cada dígito del cociente con los primeros dígitos del divisor; para
+ * que la adivinanza falle por 2 a lo mucho, primero se recorren los bits
+ * de ambos números hasta que el divisor empiece con un 1
+ */
+fn dividir_magnitud(a: &[u32], b: &[u32]) -> (Vec<u32>, Vec<u32>) {
+    if comparar_magnitud(a, b) == Ordering::Less {
+        return (Vec::new(), a.to_vec());
+    }
+    if b.len() == 1 {
+        let (cociente, residuo) = dividir_por_digito(a, b[0]);
+        let mut residuo = vec![residuo];
+        recortar(&mut residuo);
+        return (cociente, residuo);
+    }
+
+    const BASE: u64 = 1 << 32;
+    let n = b.len();
+    let m = a.len() - n;
+    let bits = b[n - 1].leading_zeros();
+    let divisor = recorrer_izquierda(b, bits);
+    let mut resto = recorrer_izquierda(a, bits);
+    if resto.len() == a.len() {
+        resto.push(0);
+    }
+    let mut cociente = vec![0u32; m + 1];
+
+    for j in (0..=m).rev() {
+        let numerador = ((resto[j + n] as u64) << 32) | resto[j + n - 1] as u64;
+        let mut q = numerador / divisor[n - 1] as u64;
+        let mut r = numerador % divisor[n - 1] as u64;
+        while q >= BASE || q * divisor[n - 2] as u64 > ((r << 32) | resto[j + n - 2] as u64) {
+            q -= 1;
+            r += divisor[n - 1] as u64;
+            if r >= BASE {
+                break;
+            }
+        }
+
+        // resto -= q * divisor, empezando en el dígito j
+        let mut prestamo = 0i64;
+        let mut acarreo = 0u64;
+        for i in 0..n {
+            let producto = q * divisor[i] as u64 + acarreo;
+            acarreo = producto >> 32;
+            let resta = resto[i + j] as i64 - prestamo - (producto & 0xFFFF_FFFF) as i64;
+            resto[i + j] = resta as u32;
+            prestamo = (resta < 0) as i64;
+        }
+        let resta = resto[j + n] as i64 - prestamo - acarreo as i64;
+        resto[j + n] = resta as u32;
+
+        // Si q era uno de más, se regresa lo que se restó de sobra
+        if resta < 0 {
+            q -= 1;
+            let mut acarreo = 0u64;
+            for i in 0..n {
+                let suma = resto[i + j] as u64 + divisor[i] as u64 + acarreo;
+                resto[i + j] = suma as u32;
+                acarreo = suma >> 32;
+            }
+            resto[j + n] = resto[j + n].wrapping_add(acarreo as u32);
+        }
+        cociente[j] = q as u32;
+    }
+
+    recortar(&mut cociente);
+    let mut residuo = recorrer_derecha(&resto[..n], bits);
+    recortar(&mut residuo);
+    (cociente, residuo)
+}
+
+impl FromStr for BigInt {
+    type Err = ErrorEnteroGrande;
+
+    // Decimal como "-12345" o hexadecimal con 0x como "0xFF"
+    fn from_str(texto: &str) -> Result<Self, Self::Err> {
+        let (negativo, sin_signo) = match texto.strip_prefix('-') {
+            Some(resto) => (true, resto),
+            None => (false, texto.strip_prefix('+').unwrap_or(texto)),
+        };
+        let (base, cifras) = match sin_signo
+            .strip_prefix("0x")
+            .or_else(|| sin_signo.strip_prefix("0X"))
+        {
+            Some(cifras) => (16, cifras),
+            None => (10, sin_signo),
+        };
+        if cifras.is_empty() {
+            return Err(ErrorEnteroGrande::Vacio);
+        }
+
+        let inicio = texto.len() - cifras.len();
+        let mut valores = Vec::with_capacity(cifras.len());
+        for (i, caracter) in cifras.chars().enumerate() {
+            match caracter.to_digit(base) {
+                Some(valor) => valores.push(valor),
+                None => {
+                    return Err(ErrorEnteroGrande::DigitoInvalido {
+                        posicion: inicio + i,
+                        caracter,
+                    })
+                }
+            }
+        }
+
+        // Se leen 8 dígitos hexadecimales o 9 decimales a la vez, que caben en un u32
+        let tamanio = if base == 16 { 8 } else { 9 };
+        let mut digitos: Vec<u32> = Vec::new();
+        for pedazo in valores.chunks(tamanio) {
+            let valor = pedazo
+                .iter()
+                .fold(0u32, |total, cifra| total * base + cifra);
+            let escala = (base as u64).pow(pedazo.len() as u32);
+            // digitos = digitos * escala + valor
+            let mut acarreo = valor as u64;
+            for digito in digitos.iter_mut() {
+                let producto = *digito as u64 * escala + acarreo;
+                *digito = producto as u32;
+                acarreo = producto >> 32;
+            }
+            if acarreo > 0 {
+                digitos.push(acarreo as u32);
+            }
+        }
+        Ok(Self::desde_partes(negativo, digitos))
+    }
+}
+
+impl fmt::Display for BigInt {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad_integral(!self.negativo, "", &self.texto_en_base(true))
+    }
+}
+
+impl fmt::Debug for BigInt {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "BigInt({self})")
+    }
+}
+
+impl fmt::LowerHex for BigInt {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad_integral(!self.negativo, "0x", &self.texto_en_base(false))
+    }
+}
+
+impl fmt::UpperHex for BigInt {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad_integral(
+            !self.negativo,
+            "0x",
+            &self.texto_en_base(false).to_uppercase(),
+        )
+    }
+}
+
+/* Conversión desde cualquier entero primitivo. Todos caben en un i128 o
+ * en un u128, así que se pasa por ahí
+ */
+fn desde_u128(negativo: bool, valor: u128) -> BigInt {
+    let digitos = (0..4).map(|i| (valor >> (32 * i)) as u32).collect();
+    BigInt::desde_partes(negativo, digitos)
+}
+
+macro_rules! desde_primitivo {
+    ($($tipo:ty),*) => {$(
+        impl From<$tipo> for BigInt {
+            #[allow(unused_comparisons)]
+            fn from(valor: $tipo) -> Self {
+                desde_u128(valor < 0, (valor as i128).unsigned_abs())
+            }
+        }
+    )*};
+}
+
+desde_primitivo!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, usize);
+
+impl From<u128> for BigInt {
+    fn from(valor: u128) -> Self {
+        desde_u128(false, valor)
+    }
+}
+
+// Sólo si el valor cabe, si no se devuelve el mismo número como error
+impl TryFrom<&BigInt> for u128 {
+    type Error = BigInt;
+
+    fn try_from(valor: &BigInt) -> Result<Self, Self::Error> {
+        if valor.negativo || valor.digitos.len() > 4 {
+            return Err(valor.clone());
+        }
+        Ok(valor
+            .digitos
+            .iter()
+            .enumerate()
+            .fold(0, |total, (i, &digito)| {
+                total | (digito as u128) << (32 * i)
+            }))
+    }
+}
+
+impl TryFrom<&BigInt> for i128 {
+    type Error = BigInt;
+
+    fn try_from(valor: &BigInt) -> Result<Self, Self::Error> {
+        let magnitud = u128::try_from(&valor.abs()).map_err(|_| valor.clone())?;
+        match (valor.negativo, i128::try_from(magnitud)) {
+            (false, Ok(positivo)) => Ok(positivo),
+            (true, Ok(positivo)) => Ok(-positivo),
+            (true, Err(_)) if magnitud == i128::MIN.unsigned_abs() => Ok(i128::MIN),
+            _ => Err(valor.clone()),
+        }
+    }
+}
+
+impl Ord for BigInt {
+    fn cmp(&self, otro: &Self) -> Ordering {
+        match (self.negativo, otro.negativo) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (false, false) => comparar_magnitud(&self.digitos, &otro.digitos),
+            (true, true) => comparar_magnitud(&otro.digitos, &self.digitos),
+        }
+    }
+}
+
+impl PartialOrd for BigInt {
+    fn partial_cmp(&self, otro: &Self) -> Option<Ordering> {
+        Some(self.cmp(otro))
+    }
+}
+
+impl Neg for &BigInt {
+    type Output = BigInt;
+
+    fn neg(self) -> BigInt {
+        BigInt::desde_partes(!self.negativo, self.digitos.clone())
+    }
+}
+
+impl Neg for BigInt {
+    type Output = BigInt;
+
+    fn neg(self) -> BigInt {
+        -&self
+    }
+}
+
+impl Add for &BigInt {
+    type Output = BigInt;
+
+    fn add(self, otro: &BigInt) -> BigInt {
+        if self.negativo == otro.negativo {
+            return BigInt::desde_partes(
+                self.negativo,
+                sumar_magnitud(&self.digitos, &otro.digitos),
+            );
+        }
+        // Con signos distintos se resta el chico del grande y gana el signo del grande
+        match comparar_magnitud(&self.digitos, &otro.digitos) {
+            Ordering::Less => {
+                BigInt::desde_partes(otro.negativo, restar_magnitud(&otro.digitos, &self.digitos))
+            }
+            _ => BigInt::desde_partes(self.negativo, restar_magnitud(&self.digitos, &otro.digitos)),
+        }
+    }
+}
+
+impl Sub for &BigInt {
+    type Output = BigInt;
+
+    fn sub(self, otro: &BigInt) -> BigInt {
+        self + &(-otro)
+    }
+}
+
+impl Mul for &BigInt {
+    type Output = BigInt;
+
+    fn mul(self, otro: &BigInt) -> BigInt {
+        BigInt::desde_partes(
+            self.negativo != otro.negativo,
+            multiplicar_magnitud(&self.digitos, &otro.digitos),
+        )
+    }
+}
+
+/* Como con los primitivos, la división trunca hacia el cero y el residuo
+ * tiene el signo del dividendo: -7 / 2 = -3 y -7 % 2 = -1
+ */
+impl Div for &BigInt {
+    type Output = BigInt;
+
+    fn div(self, otro: &BigInt) -> BigInt {
+        assert!(!otro.es_cero(), "intento de dividir un BigInt entre cero");
+        let (cociente, _) = dividir_magnitud(&self.digitos, &otro.digitos);
+        BigInt::desde_partes(self.negativo != otro.negativo, cociente)
+    }
+}
+
+impl Rem for &BigInt {
+    type Output = BigInt;
+
+    fn rem(self, otro: &BigInt) -> BigInt {
+        assert!(
+            !otro.es_cero(),
+            "intento de calcular el residuo de un BigInt entre cero"
+        );
+        let (_, residuo) = dividir_magnitud(&self.digitos, &otro.digitos);
+        BigInt::desde_partes(self.negativo, residuo)
+    }
+}
+
+// Las versiones que consumen los valores usan las de referencias
+macro_rules! operacion_por_valor {
+    ($($trait:ident $metodo:ident),*) => {$(
+        impl $trait for BigInt {
+            type Output = BigInt;
+
+            fn $metodo(self, otro: BigInt) -> BigInt {
+                (&self).$metodo(&otro)
+            }
+        }
+
+        impl $trait<&BigInt> for BigInt {
+            type Output = BigInt;
+
+            fn $metodo(self, otro: &BigInt) -> BigInt {
+                (&self).$metodo(otro)
+            }
+        }
+    )*};
+}
+
+operacion_por_valor!(Add add, Sub sub, Mul mul, Div div, Rem rem);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aleatorio::Aleatorio;
+
+    fn grande(valor: u128) -> BigInt {
+        BigInt::from(valor)
+    }
+
+    // Números de distintos tamaños, para que haya de 1 a 4 dígitos de 32 bits
+    fn valores(aleatorio: &mut Aleatorio) -> Vec<u128> {
+        let mut valores = vec![0, 1, 2, u32::MAX as u128, u64::MAX as u128, u128::MAX];
+        for bits in [8, 32, 33, 64, 65, 100, 127, 128] {
+            for _ in 0..8 {
+                let valor = (aleatorio.siguiente() as u128) << 64 | aleatorio.siguiente() as u128;
+                valores.push(valor >> (128 - bits));
+            }
+        }
+        valores
+    }
+
+    #[test]
+    fn coincide_con_u128() {
+        let mut aleatorio = Aleatorio::new(7);
+        let valores = valores(&mut aleatorio);
+        for &a in &valores {
+            for &b in &valores {
+                let (x, y) = (grande(a), grande(b));
+                assert_eq!(x.cmp(&y), a.cmp(&b), "{a} cmp {b}");
+                if let Some(suma) = a.checked_add(b) {
+                    assert_eq!(&x + &y, grande(suma), "{a} + {b}");
+                }
+                if a >= b {
+                    assert_eq!(&x - &y, grande(a - b), "{a} - {b}");
+                }
+                if let Some(producto) = a.checked_mul(b) {
+                    assert_eq!(&x * &y, grande(producto), "{a} * {b}");
+                }
+                if let (Some(cociente), Some(residuo)) = (a.checked_div(b), a.checked_rem(b)) {
+                    assert_eq!(&x / &y, grande(cociente), "{a} / {b}");
+                    assert_eq!(&x % &y, grande(residuo), "{a} % {b}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn los_signos_coinciden_con_i128() {
+        let valores: [i128; 9] = [0, 1, -1, 7, -7, 2, -2, i64::MAX as i128, i64::MIN as i128];
+        for a in valores {
+            for b in valores {
+                let (x, y) = (BigInt::from(a), BigInt::from(b));
+                assert_eq!(i128::try_from(&(&x + &y)), Ok(a + b), "{a} + {b}");
+                assert_eq!(i128::try_from(&(&x - &y)), Ok(a - b), "{a} - {b}");
+                assert_eq!(i128::try_from(&(&x * &y)), Ok(a * b), "{a} * {b}");
+                assert_eq!(x.cmp(&y), a.cmp(&b), "{a} cmp {b}");
+                if b != 0 {
+                    assert_eq!(i128::try_from(&(&x / &y)), Ok(a / b), "{a} / {b}");
+                    assert_eq!(i128::try_from(&(&x % &y)), Ok(a % b), "{a} % {b}");
+                }
+            }
+        }
+        assert_eq!(i128::try_from(&BigInt::from(i128::MIN)), Ok(i128::MIN));
+    }
+
+    #[test]
+    fn texto_decimal_y_hexadecimal() {
+        let mut aleatorio = Aleatorio::new(11);
+        for valor in valores(&mut aleatorio) {
+            let x = grande(valor);
+            assert_eq!(x.to_string(), valor.to_string());
+            assert_eq!(format!("{x:x}"), format!("{valor:x}"));
+            assert_eq!(format!("{x:#X}"), format!("{valor:#X}"));
+            assert_eq!(valor.to_string().parse::<BigInt>(), Ok(x.clone()));
+            assert_eq!(format!("0x{valor:x}").parse::<BigInt>(), Ok(x.clone()));
+        }
+        assert_eq!(format!("{:>6}", BigInt::from(-42)), "   -42");
+        assert_eq!("-0".parse::<BigInt>(), Ok(BigInt::cero()));
+        assert_eq!("".parse::<BigInt>(), Err(ErrorEnteroGrande::Vacio));
+        assert_eq!(
+            "-12a".parse::<BigInt>(),
+            Err(ErrorEnteroGrande::DigitoInvalido {
+                posicion: 3,
+                caracter: 'a'
+            })
+        );
+    }
+
+    #[test]
+    fn factorial_mas_alla_de_u128() {
+        let factorial = (1..=40u32).fold(BigInt::from(1), |total, n| total * BigInt::from(n));
+        assert_eq!(
+            factorial.to_string(),
+            "815915283247897734345611269596115894272000000000"
+        );
+        assert_eq!(
+            &factorial / &(1..=39u32).fold(BigInt::from(1), |t, n| t * BigInt::from(n)),
+            BigInt::from(40)
+        );
+    }
+
+    #[test]
+    fn karatsuba_da_lo_mismo_que_la_multiplicacion_escolar() {
+        let mut aleatorio = Aleatorio::new(3);
+        let mut numero = |largo: usize| -> Vec<u32> {
+            let mut digitos: Vec<u32> = (0..largo).map(|_| aleatorio.siguiente() as u32).collect();
+            recortar(&mut digitos);
+            digitos
+        };
+        for (largo_a, largo_b) in [(32, 32), (64, 40), (100, 33), (200, 150)] {
+            let (a, b) = (numero(largo_a), numero(largo_b));
+            assert_eq!(multiplicar_magnitud(&a, &b), multiplicar_escolar(&a, &b));
+
+            // Y la división regresa a los factores
+            let producto = BigInt::desde_partes(false, multiplicar_magnitud(&a, &b));
+            let divisor = BigInt::desde_partes(false, b.clone());
+            assert_eq!(&producto / &divisor, BigInt::desde_partes(false, a.clone()));
+            assert!((&producto % &divisor).es_cero());
+        }
+    }
+
+    #[test]
+    fn division_grande_cumple_cociente_por_divisor_mas_residuo() {
+        let mut aleatorio = Aleatorio::new(5);
+        for _ in 0..50 {
+            let largo = 2 + aleatorio.hasta(20) as usize;
+            let dividendo: Vec<u32> = (0..largo * 2)
+                .map(|_| aleatorio.siguiente() as u32)
+                .collect();
+            let divisor: Vec<u32> = (0..largo).map(|_| aleatorio.siguiente() as u32).collect();
+            let a = BigInt::desde_partes(aleatorio.hasta(2) == 0, dividendo);
+            let b = BigInt::desde_partes(aleatorio.hasta(2) == 0, divisor);
+            let (cociente, residuo) = (&a / &b, &a % &b);
+            assert_eq!(&(&cociente * &b) + &residuo, a);
+            assert!(residuo.abs() < b.abs());
+        }
+    }
+}
@@ -0,0 +1,449 @@
+/* Estadística descriptiva. En generics.rs iterar_algo<T> recibe un
+ * slice de cualquier cosa que se pueda imprimir, aquí es lo mismo pero
+ * con cualquier número: enteros (con el trait Entero de aritmetica.rs)
+ * o flotantes. El capítulo 8 del libro deja de ejercicio la media, la
+ * mediana y la moda de una lista de enteros, aquí están esas y algunas
+ * más: varianza, desviación estándar, percentiles, mínimo, máximo e
+ * histogramas
+ *
+ * Los flotantes tienen NaN, que no es igual a nada (ni a sí mismo) y que
+ * convierte en NaN cualquier suma donde aparezca. En vez de dejar que se
+ * cuele sin avisar hay que decir qué hacer con él: que sea un error o
+ * que se ignore
+ *
+ * Muestra guarda todos los datos ordenados, para la mediana hace falta.
+ * Para datos que no caben en memoria está Acumulador, que los recibe de
+ * uno en uno y solo guarda lo necesario para la media y la varianza
+ */
+
+use std::cmp::Ordering;
+use std::fmt;
+
+use super::aritmetica::Entero;
+
+pub trait Dato: Copy + PartialOrd + fmt::Display {
+    fn a_f64(self) -> f64;
+
+    fn es_nan(self) -> bool {
+        false
+    }
+}
+
+impl<T: Entero> Dato for T {
+    fn a_f64(self) -> f64 {
+        Entero::a_f64(self)
+    }
+}
+
+impl Dato for f64 {
+    fn a_f64(self) -> f64 {
+        self
+    }
+
+    fn es_nan(self) -> bool {
+        self.is_nan()
+    }
+}
+
+impl Dato for f32 {
+    fn a_f64(self) -> f64 {
+        self as f64
+    }
+
+    fn es_nan(self) -> bool {
+        self.is_nan()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PoliticaNan {
+    // Un NaN en los datos es un error, lo más seguro
+    Error,
+    // Se descartan los NaN y se calcula con lo demás
+    Ignorar,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ErrorEstadistica {
+    SinDatos,
+    // La posición (desde 0) del primer NaN
+    HayNan(usize),
+    PercentilInvalido(f64),
+    SinCajas,
+}
+
+impl fmt::Display for ErrorEstadistica {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ErrorEstadistica::SinDatos => write!(f, "no hay datos"),
+            ErrorEstadistica::HayNan(posicion) => {
+                write!(f, "el dato {} es NaN", posicion + 1)
+            }
+            ErrorEstadistica::PercentilInvalido(percentil) => {
+                write!(f, "el percentil debe estar entre 0 y 100, no {percentil}")
+            }
+            ErrorEstadistica::SinCajas => write!(f, "el histograma necesita al menos una caja"),
+        }
+    }
+}
+
+impl std::error::Error for ErrorEstadistica {}
+
+fn comparar<T: Dato>(a: &T, b: &T) -> Ordering {
+    // Ya no hay NaN, así que siempre se pueden comparar
+    a.partial_cmp(b).unwrap_or(Ordering::Equal)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Muestra<T> {
+    ordenados: Vec<T>,
+}
+
+impl<T: Dato> Muestra<T> {
+    pub fn new(datos: &[T], politica: PoliticaNan) -> Result<Self, ErrorEstadistica> {
+        let mut ordenados = Vec::with_capacity(datos.len());
+        for (i, &dato) in datos.iter().enumerate() {
+            match (dato.es_nan(), politica) {
+                (false, _) => ordenados.push(dato),
+                (true, PoliticaNan::Ignorar) => {}
+                (true, PoliticaNan::Error) => return Err(ErrorEstadistica::HayNan(i)),
+            }
+        }
+        if ordenados.is_empty() {
+            return Err(ErrorEstadistica::SinDatos);
+        }
+        ordenados.sort_by(comparar);
+        Ok(Self { ordenados })
+    }
+
+    // Sin is_empty: new no deja crear una Muestra vacía
+    #[allow(clippy::len_without_is_empty)]
+    pub fn len(&self) -> usize {
+        self.ordenados.len()
+    }
+
+    pub fn minimo(&self) -> T {
+        self.ordenados[0]
+    }
+
+    pub fn maximo(&self) -> T {
+        self.ordenados[self.len() - 1]
+    }
+
+    pub fn suma(&self) -> f64 {
+        self.ordenados.iter().map(|dato| dato.a_f64()).sum()
+    }
+
+    pub fn media(&self) -> f64 {
+        self.suma() / self.len() as f64
+    }
+
+    // Con un número par de datos es el promedio de los dos de en medio
+    pub fn mediana(&self) -> f64 {
+        let mitad = self.len() / 2;
+        if self.len() % 2 == 1 {
+            self.ordenados[mitad].a_f64()
+        } else {
+            (self.ordenados[mitad - 1].a_f64() + self.ordenados[mitad].a_f64()) / 2.0
+        }
+    }
+
+    /* Los valores que más se repiten, puede haber empate. Como están
+     * ordenados los iguales quedan juntos y basta contar las rachas
+     */
+    pub fn modas(&self) -> Vec<T> {
+        let mut modas = Vec::new();
+        let mut mayor = 0;
+        for racha in self.ordenados.chunk_by(|a, b| a == b) {
+            match racha.len().cmp(&mayor) {
+                Ordering::Greater => {
+                    mayor = racha.len();
+                    modas = vec![racha[0]];
+                }
+                Ordering::Equal => modas.push(racha[0]),
+                Ordering::Less => {}
+            }
+        }
+        modas
+    }
+
+    fn suma_de_cuadrados(&self) -> f64 {
+        let media = self.media();
+        self.ordenados
+            .iter()
+            .map(|dato| (dato.a_f64() - media).powi(2))
+            .sum()
+    }
+
+    // Cuando los datos son todos los que hay (dividir entre n)
+    pub fn varianza_poblacional(&self) -> f64 {
+        self.suma_de_cuadrados() / self.len() as f64
+    }
+
+    // Cuando los datos son una parte de algo más grande (dividir entre n - 1)
+    pub fn varianza_muestral(&self) -> Option<f64> {
+        (self.len() > 1).then(|| self.suma_de_cuadrados() / (self.len() - 1) as f64)
+    }
+
+    pub fn desviacion_poblacional(&self) -> f64 {
+        self.varianza_poblacional().sqrt()
+    }
+
+    pub fn desviacion_muestral(&self) -> Option<f64> {
+        self.varianza_muestral().map(f64::sqrt)
+    }
+
+    /* El valor debajo del cual queda el p% de los datos, interpolando
+     * entre los dos más cercanos (como PERCENTIL.INC de las hojas de
+     * cálculo). El percentil 50 es la mediana
+     */
+    pub fn percentil(&self, p: f64) -> Result<f64, ErrorEstadistica> {
+        if !(0.0..=100.0).contains(&p) {
+            return Err(ErrorEstadistica::PercentilInvalido(p));
+        }
+        let posicion = (self.len() - 1) as f64 * p / 100.0;
+        let abajo = posicion.floor() as usize;
+        let arriba = posicion.ceil() as usize;
+        let a = self.ordenados[abajo].a_f64();
+        let b = self.ordenados[arriba].a_f64();
+        Ok(a + (b - a) * (posicion - abajo as f64))
+    }
+
+    // Cajas del mismo ancho entre el mínimo y el máximo
+    pub fn histograma(&self, cajas: usize) -> Result<Histograma, ErrorEstadistica> {
+        if cajas == 0 {
+            return Err(ErrorEstadistica::SinCajas);
+        }
+        let minimo = self.minimo().a_f64();
+        let maximo = self.maximo().a_f64();
+        // Si todos son iguales se usa una caja de ancho 1 para no dividir entre 0
+        let ancho = if maximo > minimo {
+            (maximo - minimo) / cajas as f64
+        } else {
+            1.0
+        };
+        let mut conteos = vec![0; cajas];
+        for dato in &self.ordenados {
+            let caja = ((dato.a_f64() - minimo) / ancho) as usize;
+            // El máximo cae justo en el borde derecho, va en la última caja
+            conteos[caja.min(cajas - 1)] += 1;
+        }
+        Ok(Histograma {
+            minimo,
+            ancho,
+            conteos,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Histograma {
+    pub minimo: f64,
+    pub ancho: f64,
+    pub conteos: Vec<usize>,
+}
+
+impl Histograma {
+    // Los límites [desde, hasta) de cada caja
+    pub fn limites(&self, caja: usize) -> (f64, f64) {
+        let desde = self.minimo + self.ancho * caja as f64;
+        (desde, desde + self.ancho)
+    }
+}
+
+// Una barra de # por caja, la más alta mide 40
+impl fmt::Display for Histograma {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mayor = self.conteos.iter().copied().max().unwrap_or(0).max(1);
+        for (caja, &conteo) in self.conteos.iter().enumerate() {
+            let (desde, hasta) = self.limites(caja);
+            let barra = "#".repeat(conteo * 40 / mayor);
+            writeln!(f, "[{desde:>10.2}, {hasta:>10.2}) {conteo:>6} {barra}")?;
+        }
+        Ok(())
+    }
+}
+
+/* Recibe los datos de uno en uno sin guardarlos. La media y la varianza
+ * se actualizan con el algoritmo de Welford, que no acumula la suma de
+ * cuadrados (con muchos datos esa suma pierde precisión)
+ */
+#[derive(Debug, Clone)]
+pub struct Acumulador {
+    politica: PoliticaNan,
+    cantidad: usize,
+    nan: usize,
+    media: f64,
+    // Suma de (x - media)^2, de aquí sale la varianza
+    m2: f64,
+    minimo: f64,
+    maximo: f64,
+}
+
+impl Acumulador {
+    pub fn new(politica: PoliticaNan) -> Self {
+        Self {
+            politica,
+            cantidad: 0,
+            nan: 0,
+            media: 0.0,
+            m2: 0.0,
+            minimo: f64::INFINITY,
+            maximo: f64::NEG_INFINITY,
+        }
+    }
+
+    pub fn agregar<T: Dato>(&mut self, dato: T) -> Result<(), ErrorEstadistica> {
+        if dato.es_nan() {
+            self.nan += 1;
+            return match self.politica {
+                PoliticaNan::Ignorar => Ok(()),
+                PoliticaNan::Error => Err(ErrorEstadistica::HayNan(self.cantidad + self.nan - 1)),
+            };
+        }
+        let x = dato.a_f64();
+        self.cantidad += 1;
+        let delta = x - self.media;
+        self.media += delta / self.cantidad as f64;
+        self.m2 += delta * (x - self.media);
+        self.minimo = self.minimo.min(x);
+        self.maximo = self.maximo.max(x);
+        Ok(())
+    }
+
+    pub fn cantidad(&self) -> usize {
+        self.cantidad
+    }
+
+    // Cuántos NaN se ignoraron
+    pub fn nan(&self) -> usize {
+        self.nan
+    }
+
+    pub fn media(&self) -> Option<f64> {
+        (self.cantidad > 0).then_some(self.media)
+    }
+
+    pub fn varianza_poblacional(&self) -> Option<f64> {
+        (self.cantidad > 0).then(|| self.m2 / self.cantidad as f64)
+    }
+
+    pub fn varianza_muestral(&self) -> Option<f64> {
+        (self.cantidad > 1).then(|| self.m2 / (self.cantidad - 1) as f64)
+    }
+
+    pub fn desviacion_muestral(&self) -> Option<f64> {
+        self.varianza_muestral().map(f64::sqrt)
+    }
+
+    pub fn minimo(&self) -> Option<f64> {
+        (self.cantidad > 0).then_some(self.minimo)
+    }
+
+    pub fn maximo(&self) -> Option<f64> {
+        (self.cantidad > 0).then_some(self.maximo)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cerca(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-9
+    }
+
+    #[test]
+    fn media_mediana_y_moda_del_libro() {
+        let muestra = Muestra::new(&[3, 1, 4, 1, 5, 9, 2, 6, 5, 3, 5], PoliticaNan::Error).unwrap();
+        assert!(cerca(muestra.media(), 44.0 / 11.0));
+        assert_eq!(muestra.mediana(), 4.0);
+        assert_eq!(muestra.modas(), [5]);
+        assert_eq!((muestra.minimo(), muestra.maximo()), (1, 9));
+
+        let par = Muestra::new(&[1u8, 2, 2, 3, 3, 10], PoliticaNan::Error).unwrap();
+        assert_eq!(par.mediana(), 2.5);
+        assert_eq!(par.modas(), [2, 3]);
+        assert_eq!(
+            Muestra::<i32>::new(&[], PoliticaNan::Error),
+            Err(ErrorEstadistica::SinDatos)
+        );
+    }
+
+    #[test]
+    fn varianza_y_percentiles() {
+        let muestra = Muestra::new(
+            &[2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0],
+            PoliticaNan::Error,
+        )
+        .unwrap();
+        assert_eq!(muestra.varianza_poblacional(), 4.0);
+        assert_eq!(muestra.desviacion_poblacional(), 2.0);
+        assert!(cerca(muestra.varianza_muestral().unwrap(), 32.0 / 7.0));
+        assert_eq!(muestra.percentil(0.0), Ok(2.0));
+        assert_eq!(muestra.percentil(50.0), Ok(muestra.mediana()));
+        assert_eq!(muestra.percentil(100.0), Ok(9.0));
+        // Posición 7 * 0.25 = 1.75, entre 4 y 4
+        assert_eq!(muestra.percentil(25.0), Ok(4.0));
+        assert!(cerca(muestra.percentil(90.0).unwrap(), 7.0 + 2.0 * 0.3));
+        assert!(muestra.percentil(101.0).is_err());
+
+        let uno = Muestra::new(&[7i64], PoliticaNan::Error).unwrap();
+        assert_eq!(uno.varianza_muestral(), None);
+        assert_eq!(uno.percentil(30.0), Ok(7.0));
+    }
+
+    #[test]
+    fn nan_explicito() {
+        let datos = [1.0, f64::NAN, 3.0];
+        assert_eq!(
+            Muestra::new(&datos, PoliticaNan::Error),
+            Err(ErrorEstadistica::HayNan(1))
+        );
+        let muestra = Muestra::new(&datos, PoliticaNan::Ignorar).unwrap();
+        assert_eq!(muestra.media(), 2.0);
+        assert_eq!(
+            Muestra::new(&[f32::NAN], PoliticaNan::Ignorar),
+            Err(ErrorEstadistica::SinDatos)
+        );
+
+        let mut acumulador = Acumulador::new(PoliticaNan::Error);
+        acumulador.agregar(1.0).unwrap();
+        assert_eq!(
+            acumulador.agregar(f64::NAN),
+            Err(ErrorEstadistica::HayNan(1))
+        );
+    }
+
+    #[test]
+    fn histograma() {
+        let muestra =
+            Muestra::new(&[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10], PoliticaNan::Error).unwrap();
+        let histograma = muestra.histograma(5).unwrap();
+        assert_eq!(histograma.conteos, [2, 2, 2, 2, 3]);
+        assert_eq!(histograma.limites(1), (2.0, 4.0));
+        let iguales = Muestra::new(&[3u16; 4], PoliticaNan::Error).unwrap();
+        assert_eq!(iguales.histograma(3).unwrap().conteos, [4, 0, 0]);
+        assert_eq!(muestra.histograma(0), Err(ErrorEstadistica::SinCajas));
+    }
+
+    #[test]
+    fn el_acumulador_coincide_con_la_muestra() {
+        let mut aleatorio = crate::aleatorio::Aleatorio::new(20);
+        let datos: Vec<f64> = (0..1000).map(|_| aleatorio.normal(50.0, 12.0)).collect();
+        let muestra = Muestra::new(&datos, PoliticaNan::Error).unwrap();
+        let mut acumulador = Acumulador::new(PoliticaNan::Error);
+        for &dato in &datos {
+            acumulador.agregar(dato).unwrap();
+        }
+        assert_eq!(acumulador.cantidad(), 1000);
+        assert!(cerca(acumulador.media().unwrap(), muestra.media()));
+        assert!(cerca(
+            acumulador.varianza_muestral().unwrap(),
+            muestra.varianza_muestral().unwrap()
+        ));
+        assert_eq!(acumulador.minimo(), Some(muestra.minimo()));
+        assert_eq!(acumulador.maximo(), Some(muestra.maximo()));
+        assert_eq!(Acumulador::new(PoliticaNan::Ignorar).media(), None);
+    }
+}
@@ -0,0 +1,716 @@
+/* Matrices densas. Un Vec<Vec<T>> guarda cada fila en su propio lugar
+ * del heap, así que recorrerlo salta de un lado a otro de la memoria y
+ * nada impide que una fila quede más corta que las demás. Matriz<T>
+ * guarda todo en un solo Vec, fila tras fila (row-major): el elemento
+ * (fila, col) está en datos[fila * columnas + col]
+ *
+ * Sumar y multiplicar funciona con cualquier T de Numero (enteros,
+ * flotantes y Complejo). LU, determinante, inversa y resolver sistemas
+ * dividen y buscan pivotes por su tamaño, así que piden T: Real (f32 y
+ * f64, el mismo trait de complejo.rs)
+ */
+
+use std::fmt;
+use std::ops::{Add, Index, IndexMut, Mul, Sub};
+
+use super::complejo::{Complejo, Real};
+
+pub trait Numero:
+    Copy
+    + PartialEq
+    + fmt::Debug
+    + fmt::Display
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+{
+    const CERO: Self;
+    const UNO: Self;
+}
+
+macro_rules! impl_numero {
+    ($cero:literal, $uno:literal; $($tipo:ident),*) => {$(
+        impl Numero for $tipo {
+            const CERO: Self = $cero;
+            const UNO: Self = $uno;
+        }
+    )*};
+}
+
+impl_numero!(0, 1; i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+impl_numero!(0.0, 1.0; f32, f64);
+
+impl<T: Real + fmt::Debug> Numero for Complejo<T> {
+    const CERO: Self = Complejo::new(T::CERO, T::CERO);
+    const UNO: Self = Complejo::new(T::UNO, T::CERO);
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ErrorMatriz {
+    FilasIrregulares {
+        fila: usize,
+        esperadas: usize,
+        encontradas: usize,
+    },
+    TamanioIncorrecto {
+        esperados: usize,
+        recibidos: usize,
+    },
+    DimensionesIncompatibles {
+        izquierda: (usize, usize),
+        derecha: (usize, usize),
+    },
+    NoCuadrada(usize, usize),
+    Singular,
+}
+
+impl fmt::Display for ErrorMatriz {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ErrorMatriz::FilasIrregulares {
+                fila,
+                esperadas,
+                encontradas,
+            } => write!(
+                f,
+                "la fila {fila} tiene {encontradas} columnas y las demás {esperadas}"
+            ),
+            ErrorMatriz::TamanioIncorrecto {
+                esperados,
+                recibidos,
+            } => write!(f, "se esperaban {esperados} datos y llegaron {recibidos}"),
+            ErrorMatriz::DimensionesIncompatibles { izquierda, derecha } => write!(
+                f,
+                "no se puede operar una matriz de {}x{} con una de {}x{}",
+                izquierda.0, izquierda.1, derecha.0, derecha.1
+            ),
+            ErrorMatriz::NoCuadrada(filas, columnas) => {
+                write!(f, "la matriz es de {filas}x{columnas} y debe ser cuadrada")
+            }
+            ErrorMatriz::Singular => write!(f, "la matriz es singular, no tiene inversa"),
+        }
+    }
+}
+
+impl std::error::Error for ErrorMatriz {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Matriz<T> {
+    filas: usize,
+    columnas: usize,
+    datos: Vec<T>,
+}
+
+impl<T> Matriz<T> {
+    pub fn desde_vec(filas: usize, columnas: usize, datos: Vec<T>) -> Result<Self, ErrorMatriz> {
+        if datos.len() != filas * columnas {
+            return Err(ErrorMatriz::TamanioIncorrecto {
+                esperados: filas * columnas,
+                recibidos: datos.len(),
+            });
+        }
+        Ok(Self {
+            filas,
+            columnas,
+            datos,
+        })
+    }
+
+    // Pasar de Vec<Vec<T>> a Matriz, revisando que las filas sean iguales
+    pub fn desde_filas(filas: Vec<Vec<T>>) -> Result<Self, ErrorMatriz> {
+        let columnas = filas.first().map_or(0, Vec::len);
+        let cantidad = filas.len();
+        let mut datos = Vec::with_capacity(cantidad * columnas);
+        for (i, fila) in filas.into_iter().enumerate() {
+            if fila.len() != columnas {
+                return Err(ErrorMatriz::FilasIrregulares {
+                    fila: i,
+                    esperadas: columnas,
+                    encontradas: fila.len(),
+                });
+            }
+            datos.extend(fila);
+        }
+        Self::desde_vec(cantidad, columnas, datos)
+    }
+
+    pub fn desde_funcion(
+        filas: usize,
+        columnas: usize,
+        mut valor: impl FnMut(usize, usize) -> T,
+    ) -> Self {
+        let datos = (0..filas * columnas)
+            .map(|k| valor(k / columnas, k % columnas))
+            .collect();
+        Self {
+            filas,
+            columnas,
+            datos,
+        }
+    }
+
+    pub fn filas(&self) -> usize {
+        self.filas
+    }
+
+    pub fn columnas(&self) -> usize {
+        self.columnas
+    }
+
+    pub fn dimensiones(&self) -> (usize, usize) {
+        (self.filas, self.columnas)
+    }
+
+    pub fn es_cuadrada(&self) -> bool {
+        self.filas == self.columnas
+    }
+
+    // Todos los datos en orden, fila tras fila
+    pub fn datos(&self) -> &[T] {
+        &self.datos
+    }
+
+    pub fn get(&self, fila: usize, columna: usize) -> Option<&T> {
+        if fila < self.filas && columna < self.columnas {
+            self.datos.get(fila * self.columnas + columna)
+        } else {
+            None
+        }
+    }
+
+    // Una fila es un pedazo seguido del Vec, sale gratis
+    pub fn fila(&self, fila: usize) -> &[T] {
+        &self.datos[fila * self.columnas..(fila + 1) * self.columnas]
+    }
+
+    pub fn fila_mut(&mut self, fila: usize) -> &mut [T] {
+        &mut self.datos[fila * self.columnas..(fila + 1) * self.columnas]
+    }
+
+    // Una columna no está seguida: es un dato de cada fila
+    pub fn columna(&self, columna: usize) -> impl Iterator<Item = &T> {
+        assert!(columna < self.columnas, "no existe la columna {columna}");
+        self.datos.iter().skip(columna).step_by(self.columnas)
+    }
+
+    pub fn iter_filas(&self) -> impl Iterator<Item = &[T]> {
+        // chunks_exact no acepta 0, una matriz sin columnas no tiene filas que recorrer
+        self.datos.chunks_exact(self.columnas.max(1))
+    }
+
+    pub fn iter_columnas(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.columnas).map(move |j| self.columna(j))
+    }
+}
+
+impl<T: Copy> Matriz<T> {
+    pub fn new(filas: usize, columnas: usize, valor: T) -> Self {
+        Self {
+            filas,
+            columnas,
+            datos: vec![valor; filas * columnas],
+        }
+    }
+
+    pub fn traspuesta(&self) -> Self {
+        Self::desde_funcion(self.columnas, self.filas, |i, j| self[(j, i)])
+    }
+}
+
+// Tamaño de los bloques de la multiplicación, 64 x 64 f64 son 32 KiB
+const BLOQUE: usize = 64;
+
+impl<T: Numero> Matriz<T> {
+    pub fn ceros(filas: usize, columnas: usize) -> Self {
+        Self::new(filas, columnas, T::CERO)
+    }
+
+    pub fn identidad(n: usize) -> Self {
+        Self::desde_funcion(n, n, |i, j| if i == j { T::UNO } else { T::CERO })
+    }
+
+    pub fn escalar(&self, factor: T) -> Self {
+        Self {
+            filas: self.filas,
+            columnas: self.columnas,
+            datos: self.datos.iter().map(|&x| x * factor).collect(),
+        }
+    }
+
+    pub fn sumar(&self, otra: &Self) -> Result<Self, ErrorMatriz> {
+        self.combinar(otra, |a, b| a + b)
+    }
+
+    pub fn restar(&self, otra: &Self) -> Result<Self, ErrorMatriz> {
+        self.combinar(otra, |a, b| a - b)
+    }
+
+    fn combinar(&self, otra: &Self, operacion: impl Fn(T, T) -> T) -> Result<Self, ErrorMatriz> {
+        if self.dimensiones() != otra.dimensiones() {
+            return Err(self.incompatible(otra));
+        }
+        let datos = self
+            .datos
+            .iter()
+            .zip(&otra.datos)
+            .map(|(&a, &b)| operacion(a, b))
+            .collect();
+        Ok(Self {
+            filas: self.filas,
+            columnas: self.columnas,
+            datos,
+        })
+    }
+
+    fn incompatible(&self, otra: &Self) -> ErrorMatriz {
+        ErrorMatriz::DimensionesIncompatibles {
+            izquierda: self.dimensiones(),
+            derecha: otra.dimensiones(),
+        }
+    }
+
+    // Con matrices chicas los bloques sólo estorban
+    pub fn multiplicar(&self, otra: &Self) -> Result<Self, ErrorMatriz> {
+        if self.filas.max(self.columnas).max(otra.columnas) <= BLOQUE {
+            self.multiplicar_directo(otra)
+        } else {
+            self.multiplicar_por_bloques(otra)
+        }
+    }
+
+    /* El orden i, k, j en vez del i, j, k de la definición: así el ciclo
+     * de adentro recorre una fila de otra y una fila del resultado, las
+     * dos seguidas en memoria
+     */
+    pub fn multiplicar_directo(&self, otra: &Self) -> Result<Self, ErrorMatriz> {
+        if self.columnas != otra.filas {
+            return Err(self.incompatible(otra));
+        }
+        let mut resultado = Self::ceros(self.filas, otra.columnas);
+        for i in 0..self.filas {
+            for k in 0..self.columnas {
+                let a = self[(i, k)];
+                let destino = resultado.fila_mut(i);
+                for (c, &b) in destino.iter_mut().zip(otra.fila(k)) {
+                    *c = *c + a * b;
+                }
+            }
+        }
+        Ok(resultado)
+    }
+
+    /* Lo mismo pero por cuadros de BLOQUE x BLOQUE. Con matrices grandes
+     * una fila completa de otra ya no cabe en la caché junto con lo demás,
+     * así que se trabaja un cuadro a la vez mientras todavía está cerca
+     */
+    pub fn multiplicar_por_bloques(&self, otra: &Self) -> Result<Self, ErrorMatriz> {
+        if self.columnas != otra.filas {
+            return Err(self.incompatible(otra));
+        }
+        let (n, m, p) = (self.filas, self.columnas, otra.columnas);
+        let mut resultado = Self::ceros(n, p);
+        for ii in (0..n).step_by(BLOQUE) {
+            for kk in (0..m).step_by(BLOQUE) {
+                for jj in (0..p).step_by(BLOQUE) {
+                    let columnas = jj..(jj + BLOQUE).min(p);
+                    for i in ii..(ii + BLOQUE).min(n) {
+                        for k in kk..(kk + BLOQUE).min(m) {
+                            let a = self[(i, k)];
+                            let destino = &mut resultado.fila_mut(i)[columnas.clone()];
+                            for (c, &b) in destino.iter_mut().zip(&otra.fila(k)[columnas.clone()]) {
+                                *c = *c + a * b;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        Ok(resultado)
+    }
+}
+
+/* LU con pivoteo parcial: P A = L U, con L triangular inferior con unos
+ * en la diagonal y U triangular superior, guardadas juntas en una sola
+ * matriz. Con eso el determinante es el producto de la diagonal de U
+ * (con signo por cada intercambio de filas) y resolver A x = b son dos
+ * sustituciones, una hacia abajo y otra hacia arriba
+ */
+#[derive(Debug, Clone)]
+pub struct Lu<T> {
+    lu: Matriz<T>,
+    // permutacion[i] es la fila de A que quedó en la fila i
+    permutacion: Vec<usize>,
+    intercambios: usize,
+}
+
+impl<T: Real> Lu<T> {
+    pub fn determinante(&self) -> T {
+        let producto = (0..self.lu.filas).fold(T::UNO, |total, i| total * self.lu[(i, i)]);
+        if self.intercambios % 2 == 1 {
+            -producto
+        } else {
+            producto
+        }
+    }
+
+    pub fn resolver(&self, b: &[T]) -> Result<Vec<T>, ErrorMatriz> {
+        let n = self.lu.filas;
+        if b.len() != n {
+            return Err(ErrorMatriz::TamanioIncorrecto {
+                esperados: n,
+                recibidos: b.len(),
+            });
+        }
+        // L y = P b
+        let mut x: Vec<T> = self.permutacion.iter().map(|&i| b[i]).collect();
+        for i in 0..n {
+            for j in 0..i {
+                x[i] = x[i] - self.lu[(i, j)] * x[j];
+            }
+        }
+        // U x = y
+        for i in (0..n).rev() {
+            for j in i + 1..n {
+                x[i] = x[i] - self.lu[(i, j)] * x[j];
+            }
+            x[i] = x[i] / self.lu[(i, i)];
+        }
+        Ok(x)
+    }
+
+    pub fn inversa(&self) -> Matriz<T> {
+        let n = self.lu.filas;
+        let mut inversa = Matriz::new(n, n, T::CERO);
+        for j in 0..n {
+            let unitario: Vec<T> = (0..n)
+                .map(|i| if i == j { T::UNO } else { T::CERO })
+                .collect();
+            let columna = self
+                .resolver(&unitario)
+                .expect("el vector tiene el tamaño de la matriz");
+            for (i, valor) in columna.into_iter().enumerate() {
+                inversa[(i, j)] = valor;
+            }
+        }
+        inversa
+    }
+}
+
+impl<T: Real> Matriz<T> {
+    /* Un pivote cuenta como cero si es más chico que el error de redondeo
+     * que se puede acumular, relativo al dato más grande de su propia fila.
+     * Con el dato más grande de toda la matriz, diag(1e-20, 1) salía
+     * singular aunque su inversa es diag(1e20, 1)
+     */
+    pub fn lu(&self) -> Result<Lu<T>, ErrorMatriz> {
+        if !self.es_cuadrada() {
+            return Err(ErrorMatriz::NoCuadrada(self.filas, self.columnas));
+        }
+        let n = self.filas;
+        let redondeo = T::EPSILON * T::desde_f64(n as f64);
+        // Se intercambian junto con las filas
+        let mut escalas: Vec<T> = self
+            .iter_filas()
+            .map(|fila| {
+                fila.iter()
+                    .map(|x| x.abs())
+                    .fold(T::CERO, |a, b| if b > a { b } else { a })
+            })
+            .collect();
+
+        let mut lu = self.clone();
+        let mut permutacion: Vec<usize> = (0..n).collect();
+        let mut intercambios = 0;
+        for k in 0..n {
+            let mut pivote = k;
+            for i in k + 1..n {
+                if lu[(i, k)].abs() > lu[(pivote, k)].abs() {
+                    pivote = i;
+                }
+            }
+            if lu[(pivote, k)].abs() <= escalas[pivote] * redondeo {
+                return Err(ErrorMatriz::Singular);
+            }
+            if pivote != k {
+                for j in 0..n {
+                    lu.datos.swap(k * n + j, pivote * n + j);
+                }
+                permutacion.swap(k, pivote);
+                escalas.swap(k, pivote);
+                intercambios += 1;
+            }
+            for i in k + 1..n {
+                let factor = lu[(i, k)] / lu[(k, k)];
+                lu[(i, k)] = factor;
+                for j in k + 1..n {
+                    lu[(i, j)] = lu[(i, j)] - factor * lu[(k, j)];
+                }
+            }
+        }
+        Ok(Lu {
+            lu,
+            permutacion,
+            intercambios,
+        })
+    }
+
+    // Una matriz singular no es un error aquí, su determinante es 0
+    pub fn determinante(&self) -> Result<T, ErrorMatriz> {
+        match self.lu() {
+            Ok(lu) => Ok(lu.determinante()),
+            Err(ErrorMatriz::Singular) => Ok(T::CERO),
+            Err(error) => Err(error),
+        }
+    }
+
+    pub fn inversa(&self) -> Result<Self, ErrorMatriz> {
+        Ok(self.lu()?.inversa())
+    }
+
+    // Resuelve A x = b
+    pub fn resolver(&self, b: &[T]) -> Result<Vec<T>, ErrorMatriz> {
+        self.lu()?.resolver(b)
+    }
+}
+
+impl<T> Index<(usize, usize)> for Matriz<T> {
+    type Output = T;
+
+    fn index(&self, (fila, columna): (usize, usize)) -> &T {
+        assert!(
+            fila < self.filas && columna < self.columnas,
+            "({fila}, {columna}) está fuera de una matriz de {}x{}",
+            self.filas,
+            self.columnas
+        );
+        &self.datos[fila * self.columnas + columna]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Matriz<T> {
+    fn index_mut(&mut self, (fila, columna): (usize, usize)) -> &mut T {
+        assert!(
+            fila < self.filas && columna < self.columnas,
+            "({fila}, {columna}) está fuera de una matriz de {}x{}",
+            self.filas,
+            self.columnas
+        );
+        &mut self.datos[fila * self.columnas + columna]
+    }
+}
+
+// Los operadores hacen panic si las dimensiones no cuadran
+impl<T: Numero> Add for &Matriz<T> {
+    type Output = Matriz<T>;
+
+    fn add(self, otra: Self) -> Matriz<T> {
+        self.sumar(otra).unwrap_or_else(|error| panic!("{error}"))
+    }
+}
+
+impl<T: Numero> Sub for &Matriz<T> {
+    type Output = Matriz<T>;
+
+    fn sub(self, otra: Self) -> Matriz<T> {
+        self.restar(otra).unwrap_or_else(|error| panic!("{error}"))
+    }
+}
+
+impl<T: Numero> Mul for &Matriz<T> {
+    type Output = Matriz<T>;
+
+    fn mul(self, otra: Self) -> Matriz<T> {
+        self.multiplicar(otra)
+            .unwrap_or_else(|error| panic!("{error}"))
+    }
+}
+
+impl<T: Numero> Mul<T> for &Matriz<T> {
+    type Output = Matriz<T>;
+
+    fn mul(self, factor: T) -> Matriz<T> {
+        self.escalar(factor)
+    }
+}
+
+// Una fila por línea y las columnas alineadas a la derecha, con {:.2} también
+impl<T: fmt::Display> fmt::Display for Matriz<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let textos: Vec<String> = self
+            .datos
+            .iter()
+            .map(|x| match f.precision() {
+                Some(decimales) => format!("{x:.decimales$}"),
+                None => x.to_string(),
+            })
+            .collect();
+        let ancho = textos.iter().map(|t| t.chars().count()).max().unwrap_or(0);
+        for fila in textos.chunks(self.columnas.max(1)) {
+            let fila: Vec<String> = fila.iter().map(|t| format!("{t:>ancho$}")).collect();
+            writeln!(f, "[{}]", fila.join("  "))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aleatorio::Aleatorio;
+
+    fn aleatoria(filas: usize, columnas: usize, aleatorio: &mut Aleatorio) -> Matriz<f64> {
+        Matriz::desde_funcion(filas, columnas, |_, _| aleatorio.normal(0.0, 1.0))
+    }
+
+    fn casi_iguales(a: &Matriz<f64>, b: &Matriz<f64>, tolerancia: f64) -> bool {
+        a.dimensiones() == b.dimensiones()
+            && a.datos()
+                .iter()
+                .zip(b.datos())
+                .all(|(x, y)| (x - y).abs() <= tolerancia)
+    }
+
+    #[test]
+    fn acceso_filas_y_columnas() {
+        let mut m = Matriz::desde_filas(vec![vec![1, 2, 3], vec![4, 5, 6]]).unwrap();
+        assert_eq!(m.dimensiones(), (2, 3));
+        assert_eq!(m[(1, 0)], 4);
+        assert_eq!(m.get(2, 0), None);
+        m[(0, 2)] = 30;
+        assert_eq!(m.fila(0), &[1, 2, 30]);
+        assert_eq!(m.columna(1).copied().collect::<Vec<_>>(), vec![2, 5]);
+        assert_eq!(m.iter_filas().count(), 2);
+        let columnas: Vec<Vec<i32>> = m.iter_columnas().map(|c| c.copied().collect()).collect();
+        assert_eq!(columnas, vec![vec![1, 4], vec![2, 5], vec![30, 6]]);
+        assert_eq!(
+            m.traspuesta(),
+            Matriz::desde_filas(vec![vec![1, 4], vec![2, 5], vec![30, 6]]).unwrap()
+        );
+        assert_eq!(m.to_string(), "[ 1   2  30]\n[ 4   5   6]\n");
+
+        assert_eq!(
+            Matriz::desde_filas(vec![vec![1, 2], vec![3]]),
+            Err(ErrorMatriz::FilasIrregulares {
+                fila: 1,
+                esperadas: 2,
+                encontradas: 1
+            })
+        );
+        assert_eq!(
+            Matriz::desde_vec(2, 2, vec![1, 2, 3]),
+            Err(ErrorMatriz::TamanioIncorrecto {
+                esperados: 4,
+                recibidos: 3
+            })
+        );
+    }
+
+    #[test]
+    fn multiplicar_por_bloques_da_lo_mismo() {
+        let a = Matriz::desde_filas(vec![vec![1, 2], vec![3, 4], vec![5, 6]]).unwrap();
+        let b = Matriz::desde_filas(vec![vec![1, 0, 2], vec![0, 1, 3]]).unwrap();
+        let esperada =
+            Matriz::desde_filas(vec![vec![1, 2, 8], vec![3, 4, 18], vec![5, 6, 28]]).unwrap();
+        assert_eq!(&a * &b, esperada);
+        assert_eq!(a.multiplicar_por_bloques(&b), Ok(esperada));
+        assert_eq!(
+            b.multiplicar(&b),
+            Err(ErrorMatriz::DimensionesIncompatibles {
+                izquierda: (2, 3),
+                derecha: (2, 3)
+            })
+        );
+        assert_eq!(&a * &Matriz::identidad(2), a);
+
+        // Tamaños que no son múltiplos del bloque, con enteros para comparar exacto
+        let mut aleatorio = Aleatorio::new(23);
+        let a = Matriz::desde_funcion(130, 70, |_, _| aleatorio.hasta(100) as i64);
+        let b = Matriz::desde_funcion(70, 150, |_, _| aleatorio.hasta(100) as i64);
+        let directa = a.multiplicar_directo(&b).unwrap();
+        assert_eq!(a.multiplicar_por_bloques(&b).unwrap(), directa);
+        assert_eq!(a.multiplicar(&b).unwrap(), directa);
+        // (AB)ᵀ = BᵀAᵀ
+        assert_eq!(directa.traspuesta(), &b.traspuesta() * &a.traspuesta());
+    }
+
+    #[test]
+    fn determinante_inversa_y_sistemas() {
+        let m = Matriz::desde_filas(vec![
+            vec![2.0, 1.0, 1.0],
+            vec![4.0, -6.0, 0.0],
+            vec![-2.0, 7.0, 2.0],
+        ])
+        .unwrap();
+        assert!((m.determinante().unwrap() - -16.0).abs() < 1e-12);
+        let x = m.resolver(&[5.0, -2.0, 9.0]).unwrap();
+        for (obtenido, esperado) in x.iter().zip([1.0, 1.0, 2.0]) {
+            assert!((obtenido - esperado).abs() < 1e-12);
+        }
+
+        let singular = Matriz::desde_filas(vec![vec![1.0, 2.0], vec![2.0, 4.0]]).unwrap();
+        assert_eq!(singular.determinante(), Ok(0.0));
+        assert_eq!(singular.inversa(), Err(ErrorMatriz::Singular));
+        let con_fila_de_ceros = Matriz::desde_filas(vec![vec![0.0, 0.0], vec![1.0, 2.0]]).unwrap();
+        assert_eq!(con_fila_de_ceros.determinante(), Ok(0.0));
+        assert_eq!(
+            Matriz::new(2, 3, 1.0).determinante(),
+            Err(ErrorMatriz::NoCuadrada(2, 3))
+        );
+
+        let mut aleatorio = Aleatorio::new(5);
+        for n in [1, 2, 5, 20, 80] {
+            let a = aleatoria(n, n, &mut aleatorio);
+            let inversa = a.inversa().unwrap();
+            assert!(casi_iguales(&(&a * &inversa), &Matriz::identidad(n), 1e-9));
+            // det(AB) = det(A) det(B)
+            let b = aleatoria(n, n, &mut aleatorio);
+            let (da, db) = (a.determinante().unwrap(), b.determinante().unwrap());
+            let dab = (&a * &b).determinante().unwrap();
+            assert!((dab - da * db).abs() <= 1e-9 * (1.0 + (da * db).abs()));
+
+            let x: Vec<f64> = (0..n).map(|_| aleatorio.normal(0.0, 1.0)).collect();
+            let b = Matriz::desde_vec(n, 1, x.clone()).unwrap();
+            let ax = (&a * &b).datos().to_vec();
+            let resuelto = a.resolver(&ax).unwrap();
+            for (r, x) in resuelto.iter().zip(&x) {
+                assert!((r - x).abs() < 1e-7, "{r} {x} n = {n}");
+            }
+        }
+    }
+
+    #[test]
+    fn escalas_muy_distintas_no_son_singulares() {
+        let diagonal = |valores: &[f64]| {
+            let mut m = Matriz::ceros(valores.len(), valores.len());
+            for (i, &valor) in valores.iter().enumerate() {
+                m[(i, i)] = valor;
+            }
+            m
+        };
+        let chica = diagonal(&[1e-20, 1.0]);
+        assert_eq!(chica.determinante(), Ok(1e-20));
+        assert_eq!(chica.inversa(), Ok(diagonal(&[1e20, 1.0])));
+        assert_eq!(chica.resolver(&[1e-20, 2.0]), Ok(vec![1.0, 2.0]));
+
+        let mezclada = diagonal(&[1e-3, 1e13, 1.0]);
+        let determinante = mezclada.determinante().unwrap();
+        assert!((determinante - 1e10).abs() <= 1e10 * f64::EPSILON);
+        let inversa = mezclada.inversa().unwrap();
+        assert!(casi_iguales(
+            &(&mezclada * &inversa),
+            &Matriz::identidad(3),
+            1e-12
+        ));
+    }
+
+    #[test]
+    fn con_complejos() {
+        let i = Complejo::<f64>::i();
+        let uno = Complejo::real(1.0);
+        // La matriz de Pauli Y al cuadrado es la identidad
+        let y =
+            Matriz::desde_filas(vec![vec![Complejo::CERO, -i], vec![i, Complejo::CERO]]).unwrap();
+        assert_eq!(&y * &y, Matriz::identidad(2));
+        assert_eq!(&y * uno, y);
+    }
+}
@@ -1,2 +1,11 @@
 pub mod aritmetica;
+pub mod calculadora;
+pub mod complejo;
+pub mod dispersa;
+pub mod entero_grande;
+pub mod estadistica;
+pub mod matriz;
+pub mod racional;
+pub mod teoria_de_numeros;
+pub mod unidades;
 pub mod vectores;
@@ -0,0 +1,464 @@
+/* Números racionales exactos. Con f64 0.1 + 0.2 da 0.30000000000000004
+ * porque 0.1 no se puede guardar exacto en binario, con fracciones
+ * 1/10 + 2/10 da 3/10. Racional<T> funciona con cualquier entero del
+ * trait Entero (ver aritmetica.rs) y siempre está reducido: el
+ * denominador es positivo y no comparte divisores con el numerador, así
+ * que 2/4 y 1/2 son el mismo valor y se guardan igual.
+ *
+ * Las operaciones verificadas devuelven un error si algo no cabe en T,
+ * los operadores + - * / hacen panic en ese caso, igual que los enteros
+ */
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+use std::str::FromStr;
+
+use super::aritmetica::{self, Entero, ErrorAritmetico};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Racional<T: Entero> {
+    numerador: T,
+    denominador: T,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ErrorRacional {
+    DenominadorCero,
+    // El valor no cabe en el tipo de entero, con la explicación
+    Desborde(String),
+    NoFinito,
+    Formato(String),
+}
+
+impl fmt::Display for ErrorRacional {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ErrorRacional::DenominadorCero => write!(f, "el denominador no puede ser cero"),
+            ErrorRacional::Desborde(detalle) => write!(f, "el resultado no cabe: {detalle}"),
+            ErrorRacional::NoFinito => write!(f, "NaN e infinito no son racionales"),
+            ErrorRacional::Formato(texto) => write!(
+                f,
+                "\"{texto}\" no es una fracción, debe ser como 3/4, -2 o 1.25"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ErrorRacional {}
+
+impl<T: Entero> From<ErrorAritmetico<T>> for ErrorRacional {
+    fn from(error: ErrorAritmetico<T>) -> Self {
+        ErrorRacional::Desborde(error.to_string())
+    }
+}
+
+fn no_cabe<T: Entero>(valor: impl fmt::Display) -> ErrorRacional {
+    ErrorRacional::Desborde(format!("{valor} no cabe en {}", T::NOMBRE))
+}
+
+// Máximo común divisor con el algoritmo de Euclides, siempre positivo
+fn mcd<T: Entero>(mut a: T, mut b: T) -> Result<T, ErrorRacional> {
+    while b != T::CERO {
+        let residuo = a
+            .checked_rem(b)
+            .ok_or_else(|| no_cabe::<T>(format!("{a} % {b}")))?;
+        a = b;
+        b = residuo;
+    }
+    if a < T::CERO {
+        a = a
+            .checked_neg()
+            .ok_or_else(|| no_cabe::<T>(format!("-({a})")))?;
+    }
+    Ok(a)
+}
+
+impl<T: Entero> Racional<T> {
+    pub fn new(numerador: T, denominador: T) -> Result<Self, ErrorRacional> {
+        if denominador == T::CERO {
+            return Err(ErrorRacional::DenominadorCero);
+        }
+        let divisor = mcd(numerador, denominador)?;
+        let mut numerador = aritmetica::division_verificada(numerador, divisor)?;
+        let mut denominador = aritmetica::division_verificada(denominador, divisor)?;
+        if denominador < T::CERO {
+            numerador = numerador
+                .checked_neg()
+                .ok_or_else(|| no_cabe::<T>(format!("-({numerador})")))?;
+            denominador = denominador
+                .checked_neg()
+                .ok_or_else(|| no_cabe::<T>(format!("-({denominador})")))?;
+        }
+        Ok(Self {
+            numerador,
+            denominador,
+        })
+    }
+
+    pub fn entero(valor: T) -> Self {
+        Self {
+            numerador: valor,
+            denominador: T::UNO,
+        }
+    }
+
+    pub fn numerador(&self) -> T {
+        self.numerador
+    }
+
+    pub fn denominador(&self) -> T {
+        self.denominador
+    }
+
+    /* a/b + c/d = (a * d/g + c * b/g) / (b/g * d), con g = mcd(b, d).
+     * Dividir entre g antes de multiplicar hace que se desborde menos
+     */
+    pub fn suma_verificada(&self, otro: &Self) -> Result<Self, ErrorRacional> {
+        let g = mcd(self.denominador, otro.denominador)?;
+        let b = aritmetica::division_verificada(self.denominador, g)?;
+        let d = aritmetica::division_verificada(otro.denominador, g)?;
+        let numerador = aritmetica::suma_verificada(
+            aritmetica::multiplicacion_verificada(self.numerador, d)?,
+            aritmetica::multiplicacion_verificada(otro.numerador, b)?,
+        )?;
+        Self::new(
+            numerador,
+            aritmetica::multiplicacion_verificada(b, otro.denominador)?,
+        )
+    }
+
+    pub fn resta_verificada(&self, otro: &Self) -> Result<Self, ErrorRacional> {
+        let g = mcd(self.denominador, otro.denominador)?;
+        let b = aritmetica::division_verificada(self.denominador, g)?;
+        let d = aritmetica::division_verificada(otro.denominador, g)?;
+        let numerador = aritmetica::resta_verificada(
+            aritmetica::multiplicacion_verificada(self.numerador, d)?,
+            aritmetica::multiplicacion_verificada(otro.numerador, b)?,
+        )?;
+        Self::new(
+            numerador,
+            aritmetica::multiplicacion_verificada(b, otro.denominador)?,
+        )
+    }
+
+    // Se reduce en cruz antes de multiplicar: (a/g1 * c/g2) / (b/g2 * d/g1)
+    pub fn multiplicacion_verificada(&self, otro: &Self) -> Result<Self, ErrorRacional> {
+        let g1 = mcd(self.numerador, otro.denominador)?.max(T::UNO);
+        let g2 = mcd(otro.numerador, self.denominador)?.max(T::UNO);
+        let numerador = aritmetica::multiplicacion_verificada(
+            aritmetica::division_verificada(self.numerador, g1)?,
+            aritmetica::division_verificada(otro.numerador, g2)?,
+        )?;
+        let denominador = aritmetica::multiplicacion_verificada(
+            aritmetica::division_verificada(self.denominador, g2)?,
+            aritmetica::division_verificada(otro.denominador, g1)?,
+        )?;
+        Self::new(numerador, denominador)
+    }
+
+    pub fn division_verificada(&self, otro: &Self) -> Result<Self, ErrorRacional> {
+        self.multiplicacion_verificada(&otro.reciproco()?)
+    }
+
+    pub fn reciproco(&self) -> Result<Self, ErrorRacional> {
+        Self::new(self.denominador, self.numerador)
+    }
+
+    pub fn a_f64(&self) -> f64 {
+        self.numerador.a_f64() / self.denominador.a_f64()
+    }
+
+    /* El valor exacto de un f64. Todo f64 finito es m * 2^e con m entero,
+     * así que se puede escribir como fracción, aunque 0.1 sea en realidad
+     * 3602879701896397/36028797018963968. Para una fracción más "bonita"
+     * está aproximar
+     */
+    pub fn desde_f64(valor: f64) -> Result<Self, ErrorRacional> {
+        if !valor.is_finite() {
+            return Err(ErrorRacional::NoFinito);
+        }
+        let bits = valor.to_bits();
+        let exponente = ((bits >> 52) & 0x7FF) as i32;
+        let fraccion = bits & ((1 << 52) - 1);
+        let (mut mantisa, mut exponente) = if exponente == 0 {
+            (fraccion, -1074)
+        } else {
+            (fraccion | (1 << 52), exponente - 1075)
+        };
+        if mantisa == 0 {
+            return Ok(Self::entero(T::CERO));
+        }
+        // Los ceros al final de la mantisa se pasan al exponente
+        let ceros = (mantisa.trailing_zeros() as i32).min((-exponente).max(0));
+        mantisa >>= ceros;
+        exponente += ceros;
+
+        let signo = if valor < 0.0 { -1 } else { 1 };
+        let mantisa = T::desde_i128(signo * mantisa as i128).ok_or_else(|| no_cabe::<T>(valor))?;
+        let dos = T::desde_i128(2).ok_or_else(|| no_cabe::<T>(2))?;
+        let potencia = aritmetica::potencia_verificada(dos, exponente.unsigned_abs())
+            .map_err(|_| no_cabe::<T>(valor))?;
+        if exponente >= 0 {
+            let numerador = aritmetica::multiplicacion_verificada(mantisa, potencia)
+                .map_err(|_| no_cabe::<T>(valor))?;
+            Ok(Self::entero(numerador))
+        } else {
+            Self::new(mantisa, potencia)
+        }
+    }
+
+    /* La mejor aproximación de valor con denominador de a lo mucho
+     * max_denominador, con fracciones continuas:
+     *
+     *   3.245 = 3 + 1/(4 + 1/(12 + 1/(4)))
+     *
+     * Cada vez que se corta la expresión sale una fracción (convergente)
+     * más cercana que las anteriores: 3, 13/4, 159/49, 649/200. Cuando
+     * la siguiente ya tiene el denominador muy grande se prueba también
+     * con un término más chico (semiconvergente) y se queda la más cercana
+     */
+    pub fn aproximar(valor: f64, max_denominador: T) -> Result<Self, ErrorRacional> {
+        if !valor.is_finite() {
+            return Err(ErrorRacional::NoFinito);
+        }
+        if max_denominador < T::UNO {
+            return Err(ErrorRacional::DenominadorCero);
+        }
+        let limite = max_denominador.a_i128().unwrap_or(i128::MAX);
+
+        // (h_{n-2}, h_{n-1}) y (k_{n-2}, k_{n-1}) de las convergentes h/k
+        let (mut h0, mut h1) = (0i128, 1i128);
+        let (mut k0, mut k1) = (1i128, 0i128);
+        let mut resto = valor;
+        loop {
+            let termino = resto.floor();
+            let siguiente = (termino.abs() < 1e30)
+                .then_some(termino as i128)
+                .and_then(|a| {
+                    Some((
+                        a.checked_mul(h1)?.checked_add(h0)?,
+                        a.checked_mul(k1)?.checked_add(k0)?,
+                    ))
+                });
+            let Some((h2, k2)) = siguiente.filter(|&(_, k2)| k2 <= limite) else {
+                if k1 == 0 {
+                    return Err(no_cabe::<T>(valor));
+                }
+                // Ya no cabe: la semiconvergente más grande que cabe contra la última convergente
+                let a = (limite - k0) / k1;
+                let (h, k) = (a * h1 + h0, a * k1 + k0);
+                let error = |h: i128, k: i128| (h as f64 / k as f64 - valor).abs();
+                if a > 0 && error(h, k) < error(h1, k1) {
+                    (h1, k1) = (h, k);
+                }
+                break;
+            };
+            (h0, h1) = (h1, h2);
+            (k0, k1) = (k1, k2);
+
+            let fraccion = resto - termino;
+            if fraccion == 0.0 || h1 as f64 / k1 as f64 == valor {
+                break;
+            }
+            resto = 1.0 / fraccion;
+        }
+
+        let numerador = T::desde_i128(h1).ok_or_else(|| no_cabe::<T>(h1))?;
+        let denominador = T::desde_i128(k1).ok_or_else(|| no_cabe::<T>(k1))?;
+        Self::new(numerador, denominador)
+    }
+}
+
+/* Comparar a/b con c/d multiplicando en cruz se puede desbordar, así que
+ * se comparan las partes enteras y, si son iguales, los recíprocos de lo
+ * que sobra (que invierten el orden), como en las fracciones continuas
+ */
+fn comparar<T: Entero>(a: T, b: T, c: T, d: T) -> Ordering {
+    // División hacia abajo con residuo entre 0 y el divisor, b y d son positivos
+    let partir = |x: T, y: T| {
+        let mut cociente = x.checked_div(y).unwrap_or(T::CERO);
+        let mut residuo = x.checked_rem(y).unwrap_or(T::CERO);
+        if residuo < T::CERO {
+            residuo = residuo.wrapping_add(y);
+            cociente = cociente.wrapping_sub(T::UNO);
+        }
+        (cociente, residuo)
+    };
+    let (q1, r1) = partir(a, b);
+    let (q2, r2) = partir(c, d);
+    match q1.cmp(&q2) {
+        Ordering::Equal => match (r1 == T::CERO, r2 == T::CERO) {
+            (true, true) => Ordering::Equal,
+            (true, false) => Ordering::Less,
+            (false, true) => Ordering::Greater,
+            (false, false) => comparar(d, r2, b, r1),
+        },
+        orden => orden,
+    }
+}
+
+impl<T: Entero> Ord for Racional<T> {
+    fn cmp(&self, otro: &Self) -> Ordering {
+        comparar(
+            self.numerador,
+            self.denominador,
+            otro.numerador,
+            otro.denominador,
+        )
+    }
+}
+
+impl<T: Entero> PartialOrd for Racional<T> {
+    fn partial_cmp(&self, otro: &Self) -> Option<Ordering> {
+        Some(self.cmp(otro))
+    }
+}
+
+impl<T: Entero> fmt::Display for Racional<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.denominador == T::UNO {
+            write!(f, "{}", self.numerador)
+        } else {
+            write!(f, "{}/{}", self.numerador, self.denominador)
+        }
+    }
+}
+
+// Acepta "3/4", "-3/4", "5" y decimales como "-1.25" (que es -5/4)
+impl<T: Entero> FromStr for Racional<T> {
+    type Err = ErrorRacional;
+
+    fn from_str(texto: &str) -> Result<Self, Self::Err> {
+        let formato = || ErrorRacional::Formato(texto.to_string());
+        let limpio = texto.trim();
+        let leer = |parte: &str| -> Result<T, ErrorRacional> {
+            let parte = parte.trim();
+            let solo_digitos = parte.strip_prefix('-').unwrap_or(parte);
+            if solo_digitos.is_empty() || !solo_digitos.chars().all(|c| c.is_ascii_digit()) {
+                return Err(formato());
+            }
+            parte.parse().map_err(|_| no_cabe::<T>(parte))
+        };
+
+        if let Some((numerador, denominador)) = limpio.split_once('/') {
+            return Self::new(leer(numerador)?, leer(denominador)?);
+        }
+        let Some((entera, decimales)) = limpio.split_once('.') else {
+            return Ok(Self::entero(leer(limpio)?));
+        };
+        // -1.25 = -125 / 10^2
+        if decimales.is_empty() || decimales.starts_with(['-', '+']) {
+            return Err(formato());
+        }
+        let numerador = leer(&format!("{entera}{decimales}"))?;
+        let diez = T::desde_i128(10).ok_or_else(|| no_cabe::<T>(10))?;
+        let denominador = aritmetica::potencia_verificada(diez, decimales.len() as u32)?;
+        Self::new(numerador, denominador)
+    }
+}
+
+macro_rules! operador {
+    ($($trait:ident $metodo:ident $verificada:ident),*) => {$(
+        impl<T: Entero> $trait for Racional<T> {
+            type Output = Racional<T>;
+
+            fn $metodo(self, otro: Self) -> Self {
+                self.$verificada(&otro).unwrap_or_else(|error| panic!("{error}"))
+            }
+        }
+    )*};
+}
+
+operador!(
+    Add add suma_verificada,
+    Sub sub resta_verificada,
+    Mul mul multiplicacion_verificada,
+    Div div division_verificada
+);
+
+impl<T: Entero> Neg for Racional<T> {
+    type Output = Racional<T>;
+
+    fn neg(self) -> Self {
+        match self.numerador.checked_neg() {
+            Some(numerador) => Self { numerador, ..self },
+            None => panic!("{}", no_cabe::<T>(format!("-({self})"))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn r(texto: &str) -> Racional<i64> {
+        texto.parse().unwrap()
+    }
+
+    #[test]
+    fn siempre_esta_reducido() {
+        assert_eq!(Racional::new(6, -8), Ok(r("-3/4")));
+        assert_eq!(r("10/5").to_string(), "2");
+        assert_eq!(r("-1.25"), r("-5/4"));
+        assert_eq!(
+            Racional::<u8>::new(1, 0),
+            Err(ErrorRacional::DenominadorCero)
+        );
+    }
+
+    #[test]
+    fn operaciones_exactas() {
+        assert_eq!(r("0.1") + r("0.2"), r("3/10"));
+        assert_eq!(r("1/2") - r("3/4"), r("-1/4"));
+        assert_eq!(r("2/3") * r("9/4"), r("3/2"));
+        assert_eq!(r("1/2") / r("-1/4"), r("-2"));
+        assert_eq!(-r("1/2"), r("-1/2"));
+        assert!(r("1/2").division_verificada(&r("0")).is_err());
+        // La reducción en cruz evita el desborde de 100/101 * 101/100 en i8
+        let a: Racional<i8> = "100/101".parse().unwrap();
+        assert_eq!(a * a.reciproco().unwrap(), Racional::entero(1));
+        assert!(matches!(
+            Racional::<i8>::entero(100).suma_verificada(&Racional::entero(100)),
+            Err(ErrorRacional::Desborde(_))
+        ));
+    }
+
+    #[test]
+    fn orden_sin_desbordarse() {
+        let mut valores = [r("1/3"), r("-1/2"), r("0"), r("2/7"), r("-7/3"), r("5")];
+        valores.sort();
+        let texto: Vec<String> = valores.iter().map(|valor| valor.to_string()).collect();
+        assert_eq!(texto, ["-7/3", "-1/2", "0", "2/7", "1/3", "5"]);
+        let grande = Racional::new(i64::MAX - 1, i64::MAX).unwrap();
+        let mas_grande = Racional::new(i64::MAX - 2, i64::MAX - 1).unwrap();
+        assert!(grande > mas_grande);
+    }
+
+    #[test]
+    fn conversion_con_f64() {
+        assert_eq!(
+            Racional::<i32>::desde_f64(0.75),
+            Ok(Racional::new(3, 4).unwrap())
+        );
+        assert_eq!(Racional::<i64>::desde_f64(-2.5).unwrap().a_f64(), -2.5);
+        assert!(matches!(
+            Racional::<i32>::desde_f64(0.1),
+            Err(ErrorRacional::Desborde(_))
+        ));
+        assert_eq!(
+            Racional::<i32>::desde_f64(f64::NAN),
+            Err(ErrorRacional::NoFinito)
+        );
+    }
+
+    #[test]
+    fn mejor_aproximacion() {
+        let pi = std::f64::consts::PI;
+        assert_eq!(Racional::aproximar(pi, 10i64), Ok(r("22/7")));
+        assert_eq!(Racional::aproximar(pi, 1000i64), Ok(r("355/113")));
+        assert_eq!(Racional::aproximar(0.1, 1000i64), Ok(r("1/10")));
+        assert_eq!(Racional::aproximar(-1.25, 100i64), Ok(r("-5/4")));
+        // Con denominador 1 es el entero más cercano
+        assert_eq!(Racional::aproximar(2.7, 1i64), Ok(r("3")));
+    }
+}
@@ -0,0 +1,492 @@
+/* Teoría de números. En estructuras_de_control se revisa a mano si un
+ * número es divisible entre 4, 3 o 2 con %, aquí está lo que se
+ * construye encima de eso: primos, máximo común divisor, aritmética
+ * modular y la función φ de Euler
+ *
+ * Todo es genérico sobre los enteros sin signo de tipos_datos (u8, u16,
+ * u32, u64, u128 y usize) con el trait Natural. Por dentro se trabaja
+ * con u128, que es donde cabe cualquiera de ellos, y para multiplicar
+ * módulo m sin desbordar se usa u128 cuando los factores son de 64
+ * bits o menos y sumas repetidas (como la multiplicación egipcia)
+ * cuando son más grandes
+ */
+use super::aritmetica::Entero;
+
+pub trait Natural: Entero {
+    fn a_u128(self) -> u128;
+    // Solo se llama con valores que caben, se obtuvieron de otro Self
+    fn desde_u128(valor: u128) -> Self;
+}
+
+macro_rules! impl_natural {
+    ($($tipo:ty),*) => {$(
+        impl Natural for $tipo {
+            fn a_u128(self) -> u128 { self as u128 }
+            fn desde_u128(valor: u128) -> Self { valor as $tipo }
+        }
+    )*};
+}
+
+impl_natural!(u8, u16, u32, u64, u128, usize);
+
+/* a + b mod m sin pasarse de u128, a y b ya son menores que m */
+fn suma_modular(a: u128, b: u128, m: u128) -> u128 {
+    if a >= m - b {
+        a - (m - b)
+    } else {
+        a + b
+    }
+}
+
+fn multiplicacion_modular(a: u128, b: u128, m: u128) -> u128 {
+    let (mut a, mut b) = (a % m, b % m);
+    if a <= u64::MAX as u128 && b <= u64::MAX as u128 {
+        return a * b % m;
+    }
+    // a * b = a * (bits de b), duplicando a en cada paso
+    let mut resultado = 0;
+    while b > 0 {
+        if b & 1 == 1 {
+            resultado = suma_modular(resultado, a, m);
+        }
+        a = suma_modular(a, a, m);
+        b >>= 1;
+    }
+    resultado
+}
+
+fn potencia_modular_u128(base: u128, mut exponente: u128, m: u128) -> u128 {
+    let mut resultado = 1 % m;
+    let mut base = base % m;
+    while exponente > 0 {
+        if exponente & 1 == 1 {
+            resultado = multiplicacion_modular(resultado, base, m);
+        }
+        base = multiplicacion_modular(base, base, m);
+        exponente >>= 1;
+    }
+    resultado
+}
+
+fn mcd_u128(mut a: u128, mut b: u128) -> u128 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+// Euclides: mcd(a, b) = mcd(b, a % b). mcd(0, 0) es 0
+pub fn mcd<T: Natural>(a: T, b: T) -> T {
+    T::desde_u128(mcd_u128(a.a_u128(), b.a_u128()))
+}
+
+// El mínimo común múltiplo, None si no cabe en T
+pub fn mcm<T: Natural>(a: T, b: T) -> Option<T> {
+    if a == T::CERO || b == T::CERO {
+        return Some(T::CERO);
+    }
+    // Se divide antes de multiplicar para desbordar lo menos posible
+    a.checked_div(mcd(a, b))?.checked_mul(b)
+}
+
+/* Euclides extendido: además del mcd encuentra x, y con a*x + b*y = mcd.
+ * Los coeficientes pueden ser negativos, por eso son i128
+ */
+pub fn mcd_extendido<T: Natural>(a: T, b: T) -> (T, i128, i128) {
+    let (mut r0, mut r1) = (a.a_u128(), b.a_u128());
+    let (mut x0, mut x1) = (1i128, 0i128);
+    let (mut y0, mut y1) = (0i128, 1i128);
+    while r1 != 0 {
+        let q = r0 / r1;
+        (r0, r1) = (r1, r0 - q * r1);
+        // |x1| nunca pasa de b / mcd, cabe en i128 salvo con u128 cerca del máximo
+        (x0, x1) = (x1, x0.wrapping_sub((q as i128).wrapping_mul(x1)));
+        (y0, y1) = (y1, y0.wrapping_sub((q as i128).wrapping_mul(y1)));
+    }
+    (T::desde_u128(r0), x0, y0)
+}
+
+// base^exponente mod modulo, por cuadrados repetidos. None si modulo es 0
+pub fn potencia_modular<T: Natural>(base: T, exponente: T, modulo: T) -> Option<T> {
+    if modulo == T::CERO {
+        return None;
+    }
+    Some(T::desde_u128(potencia_modular_u128(
+        base.a_u128(),
+        exponente.a_u128(),
+        modulo.a_u128(),
+    )))
+}
+
+// x con a * x ≡ 1 (mod m). Solo existe si a y m no comparten divisores
+pub fn inverso_modular<T: Natural>(a: T, modulo: T) -> Option<T> {
+    if modulo == T::CERO {
+        return None;
+    }
+    let m = modulo.a_u128();
+    let (g, x, _) = mcd_extendido(a.a_u128() % m, m);
+    if g != 1 {
+        return None;
+    }
+    // x puede ser negativo, se pasa a 0..m
+    let x = if x < 0 {
+        m - (x.unsigned_abs() % m)
+    } else {
+        x as u128
+    };
+    Some(T::desde_u128(x % m))
+}
+
+/* Miller-Rabin. Se escribe n - 1 = d * 2^s con d impar y se prueba cada
+ * base: si n es primo, base^d es 1 o alguno de los cuadrados siguientes
+ * es n - 1. Si una base falla n es compuesto seguro; si todas pasan n
+ * puede ser un pseudoprimo fuerte. Con las primeras 13 bases primas no
+ * hay pseudoprimos menores que LIMITE_EXACTO (Sorenson y Webster, 2015),
+ * unos 3.3 * 10^24: cubre todo u64 pero no todo u128
+ */
+const BASES: [u128; 13] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41];
+
+pub const LIMITE_EXACTO: u128 = 3_317_044_064_679_887_385_961_981;
+
+// true si pasa todas las bases, con n >= LIMITE_EXACTO puede mentir
+fn pasa_miller_rabin(n: u128) -> bool {
+    if n < 2 {
+        return false;
+    }
+    for &primo in &BASES {
+        if n.is_multiple_of(primo) {
+            return n == primo;
+        }
+    }
+    let s = (n - 1).trailing_zeros();
+    let d = (n - 1) >> s;
+    'bases: for &base in &BASES {
+        let mut x = potencia_modular_u128(base, d, n);
+        if x == 1 || x == n - 1 {
+            continue;
+        }
+        for _ in 1..s {
+            x = multiplicacion_modular(x, x, n);
+            if x == n - 1 {
+                continue 'bases;
+            }
+        }
+        return false;
+    }
+    true
+}
+
+/* Some(false) si es compuesto, que siempre se sabe seguro, y Some(true)
+ * si es primo. None si pasa todas las bases pero es demasiado grande para
+ * estar seguro, solo puede pasar con u128
+ */
+pub fn es_primo<T: Natural>(n: T) -> Option<bool> {
+    es_primo_u128(n.a_u128())
+}
+
+fn es_primo_u128(n: u128) -> Option<bool> {
+    match pasa_miller_rabin(n) {
+        false => Some(false),
+        true if n < LIMITE_EXACTO => Some(true),
+        true => None,
+    }
+}
+
+/* Pollard rho (con la mejora de Brent): la sucesión x -> x^2 + c mod n
+ * termina en un ciclo y, si n = p * q, módulo p se cicla antes. El mcd
+ * de la diferencia con n encuentra ese p. Solo se usa con n compuesto
+ */
+fn pollard_rho(n: u128) -> u128 {
+    if n.is_multiple_of(2) {
+        return 2;
+    }
+    for c in 1.. {
+        let f = |x: u128| suma_modular(multiplicacion_modular(x, x, n), c % n, n);
+        let mut liebre = 2u128;
+        let mut divisor = 1;
+        let mut paso = 1;
+        while divisor == 1 {
+            let tortuga = liebre;
+            for _ in 0..paso {
+                liebre = f(liebre);
+                divisor = mcd_u128(tortuga.abs_diff(liebre), n);
+                if divisor != 1 {
+                    break;
+                }
+            }
+            paso *= 2;
+        }
+        // divisor == n quiere decir que con este c no hubo suerte
+        if divisor != n {
+            return divisor;
+        }
+    }
+    unreachable!("algún c encuentra un divisor")
+}
+
+// None si queda un factor que no se sabe si es primo
+fn factores_primos(n: u128, factores: &mut Vec<u128>) -> Option<()> {
+    if n == 1 {
+        return Some(());
+    }
+    // Con un primo Pollard rho nunca termina, hay que estar seguros
+    if es_primo_u128(n)? {
+        factores.push(n);
+        return Some(());
+    }
+    let divisor = pollard_rho(n);
+    factores_primos(divisor, factores)?;
+    factores_primos(n / divisor, factores)
+}
+
+/* Los factores primos con su exponente, de menor a mayor: 360 es
+ * [(2, 3), (3, 2), (5, 1)]. Los primos chicos se quitan dividiendo y lo
+ * que queda se parte con Pollard rho. 0 y 1 no tienen factores. Con
+ * u128 es None si queda un factor mayor que LIMITE_EXACTO que pasa
+ * Miller-Rabin, para no devolver un compuesto como si fuera primo
+ */
+pub fn factorizar<T: Natural>(n: T) -> Option<Vec<(T, u32)>> {
+    let mut n = n.a_u128();
+    if n < 2 {
+        return Some(Vec::new());
+    }
+    let mut primos = Vec::new();
+    for p in 2..1000u128 {
+        while n.is_multiple_of(p) {
+            primos.push(p);
+            n /= p;
+        }
+    }
+    factores_primos(n, &mut primos)?;
+    primos.sort_unstable();
+
+    let mut factores: Vec<(T, u32)> = Vec::new();
+    for p in primos {
+        match factores.last_mut() {
+            Some((anterior, exponente)) if anterior.a_u128() == p => *exponente += 1,
+            _ => factores.push((T::desde_u128(p), 1)),
+        }
+    }
+    Some(factores)
+}
+
+/* φ(n), cuántos números de 1 a n no comparten divisores con n. Con la
+ * factorización es n * (1 - 1/p) por cada primo p que lo divide, None
+ * si no se pudo factorizar
+ */
+pub fn totient<T: Natural>(n: T) -> Option<T> {
+    let mut resultado = n.a_u128();
+    for (p, _) in factorizar(n)? {
+        let p = p.a_u128();
+        resultado = resultado / p * (p - 1);
+    }
+    Some(T::desde_u128(resultado))
+}
+
+// La criba de Eratóstenes de siempre, los primos menores que limite
+fn criba(limite: usize) -> Vec<usize> {
+    let mut es_primo = vec![true; limite];
+    let mut primos = Vec::new();
+    for i in 2..limite {
+        if es_primo[i] {
+            primos.push(i);
+            for multiplo in (i * i..limite).step_by(i) {
+                es_primo[multiplo] = false;
+            }
+        }
+    }
+    primos
+}
+
+/* Los primos en [desde, hasta). Una criba normal necesita un arreglo de
+ * tamaño hasta, la segmentada criba un pedazo a la vez usando solo los
+ * primos hasta √hasta, así se pueden buscar primos cerca de 10^12 sin
+ * un arreglo de 10^12 elementos
+ *
+ * Esos primos sí se guardan todos: con hasta cerca de u64::MAX serían
+ * 4 GiB solo para cribarlos. Por eso hasta no puede pasar de
+ * HASTA_MAXIMO (√ es 2^24, unos 16 MiB), si pasa es None
+ */
+pub const HASTA_MAXIMO: u128 = 1 << 48;
+
+pub fn primos_en_rango<T: Natural>(desde: T, hasta: T) -> Option<Vec<T>> {
+    const SEGMENTO: u128 = 1 << 16;
+    let (desde, hasta) = (desde.a_u128().max(2), hasta.a_u128());
+    if hasta > HASTA_MAXIMO {
+        return None;
+    }
+    if desde >= hasta {
+        return Some(Vec::new());
+    }
+    let raiz = (hasta as f64).sqrt() as u128 + 1;
+    let base: Vec<u128> = criba(raiz as usize + 1)
+        .into_iter()
+        .map(|p| p as u128)
+        .collect();
+
+    let mut primos = Vec::new();
+    let mut inicio = desde;
+    while inicio < hasta {
+        let fin = (inicio + SEGMENTO).min(hasta);
+        let mut compuesto = vec![false; (fin - inicio) as usize];
+        for &p in &base {
+            if p * p >= fin {
+                break;
+            }
+            // El primer múltiplo de p en el segmento, sin tachar a p mismo
+            let primero = (inicio.div_ceil(p) * p).max(p * p);
+            let mut multiplo = primero;
+            while multiplo < fin {
+                compuesto[(multiplo - inicio) as usize] = true;
+                multiplo += p;
+            }
+        }
+        primos.extend(
+            compuesto
+                .iter()
+                .enumerate()
+                .filter(|(_, &tachado)| !tachado)
+                .map(|(i, _)| T::desde_u128(inicio + i as u128)),
+        );
+        inicio = fin;
+    }
+    Some(primos)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aleatorio::Aleatorio;
+
+    fn es_primo_lento(n: u64) -> bool {
+        n >= 2 && (2..).take_while(|d| d * d <= n).all(|d| !n.is_multiple_of(d))
+    }
+
+    fn mcd_lento(a: u64, b: u64) -> u64 {
+        (1..=a.max(b))
+            .rev()
+            .find(|&d| a.is_multiple_of(d) && b.is_multiple_of(d))
+            .unwrap_or(0)
+    }
+
+    #[test]
+    fn primos_contra_division() {
+        for n in 0..=u8::MAX {
+            assert_eq!(es_primo(n), Some(es_primo_lento(n as u64)), "{n}");
+        }
+        for n in 0..5000u32 {
+            assert_eq!(es_primo(n), Some(es_primo_lento(n as u64)), "{n}");
+        }
+        // Pseudoprimos fuertes de algunas bases y primos grandes
+        assert_eq!(es_primo(3_215_031_751u64), Some(false));
+        assert_eq!(es_primo(3_825_123_056_546_413_051u64), Some(false));
+        assert_eq!(es_primo(18_446_744_073_709_551_557u64), Some(true));
+        assert_eq!(es_primo(u64::MAX), Some(false));
+
+        let rango: Vec<u64> = primos_en_rango(0u64, 3000).unwrap();
+        let lentos: Vec<u64> = (0..3000).filter(|&n| es_primo_lento(n)).collect();
+        assert_eq!(rango, lentos);
+        let lejos = primos_en_rango(1_000_000_000u64, 1_000_200_000).unwrap();
+        assert!(lejos.iter().all(|&p| es_primo(p) == Some(true)));
+        let sueltos = (1_000_000_000u64..1_000_200_000)
+            .step_by(997)
+            .filter(|&n| es_primo_lento(n))
+            .all(|n| lejos.binary_search(&n).is_ok());
+        assert!(sueltos);
+    }
+
+    #[test]
+    fn limites_de_u128() {
+        // El último pseudoprimo fuerte de las 12 primeras bases, 41 lo descubre
+        let pseudoprimo = 318_665_857_834_031_151_167_461u128;
+        assert_eq!(factorizar(pseudoprimo).unwrap().len(), 2);
+        assert_eq!(es_primo(pseudoprimo), Some(false));
+
+        // 2^127 - 1 es primo, pero arriba de LIMITE_EXACTO no se puede asegurar
+        let mersenne = (1u128 << 127) - 1;
+        assert_eq!(es_primo(mersenne), None);
+        assert_eq!(factorizar(mersenne), None);
+        assert_eq!(totient(mersenne), None);
+        assert_eq!(es_primo(mersenne - 1), Some(false));
+        // Los compuestos grandes sí, si sus factores son chicos
+        assert_eq!(totient(1u128 << 100), Some(1 << 99));
+        assert_eq!(factorizar(u128::MAX).unwrap().len(), 9);
+
+        assert_eq!(primos_en_rango(u64::MAX - 100, u64::MAX), None);
+        assert_eq!(primos_en_rango(0u128, u128::MAX), None);
+        let orilla: Vec<u128> = (HASTA_MAXIMO - 100..HASTA_MAXIMO)
+            .filter(|&n| es_primo(n) == Some(true))
+            .collect();
+        assert_eq!(primos_en_rango(HASTA_MAXIMO - 100, HASTA_MAXIMO), Some(orilla));
+    }
+
+    #[test]
+    fn factorizacion_y_totient() {
+        assert_eq!(factorizar(360u32).unwrap(), [(2, 3), (3, 2), (5, 1)]);
+        assert_eq!(factorizar(1u8).unwrap(), []);
+        assert_eq!(
+            factorizar(u64::MAX).unwrap(),
+            [
+                (3, 1),
+                (5, 1),
+                (17, 1),
+                (257, 1),
+                (641, 1),
+                (65537, 1),
+                (6_700_417, 1)
+            ]
+        );
+        // Producto de dos primos grandes, aquí trabaja Pollard rho
+        let grande = 4_294_967_291u64 * 4_294_967_279;
+        assert_eq!(factorizar(grande).unwrap(), [(4_294_967_279, 1), (4_294_967_291, 1)]);
+
+        for n in 1..=500u16 {
+            let factores = factorizar(n).unwrap();
+            let producto: u64 = factores.iter().map(|&(p, e)| (p as u64).pow(e)).product();
+            assert_eq!(producto, n as u64);
+            assert!(factores.iter().all(|&(p, _)| es_primo_lento(p as u64)));
+            let coprimos = (1..=n)
+                .filter(|&k| mcd_lento(k as u64, n as u64) == 1)
+                .count();
+            assert_eq!(totient(n), Some(coprimos as u16), "φ({n})");
+        }
+    }
+
+    #[test]
+    fn mcd_y_aritmetica_modular() {
+        for a in 0..60u8 {
+            for b in 0..60u8 {
+                let g = mcd(a, b);
+                assert_eq!(g as u64, mcd_lento(a as u64, b as u64));
+                let (g2, x, y) = mcd_extendido(a, b);
+                assert_eq!(g2, g);
+                assert_eq!(a as i128 * x + b as i128 * y, g as i128);
+                if g > 0 {
+                    assert_eq!(
+                        mcm(a as u32, b as u32),
+                        Some(a as u32 * b as u32 / g as u32)
+                    );
+                }
+            }
+        }
+        assert_eq!(mcm(200u8, 3), None);
+
+        let mut aleatorio = Aleatorio::new(21);
+        for _ in 0..300 {
+            let modulo = aleatorio.hasta(1000) + 1;
+            let base = aleatorio.hasta(10_000);
+            let exponente = aleatorio.hasta(50);
+            let lento = (0..exponente).fold(1 % modulo, |acumulado, _| acumulado * base % modulo);
+            assert_eq!(potencia_modular(base, exponente, modulo), Some(lento));
+
+            match inverso_modular(base, modulo) {
+                Some(inverso) => assert_eq!(base * inverso % modulo, 1 % modulo),
+                None => assert_ne!(mcd(base, modulo), 1),
+            }
+        }
+        // Con u128 grandes la multiplicación no cabe y se hace por partes
+        let m = u128::MAX - 158;
+        assert_eq!(potencia_modular(2u128, m - 1, m), Some(1));
+        assert_eq!(potencia_modular(5u8, 3, 0), None);
+    }
+}
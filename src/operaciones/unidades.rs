@@ -0,0 +1,620 @@
+/* Cantidades con unidades. Un f32 suelto no dice si son kilos o libras,
+ * metros o pies, y nada impide sumar un peso con una longitud. Aquí cada
+ * dimensión es su propio tipo: Longitud, Masa, Tiempo y Velocidad son
+ * Cantidad<U> con U el enum de sus unidades, así que
+ *
+ *     Longitud::metros(3.0) + Masa::kilogramos(2.0)
+ *
+ * no compila, y Longitud / Tiempo da una Velocidad. Cada cantidad guarda
+ * el valor en la unidad con la que se creó, así que pedirla en esa misma
+ * unidad da el número exacto; al pasar a otra se multiplica por cuánto
+ * vale cada unidad en la unidad base (metro, kilogramo, segundo, m/s).
+ *
+ * La temperatura va aparte porque Celsius, Fahrenheit y Kelvin no sólo
+ * cambian la escala sino también dónde está el cero: es el ejercicio del
+ * capítulo 3 del libro
+ */
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+use std::str::FromStr;
+
+#[derive(Debug, PartialEq)]
+pub enum ErrorUnidad {
+    Formato(String),
+    UnidadDesconocida {
+        unidad: String,
+        dimension: &'static str,
+    },
+}
+
+impl fmt::Display for ErrorUnidad {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ErrorUnidad::Formato(texto) => {
+                write!(f, "\"{texto}\" no es una cantidad, debe ser como 12.5 km/h")
+            }
+            ErrorUnidad::UnidadDesconocida { unidad, dimension } => {
+                write!(f, "\"{unidad}\" no es una unidad de {dimension}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ErrorUnidad {}
+
+pub trait Unidad: Copy + PartialEq + fmt::Debug + 'static {
+    const DIMENSION: &'static str;
+    const TODAS: &'static [Self];
+
+    // Cuánto vale una de estas en la unidad base
+    fn factor(self) -> f64;
+    // El primero es el que se usa al escribir, los demás también se leen
+    fn simbolos(self) -> &'static [&'static str];
+
+    fn simbolo(self) -> &'static str {
+        self.simbolos()[0]
+    }
+
+    fn desde_simbolo(texto: &str) -> Option<Self> {
+        Self::TODAS
+            .iter()
+            .copied()
+            .find(|unidad| unidad.simbolos().contains(&texto))
+    }
+}
+
+// Cada enum de unidades con su factor y sus símbolos
+macro_rules! unidades {
+    ($tipo:ident, $dimension:literal { $($variante:ident: $factor:expr, [$($simbolo:literal),+]),+ $(,)? }) => {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum $tipo {
+            $($variante),+
+        }
+
+        impl Unidad for $tipo {
+            const DIMENSION: &'static str = $dimension;
+            const TODAS: &'static [Self] = &[$($tipo::$variante),+];
+
+            fn factor(self) -> f64 {
+                match self {
+                    $($tipo::$variante => $factor),+
+                }
+            }
+
+            fn simbolos(self) -> &'static [&'static str] {
+                match self {
+                    $($tipo::$variante => &[$($simbolo),+]),+
+                }
+            }
+        }
+    };
+}
+
+// Las imperiales son exactas por definición desde 1959
+unidades!(UnidadLongitud, "longitud" {
+    Metro: 1.0, ["m"],
+    Kilometro: 1000.0, ["km"],
+    Centimetro: 0.01, ["cm"],
+    Milimetro: 0.001, ["mm"],
+    Pulgada: 0.0254, ["in", "pulg"],
+    Pie: 0.3048, ["ft", "pie", "pies"],
+    Yarda: 0.9144, ["yd"],
+    Milla: 1609.344, ["mi"],
+});
+
+unidades!(UnidadMasa, "masa" {
+    Kilogramo: 1.0, ["kg"],
+    Gramo: 0.001, ["g"],
+    Tonelada: 1000.0, ["t"],
+    Libra: 0.45359237, ["lb"],
+    Onza: 0.028349523125, ["oz"],
+});
+
+unidades!(UnidadTiempo, "tiempo" {
+    Segundo: 1.0, ["s"],
+    Minuto: 60.0, ["min"],
+    Hora: 3600.0, ["h"],
+    Dia: 86400.0, ["d", "día", "días"],
+});
+
+unidades!(UnidadVelocidad, "velocidad" {
+    MetroPorSegundo: 1.0, ["m/s"],
+    KilometroPorHora: 1000.0 / 3600.0, ["km/h", "kph"],
+    MillaPorHora: 1609.344 / 3600.0, ["mph", "mi/h"],
+    PiePorSegundo: 0.3048, ["ft/s"],
+    Nudo: 1852.0 / 3600.0, ["kn", "nudos"],
+});
+
+#[derive(Debug, Clone, Copy)]
+pub struct Cantidad<U: Unidad> {
+    valor: f64,
+    unidad: U,
+}
+
+pub type Longitud = Cantidad<UnidadLongitud>;
+pub type Masa = Cantidad<UnidadMasa>;
+pub type Tiempo = Cantidad<UnidadTiempo>;
+pub type Velocidad = Cantidad<UnidadVelocidad>;
+
+impl<U: Unidad> Cantidad<U> {
+    pub fn new(valor: f64, unidad: U) -> Self {
+        Self { valor, unidad }
+    }
+
+    pub fn valor(&self) -> f64 {
+        self.valor
+    }
+
+    pub fn unidad(&self) -> U {
+        self.unidad
+    }
+
+    // El número en otra unidad, en la misma sale tal cual
+    pub fn en(&self, unidad: U) -> f64 {
+        if unidad == self.unidad {
+            self.valor
+        } else {
+            self.valor * self.unidad.factor() / unidad.factor()
+        }
+    }
+
+    pub fn convertir(&self, unidad: U) -> Self {
+        Self::new(self.en(unidad), unidad)
+    }
+
+    fn en_base(&self) -> f64 {
+        self.valor * self.unidad.factor()
+    }
+}
+
+macro_rules! constructores {
+    ($unidad:ident { $($nombre:ident => $variante:ident),+ $(,)? }) => {
+        impl Cantidad<$unidad> {
+            $(
+                pub fn $nombre(valor: f64) -> Self {
+                    Self::new(valor, $unidad::$variante)
+                }
+            )+
+        }
+    };
+}
+
+constructores!(UnidadLongitud {
+    metros => Metro,
+    kilometros => Kilometro,
+    centimetros => Centimetro,
+    pulgadas => Pulgada,
+    pies => Pie,
+    millas => Milla,
+});
+
+constructores!(UnidadMasa {
+    kilogramos => Kilogramo,
+    gramos => Gramo,
+    libras => Libra,
+    onzas => Onza,
+});
+
+constructores!(UnidadTiempo {
+    segundos => Segundo,
+    minutos => Minuto,
+    horas => Hora,
+});
+
+constructores!(UnidadVelocidad {
+    metros_por_segundo => MetroPorSegundo,
+    kilometros_por_hora => KilometroPorHora,
+    millas_por_hora => MillaPorHora,
+    nudos => Nudo,
+});
+
+/* 1 km y 1000 m son la misma cantidad aunque se hayan escrito distinto.
+ * Se comparan los f64 ya pasados a la unidad base y sin tolerancia, como
+ * cualquier f64: 1 km == 1000 m porque 1000 * 1 es exacto, pero 1 ft y
+ * 12 in no son iguales porque 12 * 0.0254 se pasa por el último bit. Para
+ * eso hay que comparar con un margen, como la función cerca de las pruebas
+ */
+impl<U: Unidad> PartialEq for Cantidad<U> {
+    fn eq(&self, otra: &Self) -> bool {
+        self.en_base() == otra.en_base()
+    }
+}
+
+impl<U: Unidad> PartialOrd for Cantidad<U> {
+    fn partial_cmp(&self, otra: &Self) -> Option<Ordering> {
+        self.en_base().partial_cmp(&otra.en_base())
+    }
+}
+
+// Sumar y restar sólo entre la misma dimensión, el resultado queda en la
+// unidad de la izquierda
+impl<U: Unidad> Add for Cantidad<U> {
+    type Output = Self;
+
+    fn add(self, otra: Self) -> Self {
+        Self::new(self.valor + otra.en(self.unidad), self.unidad)
+    }
+}
+
+impl<U: Unidad> Sub for Cantidad<U> {
+    type Output = Self;
+
+    fn sub(self, otra: Self) -> Self {
+        Self::new(self.valor - otra.en(self.unidad), self.unidad)
+    }
+}
+
+impl<U: Unidad> Neg for Cantidad<U> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.valor, self.unidad)
+    }
+}
+
+impl<U: Unidad> Mul<f64> for Cantidad<U> {
+    type Output = Self;
+
+    fn mul(self, factor: f64) -> Self {
+        Self::new(self.valor * factor, self.unidad)
+    }
+}
+
+impl<U: Unidad> Div<f64> for Cantidad<U> {
+    type Output = Self;
+
+    fn div(self, divisor: f64) -> Self {
+        Self::new(self.valor / divisor, self.unidad)
+    }
+}
+
+// Dos cantidades de lo mismo dan un número sin unidad: 1 km / 1 m = 1000
+impl<U: Unidad> Div for Cantidad<U> {
+    type Output = f64;
+
+    fn div(self, otra: Self) -> f64 {
+        self.en_base() / otra.en_base()
+    }
+}
+
+// Las que cambian de dimensión salen en unidades base
+impl Div<Tiempo> for Longitud {
+    type Output = Velocidad;
+
+    fn div(self, tiempo: Tiempo) -> Velocidad {
+        Velocidad::metros_por_segundo(self.en_base() / tiempo.en_base())
+    }
+}
+
+impl Div<Velocidad> for Longitud {
+    type Output = Tiempo;
+
+    fn div(self, velocidad: Velocidad) -> Tiempo {
+        Tiempo::segundos(self.en_base() / velocidad.en_base())
+    }
+}
+
+impl Mul<Tiempo> for Velocidad {
+    type Output = Longitud;
+
+    fn mul(self, tiempo: Tiempo) -> Longitud {
+        Longitud::metros(self.en_base() * tiempo.en_base())
+    }
+}
+
+impl Mul<Velocidad> for Tiempo {
+    type Output = Longitud;
+
+    fn mul(self, velocidad: Velocidad) -> Longitud {
+        velocidad * self
+    }
+}
+
+// Como 12.5 km/h, {:.1} también le llega al número
+impl<U: Unidad> fmt::Display for Cantidad<U> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match f.precision() {
+            Some(decimales) => write!(f, "{:.*} {}", decimales, self.valor, self.unidad.simbolo()),
+            None => write!(f, "{} {}", self.valor, self.unidad.simbolo()),
+        }
+    }
+}
+
+/* Separa "12.5 km/h" (o "12.5km/h") en el número y la unidad. El número
+ * es lo que hay al principio hecho de dígitos, punto, signos y e
+ */
+fn separar(texto: &str) -> Result<(f64, &str), ErrorUnidad> {
+    let limpio = texto.trim();
+    let fin = limpio
+        .find(|c: char| !(c.is_ascii_digit() || matches!(c, '.' | '-' | '+' | 'e' | 'E')))
+        .unwrap_or(limpio.len());
+    let (numero, unidad) = limpio.split_at(fin);
+    match numero.parse() {
+        Ok(valor) if !unidad.trim().is_empty() => Ok((valor, unidad.trim())),
+        _ => Err(ErrorUnidad::Formato(texto.to_string())),
+    }
+}
+
+impl<U: Unidad> FromStr for Cantidad<U> {
+    type Err = ErrorUnidad;
+
+    fn from_str(texto: &str) -> Result<Self, Self::Err> {
+        let (valor, simbolo) = separar(texto)?;
+        let unidad = U::desde_simbolo(simbolo).ok_or_else(|| ErrorUnidad::UnidadDesconocida {
+            unidad: simbolo.to_string(),
+            dimension: U::DIMENSION,
+        })?;
+        Ok(Self::new(valor, unidad))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Escala {
+    Celsius,
+    Fahrenheit,
+    Kelvin,
+}
+
+impl Escala {
+    pub fn simbolo(self) -> &'static str {
+        match self {
+            Escala::Celsius => "°C",
+            Escala::Fahrenheit => "°F",
+            Escala::Kelvin => "K",
+        }
+    }
+
+    fn desde_simbolo(texto: &str) -> Option<Self> {
+        match texto {
+            "°C" | "C" | "ºC" => Some(Escala::Celsius),
+            "°F" | "F" | "ºF" => Some(Escala::Fahrenheit),
+            "K" => Some(Escala::Kelvin),
+            _ => None,
+        }
+    }
+}
+
+/* No basta con multiplicar: 0 °C son 32 °F y 273.15 K. Todo pasa por
+ * Celsius, que con estos números deja exactos los puntos conocidos
+ * (agua congelada y hirviendo, el -40 donde coinciden C y F)
+ */
+#[derive(Debug, Clone, Copy)]
+pub struct Temperatura {
+    valor: f64,
+    escala: Escala,
+}
+
+impl Temperatura {
+    pub fn new(valor: f64, escala: Escala) -> Self {
+        Self { valor, escala }
+    }
+
+    pub fn celsius(valor: f64) -> Self {
+        Self::new(valor, Escala::Celsius)
+    }
+
+    pub fn fahrenheit(valor: f64) -> Self {
+        Self::new(valor, Escala::Fahrenheit)
+    }
+
+    pub fn kelvin(valor: f64) -> Self {
+        Self::new(valor, Escala::Kelvin)
+    }
+
+    pub fn valor(&self) -> f64 {
+        self.valor
+    }
+
+    pub fn escala(&self) -> Escala {
+        self.escala
+    }
+
+    fn a_celsius(&self) -> f64 {
+        match self.escala {
+            Escala::Celsius => self.valor,
+            Escala::Fahrenheit => (self.valor - 32.0) * 5.0 / 9.0,
+            Escala::Kelvin => self.valor - 273.15,
+        }
+    }
+
+    pub fn en(&self, escala: Escala) -> f64 {
+        if escala == self.escala {
+            return self.valor;
+        }
+        let celsius = self.a_celsius();
+        match escala {
+            Escala::Celsius => celsius,
+            Escala::Fahrenheit => celsius * 9.0 / 5.0 + 32.0,
+            Escala::Kelvin => celsius + 273.15,
+        }
+    }
+
+    pub fn convertir(&self, escala: Escala) -> Self {
+        Self::new(self.en(escala), escala)
+    }
+}
+
+// Igual que Cantidad: exacta, en Celsius y sin tolerancia
+impl PartialEq for Temperatura {
+    fn eq(&self, otra: &Self) -> bool {
+        self.a_celsius() == otra.a_celsius()
+    }
+}
+
+impl PartialOrd for Temperatura {
+    fn partial_cmp(&self, otra: &Self) -> Option<Ordering> {
+        self.a_celsius().partial_cmp(&otra.a_celsius())
+    }
+}
+
+impl fmt::Display for Temperatura {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match f.precision() {
+            Some(decimales) => write!(f, "{:.*} {}", decimales, self.valor, self.escala.simbolo()),
+            None => write!(f, "{} {}", self.valor, self.escala.simbolo()),
+        }
+    }
+}
+
+impl FromStr for Temperatura {
+    type Err = ErrorUnidad;
+
+    fn from_str(texto: &str) -> Result<Self, Self::Err> {
+        let (valor, simbolo) = separar(texto)?;
+        let escala =
+            Escala::desde_simbolo(simbolo).ok_or_else(|| ErrorUnidad::UnidadDesconocida {
+                unidad: simbolo.to_string(),
+                dimension: "temperatura",
+            })?;
+        Ok(Self::new(valor, escala))
+    }
+}
+
+// Para leer una cantidad sin saber antes de qué es
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Medida {
+    Longitud(Longitud),
+    Masa(Masa),
+    Tiempo(Tiempo),
+    Velocidad(Velocidad),
+    Temperatura(Temperatura),
+}
+
+pub fn leer(texto: &str) -> Result<Medida, ErrorUnidad> {
+    let (valor, simbolo) = separar(texto)?;
+    if let Some(unidad) = UnidadLongitud::desde_simbolo(simbolo) {
+        Ok(Medida::Longitud(Cantidad::new(valor, unidad)))
+    } else if let Some(unidad) = UnidadMasa::desde_simbolo(simbolo) {
+        Ok(Medida::Masa(Cantidad::new(valor, unidad)))
+    } else if let Some(unidad) = UnidadTiempo::desde_simbolo(simbolo) {
+        Ok(Medida::Tiempo(Cantidad::new(valor, unidad)))
+    } else if let Some(unidad) = UnidadVelocidad::desde_simbolo(simbolo) {
+        Ok(Medida::Velocidad(Cantidad::new(valor, unidad)))
+    } else if let Some(escala) = Escala::desde_simbolo(simbolo) {
+        Ok(Medida::Temperatura(Temperatura::new(valor, escala)))
+    } else {
+        Err(ErrorUnidad::UnidadDesconocida {
+            unidad: simbolo.to_string(),
+            dimension: "ninguna dimensión conocida",
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cerca(a: f64, b: f64) -> bool {
+        (a - b).abs() <= 1e-9 * (1.0 + b.abs())
+    }
+
+    #[test]
+    fn metricas_e_imperiales() {
+        assert!(cerca(
+            Longitud::millas(1.0).en(UnidadLongitud::Kilometro),
+            1.609344
+        ));
+        assert!(cerca(Longitud::pies(3.0).en(UnidadLongitud::Yarda), 1.0));
+        assert!(cerca(Longitud::pulgadas(12.0).en(UnidadLongitud::Pie), 1.0));
+        assert!(cerca(Masa::libras(1.0).en(UnidadMasa::Gramo), 453.59237));
+        assert!(cerca(Masa::libras(1.0).en(UnidadMasa::Onza), 16.0));
+        assert!(cerca(Tiempo::horas(1.5).en(UnidadTiempo::Minuto), 90.0));
+        assert!(cerca(
+            Velocidad::nudos(1.0).en(UnidadVelocidad::KilometroPorHora),
+            1.852
+        ));
+        // En la misma unidad el número no pasa por la base
+        assert_eq!(
+            Velocidad::kilometros_por_hora(12.3).en(UnidadVelocidad::KilometroPorHora),
+            12.3
+        );
+
+        assert_eq!(Longitud::kilometros(1.0), Longitud::metros(1000.0));
+        // == no tiene tolerancia, con la conversión de pulgadas no alcanza
+        assert_ne!(Longitud::pies(1.0), Longitud::pulgadas(12.0));
+        assert!(cerca(Longitud::pies(1.0) / Longitud::pulgadas(12.0), 1.0));
+        assert!(Masa::kilogramos(1.0) > Masa::libras(2.0));
+        let suma = Longitud::metros(1.0) + Longitud::centimetros(50.0);
+        assert_eq!(suma.unidad(), UnidadLongitud::Metro);
+        assert!(cerca(suma.valor(), 1.5));
+        assert!(cerca(
+            Longitud::kilometros(1.0) / Longitud::metros(250.0),
+            4.0
+        ));
+        assert_eq!((-Masa::gramos(3.0) * 2.0).valor(), -6.0);
+
+        // 100 km en 2 horas son 50 km/h, y a 50 km/h en 30 minutos se hacen 25 km
+        let velocidad = Longitud::kilometros(100.0) / Tiempo::horas(2.0);
+        assert!(cerca(velocidad.en(UnidadVelocidad::KilometroPorHora), 50.0));
+        let distancia = velocidad * Tiempo::minutos(30.0);
+        assert!(cerca(distancia.en(UnidadLongitud::Kilometro), 25.0));
+        let tiempo = Longitud::millas(60.0) / Velocidad::millas_por_hora(30.0);
+        assert!(cerca(tiempo.en(UnidadTiempo::Hora), 2.0));
+    }
+
+    #[test]
+    fn temperaturas() {
+        let casos = [
+            (0.0, 32.0, 273.15),
+            (100.0, 212.0, 373.15),
+            (-40.0, -40.0, 233.15),
+        ];
+        for (c, f, k) in casos {
+            let celsius = Temperatura::celsius(c);
+            assert!(cerca(celsius.en(Escala::Fahrenheit), f));
+            assert!(cerca(celsius.en(Escala::Kelvin), k));
+            assert!(cerca(Temperatura::fahrenheit(f).en(Escala::Celsius), c));
+            assert!(cerca(Temperatura::kelvin(k).en(Escala::Fahrenheit), f));
+        }
+        assert_eq!(Temperatura::kelvin(0.0).en(Escala::Celsius), -273.15);
+        assert_eq!(Temperatura::celsius(100.0), Temperatura::fahrenheit(212.0));
+        // La comparación es exacta, el redondeo de la conversión cuenta
+        assert_ne!(Temperatura::kelvin(0.1), Temperatura::celsius(-273.05));
+        assert!(cerca(Temperatura::kelvin(0.1).en(Escala::Celsius), -273.05));
+        assert!(Temperatura::fahrenheit(50.0) < Temperatura::celsius(20.0));
+        assert_eq!(Temperatura::celsius(36.6).to_string(), "36.6 °C");
+    }
+
+    #[test]
+    fn escribir_y_leer() {
+        let velocidad: Velocidad = "12.5 km/h".parse().unwrap();
+        assert_eq!(velocidad.unidad(), UnidadVelocidad::KilometroPorHora);
+        assert_eq!(velocidad.valor(), 12.5);
+        assert_eq!(velocidad.to_string(), "12.5 km/h");
+        assert_eq!(format!("{:.2}", Longitud::pies(3.0)), "3.00 ft");
+        assert_eq!("-3e2m".parse::<Longitud>(), Ok(Longitud::metros(-300.0)));
+        assert_eq!(" 2 pies ".parse::<Longitud>(), Ok(Longitud::pies(2.0)));
+        assert_eq!(
+            "98.6 F".parse::<Temperatura>(),
+            Ok(Temperatura::fahrenheit(98.6))
+        );
+
+        assert_eq!(
+            "12.5 km/h".parse::<Longitud>(),
+            Err(ErrorUnidad::UnidadDesconocida {
+                unidad: "km/h".to_string(),
+                dimension: "longitud"
+            })
+        );
+        for malo in ["", "km", "12", "doce km", "1.2.3 m"] {
+            assert_eq!(
+                malo.parse::<Masa>(),
+                Err(ErrorUnidad::Formato(malo.to_string())),
+                "{malo}"
+            );
+        }
+
+        assert_eq!(leer("12.5 km/h"), Ok(Medida::Velocidad(velocidad)));
+        assert_eq!(leer("3 lb"), Ok(Medida::Masa(Masa::libras(3.0))));
+        assert_eq!(
+            leer("300 K"),
+            Ok(Medida::Temperatura(Temperatura::kelvin(300.0)))
+        );
+        assert!(matches!(
+            leer("3 furlongs"),
+            Err(ErrorUnidad::UnidadDesconocida { .. })
+        ));
+    }
+}
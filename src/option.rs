@@ -30,6 +30,48 @@
  * tomar información de cualquier tipo.
  */
 
+use crate::ejercicios::{self, caso, Ejercicio};
+use crate::lecciones::{Entrada, Leccion};
+
+pub const LECCION: Leccion = Leccion {
+    nombre: "option",
+    titulo: "El enum Option",
+    orden: 7,
+    entrada: Entrada::Simple(main),
+    ejercicios: EJERCICIOS,
+    preguntas: include_str!("preguntas/option.txt"),
+    fuente: include_str!("option.rs"),
+};
+
+// Practicar cómo sacar el T de un Option<T>
+const EJERCICIOS: &[Ejercicio] = &[
+    Ejercicio {
+        nombre: "sumar_opcion",
+        enunciado: "Suma un i32 a un Option<i32>, tomando None como 0",
+        firma: "fn sumar_opcion(a: Option<i32>, b: i32) -> i32",
+        casos: || {
+            vec![
+                caso("sumar_opcion(Some(2), 3)", || ejercicios::option::sumar_opcion(Some(2), 3), 5),
+                caso("sumar_opcion(None, 3)", || ejercicios::option::sumar_opcion(None, 3), 3),
+                caso("sumar_opcion(Some(-4), 0)", || ejercicios::option::sumar_opcion(Some(-4), 0), -4),
+            ]
+        },
+    },
+    Ejercicio {
+        nombre: "sumar_opciones",
+        enunciado: "Suma dos Option<i32>, el resultado es None si falta alguno",
+        firma: "fn sumar_opciones(a: Option<i32>, b: Option<i32>) -> Option<i32>",
+        casos: || {
+            vec![
+                caso("sumar_opciones(Some(1), Some(2))", || ejercicios::option::sumar_opciones(Some(1), Some(2)), Some(3)),
+                caso("sumar_opciones(None, Some(2))", || ejercicios::option::sumar_opciones(None, Some(2)), None),
+                caso("sumar_opciones(Some(1), None)", || ejercicios::option::sumar_opciones(Some(1), None), None),
+                caso("sumar_opciones(None, None)", || ejercicios::option::sumar_opciones(None, None), None),
+            ]
+        },
+    },
+];
+
 pub fn main() {
     let _some_number = Some(5); // Tipo Option<i32>
     let _some_char = Some('e'); // Tipo Option<char>
@@ -12,6 +12,18 @@
  * When the owner goes out of scope, the value will be dropped.
  */
 
+use crate::lecciones::{Entrada, Leccion};
+
+pub const LECCION: Leccion = Leccion {
+    nombre: "ownership",
+    titulo: "Ownership",
+    orden: 4,
+    entrada: Entrada::Simple(main),
+    ejercicios: &[],
+    preguntas: include_str!("preguntas/ownership.txt"),
+    fuente: include_str!("ownership.rs"),
+};
+
 pub fn main() {
     // hola aquí no existe
     let hola = "hola"; // Se crea hola y al ser inmutable se almacena en el stack
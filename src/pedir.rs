@@ -0,0 +1,125 @@
+/* Pedir datos al usuario sin que un error de dedo termine el programa.
+ * Con .parse().expect(...) el primer dato mal escrito causa un panic,
+ * aquí se vuelve a preguntar hasta que el dato se pueda convertir al
+ * tipo que se pidió. Todo se lee desde entrada.rs, así que también
+ * funciona con los guiones, y los mensajes se muestran con el print
+ * de leer_de_consola que limpia el buffer para que se vean de inmediato
+ *
+ * Si la entrada se acaba (por ejemplo un guion que ya no tiene más
+ * respuestas) se devuelve un error en lugar de preguntar para siempre
+ */
+
+use std::fmt::Display;
+use std::io;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+use crate::entrada;
+use crate::leer_de_consola::print;
+use crate::mensajes::{self, mensaje};
+
+// Muestra el mensaje y devuelve lo que se escribió, ya sin espacios
+fn leer_respuesta(mensaje: &str) -> io::Result<String> {
+    print(mensaje.to_string());
+    let mut respuesta = String::new();
+    if entrada::leer_linea(&mut respuesta)? == 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::UnexpectedEof,
+            mensajes::texto("pedir.fin_de_entrada"),
+        ));
+    }
+    Ok(respuesta.trim().to_string())
+}
+
+// Pregunta hasta que la respuesta se pueda convertir a T
+pub fn pedir<T: FromStr>(mensaje: &str) -> io::Result<T> {
+    loop {
+        let respuesta = leer_respuesta(mensaje)?;
+        match respuesta.parse() {
+            Ok(valor) => return Ok(valor),
+            Err(_) => println!("{}", mensaje!("pedir.no_entendi", respuesta)),
+        }
+    }
+}
+
+// Igual que pedir, pero además el valor debe estar dentro del rango
+pub fn pedir_en_rango<T>(mensaje: &str, rango: RangeInclusive<T>) -> io::Result<T>
+where
+    T: FromStr + PartialOrd + Display,
+{
+    loop {
+        let valor: T = pedir(mensaje)?;
+        if rango.contains(&valor) {
+            return Ok(valor);
+        }
+        println!(
+            "{}",
+            mensaje!("pedir.fuera_de_rango", valor, rango.start(), rango.end())
+        );
+    }
+}
+
+// Si no se escribe nada se usa el valor por defecto, que se muestra
+// entre corchetes después del mensaje
+pub fn pedir_o<T: FromStr + Display>(mensaje: &str, defecto: T) -> io::Result<T> {
+    let mensaje = format!("{mensaje}[{defecto}] ");
+    loop {
+        let respuesta = leer_respuesta(&mensaje)?;
+        if respuesta.is_empty() {
+            return Ok(defecto);
+        }
+        match respuesta.parse() {
+            Ok(valor) => return Ok(valor),
+            Err(_) => println!("{}", mensaje!("pedir.no_entendi", respuesta)),
+        }
+    }
+}
+
+// Pregunta de sí o no, con una respuesta vacía se usa el valor por defecto
+pub fn confirmar(mensaje: &str, defecto: bool) -> io::Result<bool> {
+    let opciones = if defecto {
+        mensajes::texto("pedir.si_por_defecto")
+    } else {
+        mensajes::texto("pedir.no_por_defecto")
+    };
+    let mensaje = format!("{mensaje}{opciones} ");
+    loop {
+        let respuesta = leer_respuesta(&mensaje)?.to_lowercase();
+        match respuesta.as_str() {
+            "" => return Ok(defecto),
+            "s" | "si" | "sí" | "y" | "yes" => return Ok(true),
+            "n" | "no" => return Ok(false),
+            _ => println!("{}", mensaje!("pedir.responde_si_o_no")),
+        }
+    }
+}
+
+/* Muestra las opciones numeradas desde 1 y devuelve el índice (desde 0)
+ * de la que se eligió. Sin opciones el rango sería 1..=0 y ninguna
+ * respuesta serviría, así que es un error desde el principio
+ */
+pub fn elegir(mensaje: &str, opciones: &[&str]) -> io::Result<usize> {
+    if opciones.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            mensajes::texto("pedir.sin_opciones"),
+        ));
+    }
+    println!("{mensaje}");
+    for (i, opcion) in opciones.iter().enumerate() {
+        println!("  {}. {opcion}", i + 1);
+    }
+    let eleccion = pedir_en_rango(mensajes::texto("pedir.opcion"), 1..=opciones.len())?;
+    Ok(eleccion - 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn elegir_sin_opciones_es_un_error() {
+        let error = elegir("¿Cuál?", &[]).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+    }
+}
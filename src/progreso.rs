@@ -0,0 +1,281 @@
+/* Avance de cada alumno: qué lecciones ha corrido y qué ejercicios ha
+ * pasado. Se guarda en un archivo de texto plano que se puede leer y
+ * editar a mano, por defecto en ~/.local/share/rust-notas/progreso.txt
+ * (o en $XDG_DATA_HOME/rust-notas/, o donde diga RUST_NOTAS_PROGRESO).
+ *
+ * Formato (versión 1):
+ *
+ *   # rust-notas progreso v1
+ *   <alumno>	leccion	<leccion>
+ *   <alumno>	ejercicio	<leccion>/<ejercicio>
+ *
+ * La primera línea es obligatoria e indica la versión. Cada registro va
+ * en su propia línea con tres campos separados por tabuladores. Las
+ * líneas vacías y las que empiezan con # después del encabezado se
+ * ignoran. El alumno es el de la variable ALUMNO o, si no existe, el
+ * usuario del sistema. Un nombre con tabuladores, saltos de línea u otros
+ * caracteres de control, o que empiece con #, rompería el formato, así
+ * que no se acepta
+ */
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::env;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::lecciones::{self, Leccion};
+
+const ENCABEZADO: &str = "# rust-notas progreso v1";
+const PREFIJO_ENCABEZADO: &str = "# rust-notas progreso v";
+
+#[derive(Debug)]
+pub enum ErrorProgreso {
+    Io(io::Error),
+    // La primera línea no es un encabezado reconocible
+    SinEncabezado,
+    VersionNoSoportada(String),
+    LineaInvalida { numero: usize, contenido: String },
+    AlumnoInvalido(String),
+}
+
+impl fmt::Display for ErrorProgreso {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ErrorProgreso::Io(error) => write!(f, "no se pudo leer o escribir el progreso: {error}"),
+            ErrorProgreso::SinEncabezado => write!(
+                f,
+                "el archivo de progreso no empieza con \"{ENCABEZADO}\", puede estar dañado"
+            ),
+            ErrorProgreso::VersionNoSoportada(version) => write!(
+                f,
+                "el archivo de progreso es de la versión {version} y sólo se entiende la 1"
+            ),
+            ErrorProgreso::LineaInvalida { numero, contenido } => write!(
+                f,
+                "la línea {numero} del archivo de progreso no es válida: \"{contenido}\""
+            ),
+            ErrorProgreso::AlumnoInvalido(alumno) => write!(
+                f,
+                "el alumno {alumno:?} no se puede guardar, no debe tener caracteres de control ni empezar con #"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ErrorProgreso {}
+
+impl From<io::Error> for ErrorProgreso {
+    fn from(error: io::Error) -> Self {
+        ErrorProgreso::Io(error)
+    }
+}
+
+#[derive(Default)]
+pub struct Avance {
+    pub lecciones: BTreeSet<String>,
+    // Guardados como "<leccion>/<ejercicio>"
+    pub ejercicios: BTreeSet<String>,
+}
+
+#[derive(Default)]
+pub struct Progreso {
+    alumnos: BTreeMap<String, Avance>,
+}
+
+pub fn ruta() -> PathBuf {
+    if let Some(ruta) = env::var_os("RUST_NOTAS_PROGRESO") {
+        return PathBuf::from(ruta);
+    }
+    let datos = match env::var_os("XDG_DATA_HOME") {
+        Some(datos) => PathBuf::from(datos),
+        None => {
+            let casa = env::var_os("HOME").or_else(|| env::var_os("USERPROFILE"));
+            PathBuf::from(casa.unwrap_or_default()).join(".local").join("share")
+        }
+    };
+    datos.join("rust-notas").join("progreso.txt")
+}
+
+fn nombre_valido(alumno: &str) -> bool {
+    !alumno.trim().is_empty() && !alumno.starts_with('#') && !alumno.contains(char::is_control)
+}
+
+pub fn alumno() -> String {
+    let Some((variable, nombre)) = ["ALUMNO", "USER", "USERNAME"]
+        .iter()
+        .find_map(|variable| env::var(variable).ok().map(|nombre| (variable, nombre)))
+    else {
+        return String::from("alumno");
+    };
+    if nombre_valido(&nombre) {
+        return nombre;
+    }
+    if !nombre.trim().is_empty() {
+        eprintln!("Aviso: {variable}={nombre:?} no sirve como nombre de alumno, se usa \"alumno\"");
+    }
+    String::from("alumno")
+}
+
+impl Progreso {
+    // Si el archivo no existe todavía se empieza sin progreso
+    pub fn cargar(ruta: &Path) -> Result<Self, ErrorProgreso> {
+        match fs::read_to_string(ruta) {
+            Ok(texto) => Self::leer(&texto),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    fn leer(texto: &str) -> Result<Self, ErrorProgreso> {
+        let mut lineas = texto.lines().enumerate();
+        match lineas.next() {
+            Some((_, ENCABEZADO)) => {}
+            Some((_, linea)) => {
+                return Err(match linea.strip_prefix(PREFIJO_ENCABEZADO) {
+                    Some(version) => ErrorProgreso::VersionNoSoportada(version.to_string()),
+                    None => ErrorProgreso::SinEncabezado,
+                });
+            }
+            // Un archivo vacío es como no tener progreso
+            None => return Ok(Self::default()),
+        }
+
+        let mut progreso = Self::default();
+        for (indice, linea) in lineas {
+            if linea.trim().is_empty() || linea.starts_with('#') {
+                continue;
+            }
+            let invalida = || ErrorProgreso::LineaInvalida {
+                numero: indice + 1,
+                contenido: linea.to_string(),
+            };
+            let campos: Vec<&str> = linea.split('\t').collect();
+            let [alumno, tipo, nombre] = campos[..] else {
+                return Err(invalida());
+            };
+            if alumno.is_empty() || nombre.is_empty() {
+                return Err(invalida());
+            }
+            let avance = progreso.alumnos.entry(alumno.to_string()).or_default();
+            match tipo {
+                "leccion" => avance.lecciones.insert(nombre.to_string()),
+                "ejercicio" if nombre.contains('/') => avance.ejercicios.insert(nombre.to_string()),
+                _ => return Err(invalida()),
+            };
+        }
+        Ok(progreso)
+    }
+
+    pub fn guardar(&self, ruta: &Path) -> Result<(), ErrorProgreso> {
+        let mut texto = format!("{ENCABEZADO}\n");
+        for (alumno, avance) in &self.alumnos {
+            // Mejor no guardar que dejar un archivo que ya no se puede leer
+            if !nombre_valido(alumno) {
+                return Err(ErrorProgreso::AlumnoInvalido(alumno.clone()));
+            }
+            for leccion in &avance.lecciones {
+                texto.push_str(&format!("{alumno}\tleccion\t{leccion}\n"));
+            }
+            for ejercicio in &avance.ejercicios {
+                texto.push_str(&format!("{alumno}\tejercicio\t{ejercicio}\n"));
+            }
+        }
+        if let Some(carpeta) = ruta.parent() {
+            fs::create_dir_all(carpeta)?;
+        }
+        fs::write(ruta, texto)?;
+        Ok(())
+    }
+
+    pub fn avance(&self, alumno: &str) -> Option<&Avance> {
+        self.alumnos.get(alumno)
+    }
+
+    pub fn marcar_leccion(&mut self, alumno: &str, leccion: &str) {
+        let avance = self.alumnos.entry(alumno.to_string()).or_default();
+        avance.lecciones.insert(leccion.to_string());
+    }
+
+    pub fn marcar_ejercicio(&mut self, alumno: &str, leccion: &str, ejercicio: &str) {
+        let avance = self.alumnos.entry(alumno.to_string()).or_default();
+        avance.ejercicios.insert(format!("{leccion}/{ejercicio}"));
+    }
+}
+
+impl Avance {
+    fn ejercicios_pasados(&self, leccion: &Leccion) -> usize {
+        leccion
+            .ejercicios
+            .iter()
+            .filter(|ejercicio| {
+                self.ejercicios
+                    .contains(&format!("{}/{}", leccion.nombre, ejercicio.nombre))
+            })
+            .count()
+    }
+}
+
+/* Carga, modifica y guarda el progreso del alumno actual. Se usa al
+ * terminar una lección o un ejercicio; si el archivo está dañado sólo se
+ * avisa, no es motivo para que la lección falle
+ */
+pub fn registrar(modificar: impl FnOnce(&mut Progreso, &str)) {
+    let ruta = ruta();
+    let resultado = Progreso::cargar(&ruta).and_then(|mut progreso| {
+        modificar(&mut progreso, &alumno());
+        progreso.guardar(&ruta)
+    });
+    if let Err(error) = resultado {
+        eprintln!("Aviso: no se guardó el progreso ({}): {error}", ruta.display());
+    }
+}
+
+pub fn mostrar(progreso: &Progreso, alumno: &str) {
+    let vacio = Avance::default();
+    let avance = progreso.avance(alumno).unwrap_or(&vacio);
+
+    println!("Progreso de {alumno}\n");
+    let todas = lecciones::todas();
+    for leccion in &todas {
+        let marca = if avance.lecciones.contains(leccion.nombre) { "x" } else { " " };
+        let ejercicios = if leccion.ejercicios.is_empty() {
+            String::new()
+        } else {
+            format!(
+                "  ejercicios {}/{}",
+                avance.ejercicios_pasados(leccion),
+                leccion.ejercicios.len()
+            )
+        };
+        println!("[{marca}] {:>2}. {:<24}{ejercicios}", leccion.orden, leccion.nombre);
+    }
+
+    let vistas = todas
+        .iter()
+        .filter(|leccion| avance.lecciones.contains(leccion.nombre))
+        .count();
+    println!("\n{vistas} de {} lecciones vistas", todas.len());
+}
+
+/* La siguiente lección en el orden de las clases: primero la primera que
+ * no se ha corrido y, si ya se vieron todas, la primera con ejercicios
+ * pendientes
+ */
+pub fn siguiente(progreso: &Progreso, alumno: &str) -> Option<(&'static Leccion, &'static str)> {
+    let vacio = Avance::default();
+    let avance = progreso.avance(alumno).unwrap_or(&vacio);
+    let todas = lecciones::todas();
+
+    if let Some(leccion) = todas
+        .iter()
+        .find(|leccion| !avance.lecciones.contains(leccion.nombre))
+    {
+        return Some((leccion, "run"));
+    }
+    todas
+        .into_iter()
+        .find(|leccion| avance.ejercicios_pasados(leccion) < leccion.ejercicios.len())
+        .map(|leccion| (leccion, "check"))
+}
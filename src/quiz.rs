@@ -0,0 +1,163 @@
+/* Quizzes de opción múltiple para repasar las lecciones. Cada lección
+ * puede traer un banco de preguntas en src/preguntas/<leccion>.txt con
+ * este formato:
+ *
+ *   # comentario
+ *   ? La pregunta
+ *   - una opción incorrecta
+ *   * la opción correcta (sólo una)
+ *   - otra opción incorrecta
+ *   > La explicación que se muestra después de responder
+ *
+ * Las preguntas se separan con una línea vacía. Las opciones se barajan
+ * con una semilla para que el orden no se memorice pero se pueda repetir
+ * el mismo quiz pasando la misma semilla
+ */
+
+use std::fmt;
+use std::io;
+
+use crate::aleatorio::Aleatorio;
+use crate::lecciones::Leccion;
+use crate::pedir;
+
+pub struct Pregunta {
+    pub enunciado: String,
+    pub opciones: Vec<String>,
+    // Índice de la opción correcta dentro de opciones
+    pub correcta: usize,
+    pub explicacion: String,
+}
+
+#[derive(Debug)]
+pub struct ErrorPreguntas {
+    pub linea: usize,
+    pub mensaje: String,
+}
+
+impl fmt::Display for ErrorPreguntas {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "línea {}: {}", self.linea, self.mensaje)
+    }
+}
+
+impl std::error::Error for ErrorPreguntas {}
+
+// Una pregunta a medio leer mientras se recorren las líneas
+#[derive(Default)]
+struct Borrador {
+    linea: usize,
+    enunciado: Option<String>,
+    opciones: Vec<String>,
+    correctas: Vec<usize>,
+    explicacion: Option<String>,
+}
+
+impl Borrador {
+    fn terminar(self) -> Result<Pregunta, ErrorPreguntas> {
+        let error = |mensaje: &str| ErrorPreguntas {
+            linea: self.linea,
+            mensaje: mensaje.to_string(),
+        };
+        let Some(enunciado) = self.enunciado.clone() else {
+            return Err(error("a la pregunta le falta el enunciado (?)"));
+        };
+        if self.opciones.len() < 2 {
+            return Err(error("una pregunta necesita al menos dos opciones"));
+        }
+        let [correcta] = self.correctas[..] else {
+            return Err(error("una pregunta debe tener exactamente una opción correcta (*)"));
+        };
+        let Some(explicacion) = self.explicacion.clone() else {
+            return Err(error("a la pregunta le falta la explicación (>)"));
+        };
+        Ok(Pregunta {
+            enunciado,
+            opciones: self.opciones,
+            correcta,
+            explicacion,
+        })
+    }
+}
+
+pub fn leer_preguntas(texto: &str) -> Result<Vec<Pregunta>, ErrorPreguntas> {
+    let mut preguntas = Vec::new();
+    let mut borrador: Option<Borrador> = None;
+
+    for (indice, linea) in texto.lines().enumerate() {
+        let numero = indice + 1;
+        let linea = linea.trim_end();
+
+        if linea.trim().is_empty() {
+            if let Some(terminado) = borrador.take() {
+                preguntas.push(terminado.terminar()?);
+            }
+            continue;
+        }
+        if linea.starts_with('#') {
+            continue;
+        }
+
+        let mut caracteres = linea.chars();
+        let marca = caracteres.next().unwrap_or(' ');
+        let contenido = caracteres.as_str().trim().to_string();
+        let actual = borrador.get_or_insert_with(|| Borrador {
+            linea: numero,
+            ..Borrador::default()
+        });
+        match marca {
+            '?' if actual.enunciado.is_none() => actual.enunciado = Some(contenido),
+            '?' => {
+                return Err(ErrorPreguntas {
+                    linea: numero,
+                    mensaje: String::from("falta una línea vacía antes de la siguiente pregunta"),
+                })
+            }
+            '-' => actual.opciones.push(contenido),
+            '*' => {
+                actual.correctas.push(actual.opciones.len());
+                actual.opciones.push(contenido);
+            }
+            '>' => actual.explicacion = Some(contenido),
+            _ => {
+                return Err(ErrorPreguntas {
+                    linea: numero,
+                    mensaje: format!("la línea debe empezar con ?, -, *, > o #: \"{linea}\""),
+                })
+            }
+        }
+    }
+    if let Some(terminado) = borrador {
+        preguntas.push(terminado.terminar()?);
+    }
+    Ok(preguntas)
+}
+
+// Devuelve (aciertos, total)
+pub fn jugar(leccion: &Leccion, preguntas: &mut [Pregunta], semilla: u64) -> io::Result<(usize, usize)> {
+    let mut aleatorio = Aleatorio::new(semilla);
+    aleatorio.barajar(preguntas);
+
+    let mut aciertos = 0;
+    for (numero, pregunta) in preguntas.iter().enumerate() {
+        // Se barajan los índices para no perder cuál es la correcta
+        let mut orden: Vec<usize> = (0..pregunta.opciones.len()).collect();
+        aleatorio.barajar(&mut orden);
+        let opciones: Vec<&str> = orden.iter().map(|&i| pregunta.opciones[i].as_str()).collect();
+
+        println!();
+        let mensaje = format!("{}. {}", numero + 1, pregunta.enunciado);
+        let elegida = orden[pedir::elegir(&mensaje, &opciones)?];
+
+        if elegida == pregunta.correcta {
+            aciertos += 1;
+            println!("¡Correcto!");
+        } else {
+            println!("Incorrecto, la respuesta era: {}", pregunta.opciones[pregunta.correcta]);
+        }
+        println!("{}", pregunta.explicacion);
+        println!("(Repasa con: cargo run -- run {})", leccion.nombre);
+    }
+
+    Ok((aciertos, preguntas.len()))
+}
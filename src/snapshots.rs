@@ -0,0 +1,162 @@
+/* Las lecciones sólo se comunican con println!, así que la forma de
+ * saber si un cambio alteró lo que imprimen es guardar su salida en un
+ * archivo .snap y compararla cada vez. Cada lección se corre aislada
+ * (ver aislamiento.rs) y con su guion si es interactiva. Si la salida
+ * cambió se muestra qué líneas son distintas; si el cambio era a
+ * propósito se corre con --aceptar para guardar la salida nueva
+ */
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::aislamiento::{self, Resultado};
+use crate::lecciones::Leccion;
+
+const CARPETA_SNAPSHOTS: &str = "tests/snapshots";
+// Líneas iguales que se muestran alrededor de cada cambio
+const CONTEXTO: usize = 2;
+
+// Junto al Cargo.toml, igual que los guiones de entrada.rs, para que
+// `snapshot` funcione aunque se corra desde otra carpeta
+fn carpeta() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join(CARPETA_SNAPSHOTS)
+}
+
+fn ruta_de(leccion: &str) -> PathBuf {
+    carpeta().join(format!("{leccion}.snap"))
+}
+
+/* Lo que se guarda: una primera línea con cómo terminó la lección y
+ * después todo lo que imprimió. stderr no se guarda porque trae rutas
+ * y números de línea que cambian con cualquier edición
+ */
+fn contenido(resultado: &Resultado) -> String {
+    let mut texto = format!("# estado: {}\n", resultado.descripcion_estado());
+    texto.push_str(&resultado.stdout);
+    if !texto.ends_with('\n') {
+        texto.push('\n');
+    }
+    texto
+}
+
+// Devuelve true si todas las lecciones coinciden con su snapshot
+pub fn comprobar(lecciones: &[&Leccion], aceptar: bool) -> io::Result<bool> {
+    let mut todo_igual = true;
+    for leccion in lecciones {
+        let actual = contenido(&aislamiento::ejecutar_aislada(leccion)?);
+        let ruta = ruta_de(leccion.nombre);
+        let guardado = fs::read_to_string(&ruta).ok();
+
+        if guardado.as_deref() == Some(actual.as_str()) {
+            println!("{:<24} igual", leccion.nombre);
+            continue;
+        }
+
+        if aceptar {
+            fs::create_dir_all(carpeta())?;
+            fs::write(&ruta, &actual)?;
+            println!("{:<24} guardado en {}", leccion.nombre, ruta.display());
+            continue;
+        }
+
+        todo_igual = false;
+        match guardado {
+            Some(guardado) => {
+                println!("{:<24} DIFERENTE", leccion.nombre);
+                println!("--- {} (guardado)", ruta.display());
+                println!("+++ salida actual");
+                print!("{}", diferencias(&guardado, &actual));
+            }
+            None => println!(
+                "{:<24} no tiene snapshot, corre con --aceptar para crearlo",
+                leccion.nombre
+            ),
+        }
+    }
+    Ok(todo_igual)
+}
+
+enum Cambio<'a> {
+    Igual(&'a str),
+    Quitada(&'a str),
+    Agregada(&'a str),
+}
+
+/* Diferencias línea por línea usando la subsecuencia común más larga:
+ * las líneas que están en ambas se conservan y el resto se marca como
+ * quitada (-) o agregada (+)
+ */
+fn comparar<'a>(antes: &[&'a str], despues: &[&'a str]) -> Vec<Cambio<'a>> {
+    // lcs[i][j] = largo de la subsecuencia común de antes[i..] y despues[j..]
+    let mut lcs = vec![vec![0usize; despues.len() + 1]; antes.len() + 1];
+    for i in (0..antes.len()).rev() {
+        for j in (0..despues.len()).rev() {
+            lcs[i][j] = if antes[i] == despues[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut cambios = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < antes.len() && j < despues.len() {
+        if antes[i] == despues[j] {
+            cambios.push(Cambio::Igual(antes[i]));
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            cambios.push(Cambio::Quitada(antes[i]));
+            i += 1;
+        } else {
+            cambios.push(Cambio::Agregada(despues[j]));
+            j += 1;
+        }
+    }
+    cambios.extend(antes[i..].iter().map(|linea| Cambio::Quitada(linea)));
+    cambios.extend(despues[j..].iter().map(|linea| Cambio::Agregada(linea)));
+    cambios
+}
+
+// Muestra sólo los cambios y unas cuantas líneas iguales alrededor
+fn diferencias(antes: &str, despues: &str) -> String {
+    let antes: Vec<&str> = antes.lines().collect();
+    let despues: Vec<&str> = despues.lines().collect();
+    let cambios = comparar(&antes, &despues);
+
+    let cerca_de_un_cambio = |indice: usize| {
+        let inicio = indice.saturating_sub(CONTEXTO);
+        let fin = (indice + CONTEXTO + 1).min(cambios.len());
+        cambios[inicio..fin]
+            .iter()
+            .any(|cambio| !matches!(cambio, Cambio::Igual(_)))
+    };
+
+    let mut texto = String::new();
+    let mut omitidas = false;
+    for (indice, cambio) in cambios.iter().enumerate() {
+        match cambio {
+            Cambio::Igual(linea) if cerca_de_un_cambio(indice) => {
+                texto.push_str(&format!("  {linea}\n"));
+                omitidas = false;
+            }
+            Cambio::Igual(_) => {
+                if !omitidas {
+                    texto.push_str("  ...\n");
+                    omitidas = true;
+                }
+            }
+            Cambio::Quitada(linea) => {
+                texto.push_str(&format!("- {linea}\n"));
+                omitidas = false;
+            }
+            Cambio::Agregada(linea) => {
+                texto.push_str(&format!("+ {linea}\n"));
+                omitidas = false;
+            }
+        }
+    }
+    texto
+}
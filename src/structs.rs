@@ -34,6 +34,8 @@ struct RgbColor(i32, i32, i32);
 // Definimos propiedades
 struct AnimalVolador {
     nombre: String,
+    // Se asigna en el constructor pero ningún método la lee
+    #[allow(dead_code)]
     edad: u8,
 }
 
@@ -53,11 +55,27 @@ impl AnimalVolador {
      * a estos valores. self también debe ser mutable o inmutable según
      * necesites
      */
-    pub fn volar(&self, velocidad: f32) {
-        println!("{} vuela a {} km/h", self.nombre, velocidad);
+    pub fn volar(&self, velocidad: Velocidad) {
+        // La velocidad puede venir en cualquier unidad, el mensaje la muestra en km/h
+        let kilometros_por_hora = velocidad.en(UnidadVelocidad::KilometroPorHora);
+        println!("{}", mensaje!("structs.vuela", self.nombre, kilometros_por_hora));
     }
 }
 
+use crate::lecciones::{Entrada, Leccion};
+use crate::mensajes::mensaje;
+use crate::operaciones::unidades::{UnidadVelocidad, Velocidad};
+
+pub const LECCION: Leccion = Leccion {
+    nombre: "structs",
+    titulo: "Estructuras",
+    orden: 5,
+    entrada: Entrada::Simple(main),
+    ejercicios: &[],
+    preguntas: "",
+    fuente: include_str!("structs.rs"),
+};
+
 pub fn main() {
     // Una forma de inicializarlo es:
     let _usuario1 = Usuario {
@@ -72,8 +90,8 @@ pub fn main() {
     // Podemos acceder a estos miembros usando el .
     if usuario1.active {
         println!(
-            "Hola soy {} y tengo {} años",
-            usuario1.username, usuario1.age
+            "{}",
+            mensaje!("structs.presentacion", usuario1.username, usuario1.age)
         );
         usuario1.active = false;
     }
@@ -89,8 +107,8 @@ pub fn main() {
     //Estructura sin miembro con nombres
     let color_negro = RgbColor(0, 0, 0);
     println!(
-        "Color rgb: r: {}, g: {}, b: {}",
-        color_negro.0, color_negro.1, color_negro.2
+        "{}",
+        mensaje!("structs.color_rgb", color_negro.0, color_negro.1, color_negro.2)
     );
 
     /* Creamos un nuevo animal volador usando el constructor
@@ -100,7 +118,7 @@ pub fn main() {
     let pajaro = AnimalVolador::new(String::from("Murcielago"), 1);
 
     //llamamos a su método
-    pajaro.volar(12.0);
+    pajaro.volar(Velocidad::kilometros_por_hora(12.0));
 }
 
 //forma cutre que se puede refactorizar, incluso rust-analyzer lo marca
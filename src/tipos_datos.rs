@@ -1,3 +1,16 @@
+use crate::lecciones::{Entrada, Leccion};
+use crate::mensajes::mensaje;
+
+pub const LECCION: Leccion = Leccion {
+    nombre: "tipos_datos",
+    titulo: "Tipos de datos",
+    orden: 1,
+    entrada: Entrada::Simple(main),
+    ejercicios: &[],
+    preguntas: "",
+    fuente: include_str!("tipos_datos.rs"),
+};
+
 pub fn main() {
     const _CONSTANTE: u8 = 3;
     // Por defecto las variables son inmutables
@@ -59,27 +72,27 @@ pub fn main() {
 
     //String: Es una cadena en el heap, puede variar en tamaño
     let mut _string = String::new(); //Una nueva cadena en el heap, vacía
-    println!("Cadena: {_string}");
+    println!("{}", mensaje!("tipos_datos.cadena", _string));
 
     // Para agregar caracteres uno por uno con el método push
     _string.push('H');
-    println!("push: {_string}");
+    println!("{}", mensaje!("tipos_datos.push", _string));
 
     // O agregamos cadenas
     _string.push_str("ola mundo");
-    println!("Push str: {_string}");
+    println!("{}", mensaje!("tipos_datos.push_str", _string));
 
     // Podemos tmb eliminar caracteres con
     _string.pop();
-    println!("eliminar caracteres con pop: {_string}");
+    println!("{}", mensaje!("tipos_datos.pop", _string));
 
     //Crear una cadena e inicializarla
     let mut _string = String::from("Adiós mundo");
-    println!("Crear una cadena e inicializarla: {_string}");
+    println!("{}", mensaje!("tipos_datos.inicializar", _string));
 
     //Cadena String de un str
     let mut _string = _str.to_string();
-    println!("Cadena String de un str: {_string}");
+    println!("{}", mensaje!("tipos_datos.desde_str", _string));
 
     // Tambien podemos hacer un str de un String
     let _str = _string;
@@ -91,6 +104,8 @@ pub fn main() {
      * un tamaño definido, una vez se establece no puede
      * modificarse
      */
+    // 3.14 es sólo un flotante de ejemplo, no se busca PI
+    #[allow(clippy::approx_constant)]
     let tupla: (i32, f32, &str) = (500, 3.14, "Hola");
     println!("{} {} {}", tupla.0, tupla.1, tupla.2);
 
@@ -11,24 +11,31 @@
 
 use std::ops::Add; 
 
+// Masa y Longitud guardan el número junto con su unidad (kilos, metros...)
+use crate::operaciones::unidades::{Longitud, Masa, UnidadLongitud, UnidadMasa};
+
 trait Ave {
     fn get_nombre(&self) -> String;
     fn get_nombre_cientifico(&self) -> String;
-    fn get_peso(&self) -> f32;
-    fn get_tamanio_alas(&self) -> f32;
+    fn get_peso(&self) -> Masa;
+    fn get_tamanio_alas(&self) -> Longitud;
 
     // Podemos definir funcionalidad básica para
-    // no necesitar implementacion(?)
+    // no necesitar implementacion(?). main no la llama
+    #[allow(dead_code)]
     fn hacer_sonido(&self){
-        println!("{} Hace sonido", self.get_nombre());
+        println!("{}", mensaje!("traits.hace_sonido", self.get_nombre()));
     }
 }
 
+// Volador y Nadador son sólo la firma de ejemplo, ningún animal los implementa
+#[allow(dead_code)]
 trait Volador{
     fn volar(&self);
     fn aterrizar(&self);
 }
 
+#[allow(dead_code)]
 trait Nadador{
     fn nadar(&self);
     fn secarse(&self);
@@ -49,12 +56,12 @@ trait Corredor{
 struct Pinguino {
     nombre: String,
     nombre_cientifico: String,
-    peso: f32,
-    tamanio_alas: f32,
+    peso: Masa,
+    tamanio_alas: Longitud,
 }
 
 impl Pinguino {
-    fn new(peso: f32, tamanio_alas: f32) -> Self{
+    fn new(peso: Masa, tamanio_alas: Longitud) -> Self{
         Self{
             nombre: String::from("Pingüino"),
             nombre_cientifico: String::from("Spheniscidae"),
@@ -68,13 +75,13 @@ impl Ave for Pinguino{
     // Podemos brindar implementacion a pesar de
     // estar definido ya en el trait
     fn hacer_sonido(&self) {
-        println!("Sonido Pinguino");
+        println!("{}", mensaje!("traits.sonido_pinguino"));
     }
 
-    fn get_tamanio_alas(&self) -> f32 {
+    fn get_tamanio_alas(&self) -> Longitud {
         self.tamanio_alas
     }
-    fn get_peso(&self) -> f32 {
+    fn get_peso(&self) -> Masa {
         self.peso
     }
     fn get_nombre_cientifico(&self) -> String {
@@ -87,23 +94,23 @@ impl Ave for Pinguino{
 
 impl Nadador for Pinguino{
     fn secarse(&self) {
-        println!("{} se está secando", self.nombre);
+        println!("{}", mensaje!("traits.secandose", self.nombre));
     }
 
     fn nadar(&self) {
-        println!("{} está nadando", self.nombre);
+        println!("{}", mensaje!("traits.nadando", self.nombre));
     }
 }
 
 struct Avestruz{
     nombre: String,
     nombre_cientifico: String,
-    peso: f32,
-    tamanio_alas: f32,
+    peso: Masa,
+    tamanio_alas: Longitud,
 }
 
 impl Avestruz{
-    fn new(peso: f32, tamanio_alas: f32) -> Self{
+    fn new(peso: Masa, tamanio_alas: Longitud) -> Self{
         Self{
             nombre: String::from("Avestruz"),
             nombre_cientifico: String::from("Struthio camelus"),
@@ -122,26 +129,34 @@ impl Ave for Avestruz{
         self.nombre_cientifico.clone()
     }
 
-    fn get_peso(&self) -> f32 {
+    fn get_peso(&self) -> Masa {
         self.peso
     }
 
-    fn get_tamanio_alas(&self) -> f32 {
+    fn get_tamanio_alas(&self) -> Longitud {
         self.tamanio_alas
     }
 }
 
 impl Corredor for Avestruz{
     fn correr_rapidamente(&self) {
-        println!("{} corre rápidamente", self.nombre);
+        println!("{}", mensaje!("traits.corre", self.nombre));
     }
 }
 
 // impl indica que admite "cualquier implementacion de ave"
 fn mostrar_datos(ave: &impl Ave){
-    println!("Nombre: {}", ave.get_nombre());
-    println!("Nombre cientifico: {}", ave.get_nombre_cientifico());
-    println!("Peso: {}, Tamaño alas: {}", ave.get_peso(), ave.get_tamanio_alas());
+    println!("{}", mensaje!("traits.nombre", ave.get_nombre()));
+    println!("{}", mensaje!("traits.nombre_cientifico", ave.get_nombre_cientifico()));
+    // Se muestran en kilos y metros, aunque se hayan dado en libras o pies
+    println!(
+        "{}",
+        mensaje!(
+            "traits.peso_y_alas",
+            ave.get_peso().en(UnidadMasa::Kilogramo),
+            ave.get_tamanio_alas().en(UnidadLongitud::Metro)
+        )
+    );
 }
 
 // Impl es una avrebiacion de lo siguiente:
@@ -168,7 +183,7 @@ fn _some_function<T, U, V>(_t: &T, _u: &U, _v: &V) -> i32
 
 // Podemos retornar una impl de ave
 fn _func1() -> impl Ave {
-    Pinguino::new(12.3, 4.0)
+    Pinguino::new(Masa::kilogramos(12.3), Longitud::metros(4.0))
 }
 
 /* No es válido retornar dos impl diferentes de ave, o al menos
@@ -189,11 +204,11 @@ fn _func1() -> impl Ave {
 
 fn _func(switch: bool) -> Box<dyn Ave> {
     if switch {
-        Box::new(Pinguino::new(12.3, 2.0))
+        Box::new(Pinguino::new(Masa::kilogramos(12.3), Longitud::metros(2.0)))
             
     }
     else {
-        Box::new(Avestruz::new(12.3, 2.0))
+        Box::new(Avestruz::new(Masa::kilogramos(12.3), Longitud::metros(2.0)))
     }
 }
 
@@ -211,16 +226,29 @@ impl <T: Add<Output = T> + Copy> Dato<T> {
 
 impl <T: std::fmt::Display> Dato<T> {
     fn print(&self){
-        println!("Dato: {}", self.dato);
+        println!("{}", mensaje!("traits.dato", self.dato));
     }
 }
 
-pub fn _main(){
-    let cody_maverick = Pinguino::new(12.3, 0.30);
+use crate::lecciones::{Entrada, Leccion};
+use crate::mensajes::mensaje;
+
+pub const LECCION: Leccion = Leccion {
+    nombre: "traits",
+    titulo: "Traits",
+    orden: 14,
+    entrada: Entrada::Simple(main),
+    ejercicios: &[],
+    preguntas: "",
+    fuente: include_str!("traits.rs"),
+};
+
+pub fn main(){
+    let cody_maverick = Pinguino::new(Masa::kilogramos(12.3), Longitud::metros(0.30));
 
     mostrar_datos(&cody_maverick);
 
-    let avesota = Avestruz::new(40.2, 0.20);
+    let avesota = Avestruz::new(Masa::kilogramos(40.2), Longitud::metros(0.20));
 
     mostrar_datos(&avesota);
 
@@ -0,0 +1,101 @@
+// La calculadora respeta la precedencia, guarda variables con let y
+// señala la columna de los errores en vez de hacer panic
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+fn calc(args: &[&str], entrada: &str) -> Output {
+    let mut hijo = Command::new(env!("CARGO_BIN_EXE_rust"))
+        .arg("calc")
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    hijo.stdin
+        .take()
+        .unwrap()
+        .write_all(entrada.as_bytes())
+        .unwrap();
+    hijo.wait_with_output().unwrap()
+}
+
+fn calcular(expresion: &str) -> String {
+    let salida = calc(&[expresion], "");
+    assert!(
+        salida.status.success(),
+        "{}",
+        String::from_utf8_lossy(&salida.stderr)
+    );
+    String::from_utf8(salida.stdout).unwrap().trim().to_string()
+}
+
+#[test]
+fn respeta_la_precedencia() {
+    assert_eq!(calcular("1 + 2 * 3"), "7");
+    assert_eq!(calcular("(1 + 2) * 3"), "9");
+    assert_eq!(calcular("-2 ^ 2"), "-4");
+    assert_eq!(calcular("2 ^ 3 ^ 2"), "512");
+    assert_eq!(calcular("10 - 4 - 3"), "3");
+    assert_eq!(calcular("7 % 4 + max(1, 5, 3) - sqrt(16) + abs(-2)"), "6");
+}
+
+#[test]
+fn los_errores_dicen_la_columna() {
+    let salida = calc(&["1 + (2 * x)"], "");
+    assert!(!salida.status.success());
+    let stderr = String::from_utf8(salida.stderr).unwrap();
+    assert!(stderr.contains("\n         ^\n"), "{stderr}");
+    assert!(
+        stderr.contains("columna 10: la variable \"x\" no existe"),
+        "{stderr}"
+    );
+
+    let stderr = String::from_utf8(calc(&["2 * (3 +"], "").stderr).unwrap();
+    assert!(stderr.contains("columna 9"), "{stderr}");
+    let stderr = String::from_utf8(calc(&["1 / 0"], "").stderr).unwrap();
+    assert!(
+        stderr.contains("columna 3: división entre cero"),
+        "{stderr}"
+    );
+}
+
+#[test]
+fn el_modo_interactivo_guarda_variables() {
+    let salida = calc(&[], "let x = 3\nlet y = x * 2\ny ^ 2\nz\nsalir\n");
+    let stdout = String::from_utf8(salida.stdout).unwrap();
+    assert!(salida.status.success());
+    assert!(
+        stdout.contains("x = 3\n") && stdout.contains("y = 6\n"),
+        "{stdout}"
+    );
+    assert!(stdout.contains("36\n"), "{stdout}");
+    assert!(
+        stdout.contains("columna 1: la variable \"z\" no existe"),
+        "{stdout}"
+    );
+}
+
+#[test]
+fn demasiados_niveles_son_un_error_y_no_un_desborde() {
+    let parentesis = format!("{}1{}", "(".repeat(10_000), ")".repeat(10_000));
+    let menos = format!("{}1", "-".repeat(10_000));
+    let suma = vec!["1"; 10_000].join("+");
+    for expresion in [parentesis, menos, suma] {
+        let salida = calc(&[&expresion], "");
+        assert_eq!(salida.status.code(), Some(1));
+        let stderr = String::from_utf8(salida.stderr).unwrap();
+        assert!(stderr.contains("más de 256 niveles"), "{stderr}");
+    }
+
+    let stderr = String::from_utf8(calc(&[&"(".repeat(300)], "").stderr).unwrap();
+    assert!(
+        stderr.contains("columna 257: la expresión tiene más de 256 niveles"),
+        "{stderr}"
+    );
+    assert_eq!(
+        calcular(&format!("{}1{}", "(".repeat(200), ")".repeat(200))),
+        "1"
+    );
+    assert_eq!(calcular(&vec!["1"; 200].join(" + ")), "200");
+}
@@ -0,0 +1,25 @@
+// Con la misma semilla la máquina que ordena monedas da el mismo reporte
+use std::process::{Command, Output};
+
+fn clasificar(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_rust"))
+        .arg("clasificar")
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn la_misma_semilla_da_el_mismo_reporte() {
+    let primera = clasificar(&["--semilla", "18", "--monedas", "500"]);
+    let segunda = clasificar(&["--monedas", "500", "--semilla", "18"]);
+    assert!(primera.status.success());
+    assert_eq!(primera.stdout, segunda.stdout);
+
+    let reporte = String::from_utf8(primera.stdout).unwrap();
+    assert!(reporte.starts_with("Clasificando 500 monedas (semilla 18)"));
+    assert!(reporte.contains("Contenedor  Monedas  Falsas        Pesos\n"), "{reporte}");
+    assert!(reporte.contains("Bien clasificadas: "), "{reporte}");
+
+    assert_eq!(clasificar(&["--monedas", "muchas"]).status.code(), Some(2));
+}
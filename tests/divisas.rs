@@ -0,0 +1,55 @@
+// convertir usa solo las tasas del archivo, triangula cuando no hay tasa
+// directa y avisa de tasas faltantes o con arbitraje
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output};
+
+fn convertir(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_rust"))
+        .arg("convertir")
+        .args(args)
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .output()
+        .unwrap()
+}
+
+fn texto(bytes: &[u8]) -> String {
+    String::from_utf8_lossy(bytes).to_string()
+}
+
+#[test]
+fn convierte_con_las_tasas_de_ejemplo() {
+    let salida = convertir(&["1,000", "EUR", "JPY"]);
+    assert!(salida.status.success(), "{}", texto(&salida.stderr));
+    assert_eq!(
+        texto(&salida.stdout),
+        "1.000,00 € = 161,460 JPY\nRuta: EUR -> USD -> JPY, tasa 161.46\n"
+    );
+
+    let salida = convertir(&["--revisar"]);
+    assert!(salida.status.success(), "{}", texto(&salida.stdout));
+}
+
+#[test]
+fn avisa_de_tasas_faltantes_y_arbitraje() {
+    let archivo = Path::new(env!("CARGO_TARGET_TMPDIR")).join("tasas_raras.toml");
+    fs::write(
+        &archivo,
+        "[USD]\nMXN = 17.25\n[EUR]\nUSD = 1.08\nMXN = 19.5\n[GBP]\nCHF = 1.12\n",
+    )
+    .unwrap();
+    let tasas = archivo.to_str().unwrap();
+
+    let salida = convertir(&["--revisar", "--tasas", tasas]);
+    let revision = texto(&salida.stdout);
+    assert!(!salida.status.success());
+    assert!(revision.contains("- CHF, GBP\n"), "{revision}");
+    assert!(revision.contains("gana 4.67% en cada vuelta"), "{revision}");
+
+    let salida = convertir(&["10", "GBP", "MXN", "--tasas", tasas]);
+    assert!(!salida.status.success());
+    assert!(texto(&salida.stderr).contains("no hay tasas para ir de GBP a MXN"));
+
+    let salida = convertir(&["diez", "USD", "MXN"]);
+    assert_eq!(salida.status.code(), Some(2));
+}
@@ -0,0 +1,31 @@
+// Los ejercicios sin resolver se reportan como pendientes, no como un
+// panic que atraviesa el aislamiento
+use std::path::Path;
+use std::process::Command;
+
+#[test]
+fn check_cuenta_los_ejercicios_sin_resolver() {
+    let salida = Command::new(env!("CARGO_BIN_EXE_rust"))
+        .args(["check", "option"])
+        // Para no tocar el progreso real de quien corre las pruebas
+        .env(
+            "RUST_NOTAS_PROGRESO",
+            Path::new(env!("CARGO_TARGET_TMPDIR")).join("progreso_check.txt"),
+        )
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(salida.stdout).unwrap();
+    let stderr = String::from_utf8(salida.stderr).unwrap();
+
+    assert_eq!(salida.status.code(), Some(1));
+    assert!(
+        stdout.contains("  [sin resolver] sumar_opcion(None, 3)\n"),
+        "{stdout}"
+    );
+    assert!(
+        stdout.contains("0 de 7 casos correctos, 7 sin resolver\n"),
+        "{stdout}"
+    );
+    assert!(!stdout.contains("panic"), "{stdout}");
+    assert!(!stderr.contains("panicked"), "{stderr}");
+}
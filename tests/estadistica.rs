@@ -0,0 +1,74 @@
+// estadistica lee un número por línea y NaN es un error a menos que se
+// pida ignorarlo
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+fn archivo(nombre: &str, contenido: &str) -> PathBuf {
+    let ruta = Path::new(env!("CARGO_TARGET_TMPDIR")).join(nombre);
+    fs::write(&ruta, contenido).unwrap();
+    ruta
+}
+
+fn estadistica(ruta: &Path, opciones: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_rust"))
+        .arg("estadistica")
+        .arg(ruta)
+        .args(opciones)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn resume_los_datos_del_archivo() {
+    let ruta = archivo("numeros.txt", "3\n1\n4\n1\n5\n\n9\n2\n6\n5\n");
+    let salida = estadistica(&ruta, &["--histograma", "4"]);
+    let texto = String::from_utf8(salida.stdout).unwrap();
+    assert!(salida.status.success());
+    for linea in ["Datos: 9", "Media: 4.0000", "Mediana: 4.0000", "Moda: 1, 5"] {
+        assert!(texto.contains(linea), "{texto}");
+    }
+    assert!(texto.contains("[      7.00,       9.00)      1 #"), "{texto}");
+
+    let salida = estadistica(&ruta, &["--streaming"]);
+    let texto = String::from_utf8(salida.stdout).unwrap();
+    assert!(texto.contains("Media: 4.0000") && !texto.contains("Mediana"), "{texto}");
+}
+
+#[test]
+fn varianza_muestral_y_de_la_poblacion() {
+    let ruta = archivo("varianza.txt", "3\n1\n4\n1\n5\n9\n2\n6\n5\n");
+    // Con los datos en memoria y sin ellos tiene que dar lo mismo
+    for streaming in [&[][..], &["--streaming"]] {
+        let salida = estadistica(&ruta, streaming);
+        let texto = String::from_utf8(salida.stdout).unwrap();
+        assert!(texto.contains("Varianza: 6.7500\nDesviación estándar: 2.5981\n"), "{texto}");
+
+        let salida = estadistica(&ruta, &[streaming, &["--poblacion"]].concat());
+        let texto = String::from_utf8(salida.stdout).unwrap();
+        assert!(texto.contains("Varianza: 6.0000\nDesviación estándar: 2.4495\n"), "{texto}");
+    }
+    let texto = String::from_utf8(estadistica(&ruta, &[]).stdout).unwrap();
+    assert!(texto.contains("Suma: 36\n"), "{texto}");
+
+    // Con un solo dato no hay varianza muestral
+    let ruta = archivo("un_dato.txt", "7\n");
+    let texto = String::from_utf8(estadistica(&ruta, &[]).stdout).unwrap();
+    assert!(!texto.contains("Varianza"), "{texto}");
+}
+
+#[test]
+fn nan_y_lineas_invalidas() {
+    let ruta = archivo("con_nan.txt", "1\nNaN\n3\n");
+    let salida = estadistica(&ruta, &[]);
+    assert!(!salida.status.success());
+    assert!(String::from_utf8_lossy(&salida.stderr).contains("Línea 2: es NaN"));
+
+    let salida = estadistica(&ruta, &["--nan", "ignorar"]);
+    let texto = String::from_utf8(salida.stdout).unwrap();
+    assert!(texto.contains("NaN ignorados: 1\nMedia: 2.0000"), "{texto}");
+
+    let ruta = archivo("con_texto.txt", "1\ndos\n");
+    let salida = estadistica(&ruta, &[]);
+    assert!(String::from_utf8_lossy(&salida.stderr).contains("Línea 2: \"dos\" no es un número"));
+}
@@ -0,0 +1,34 @@
+// La sesión de la máquina expendedora vende, da cambio y no deja hacer
+// acciones que no corresponden al estado en que está
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+fn sesion(entrada: &str) -> String {
+    let mut hijo = Command::new(env!("CARGO_BIN_EXE_rust"))
+        .arg("expendedora")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    hijo.stdin
+        .take()
+        .unwrap()
+        .write_all(entrada.as_bytes())
+        .unwrap();
+    let salida = hijo.wait_with_output().unwrap();
+    assert!(salida.status.success());
+    String::from_utf8(salida.stdout).unwrap()
+}
+
+#[test]
+fn compra_con_cambio_y_registro() {
+    let salida = sesion("plata\nplata\nelegir b1\ntomar\ntomar\ntomar\nregistro\nsalir\n");
+    assert!(salida.contains("Crédito: $20.00"), "{salida}");
+    assert!(salida.contains("Tomaste: Chocolate"), "{salida}");
+    assert!(salida.contains("Tomaste $2.00 (cobre, cobre)"), "{salida}");
+    assert!(
+        salida.contains("Error: no se puede tomar el cambio cuando la máquina está inactiva"),
+        "{salida}"
+    );
+    assert!(salida.contains("  3. venta de B1 por $18.00, cambio $2.00"), "{salida}");
+}
@@ -0,0 +1,61 @@
+// El libro debe tener una página por lección, enlazadas en orden desde
+// el índice, y sin el código que sólo registra la lección
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+fn exportar(carpeta: &Path, opciones: &[&str]) {
+    let _ = fs::remove_dir_all(carpeta);
+    let salida = Command::new(env!("CARGO_BIN_EXE_rust"))
+        .arg("export")
+        .args(opciones)
+        .arg("--salida")
+        .arg(carpeta)
+        .output()
+        .unwrap();
+    assert!(
+        salida.status.success(),
+        "{}",
+        String::from_utf8_lossy(&salida.stderr)
+    );
+}
+
+#[test]
+fn el_libro_html_tiene_todas_las_lecciones() {
+    let carpeta = Path::new(env!("CARGO_TARGET_TMPDIR")).join("libro_html");
+    exportar(&carpeta, &[]);
+
+    let indice = fs::read_to_string(carpeta.join("index.html")).unwrap();
+    let primera = indice.find("tipos_datos.html").unwrap();
+    let ultima = indice.find("traits.html").unwrap();
+    assert!(
+        primera < ultima,
+        "las lecciones no están en orden:\n{indice}"
+    );
+
+    let pagina = fs::read_to_string(carpeta.join("ownership.html")).unwrap();
+    assert!(pagina.contains("<p>El ownership es una caracteristica"));
+    assert!(pagina.contains("<span class=\"kw\">fn</span>"));
+    assert!(pagina.contains("href=\"structs.html\""));
+    assert!(!pagina.contains("LECCION"));
+}
+
+#[test]
+fn el_libro_markdown_tiene_bloques_de_codigo() {
+    let carpeta = Path::new(env!("CARGO_TARGET_TMPDIR")).join("libro_md");
+    exportar(&carpeta, &["--markdown"]);
+
+    let indice = fs::read_to_string(carpeta.join("README.md")).unwrap();
+    assert!(
+        indice.contains("1. [Tipos de datos](tipos_datos.md)"),
+        "{indice}"
+    );
+    let pagina = fs::read_to_string(carpeta.join("traits.md")).unwrap();
+    assert!(pagina.contains("```rust\n"));
+    assert!(!pagina.contains("#![allow"));
+
+    // El comentario de los ejercicios se va con ellos
+    let pagina = fs::read_to_string(carpeta.join("option.md")).unwrap();
+    assert!(!pagina.contains("EJERCICIOS"));
+    assert!(!pagina.contains("Practicar cómo sacar el T"), "{pagina}");
+}
@@ -0,0 +1,51 @@
+// Cada guion en entradas/ debe poder llevar su lección de principio a
+// fin sin nadie en la terminal
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+#[test]
+fn las_lecciones_interactivas_corren_con_su_guion() {
+    let carpeta = Path::new(env!("CARGO_MANIFEST_DIR")).join("entradas");
+    let mut guiones: Vec<_> = fs::read_dir(&carpeta)
+        .expect("falta la carpeta entradas/")
+        .map(|archivo| archivo.unwrap().path())
+        .filter(|ruta| ruta.extension().is_some_and(|ext| ext == "txt"))
+        .collect();
+    guiones.sort();
+    assert!(!guiones.is_empty(), "no hay guiones en {}", carpeta.display());
+
+    for guion in guiones {
+        let leccion = guion.file_stem().unwrap().to_str().unwrap();
+        let salida = Command::new(env!("CARGO_BIN_EXE_rust"))
+            .args(["run", leccion, "--entrada"])
+            .arg(&guion)
+            // Para no tocar el progreso real de quien corre las pruebas
+            .env("RUST_NOTAS_PROGRESO", Path::new(env!("CARGO_TARGET_TMPDIR")).join("progreso.txt"))
+            .stdin(Stdio::null())
+            .output()
+            .unwrap();
+        assert!(
+            salida.status.success(),
+            "la lección {leccion} falló con su guion:\n{}",
+            String::from_utf8_lossy(&salida.stderr)
+        );
+    }
+}
+
+#[test]
+fn run_all_encuentra_los_guiones_desde_otra_carpeta() {
+    let salida = Command::new(env!("CARGO_BIN_EXE_rust"))
+        .args(["run", "--all"])
+        .current_dir(env!("CARGO_TARGET_TMPDIR"))
+        .env("RUST_NOTAS_PROGRESO", Path::new(env!("CARGO_TARGET_TMPDIR")).join("progreso.txt"))
+        .stdin(Stdio::null())
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&salida.stdout);
+    let resumen = stdout
+        .lines()
+        .find(|linea| linea.starts_with("leer_de_consola "))
+        .expect("falta leer_de_consola en el resumen");
+    assert!(resumen.contains(" ok"), "{resumen}");
+}
@@ -0,0 +1,34 @@
+// matrices compara Vec<Vec<f64>> con Matriz, los tiempos cambian pero los
+// resultados deben coincidir
+use std::process::{Command, Output};
+
+fn matrices(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_rust"))
+        .arg("matrices")
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn las_cuatro_formas_dan_lo_mismo() {
+    let salida = matrices(&["--tamanio", "70", "--semilla", "23"]);
+    let texto = String::from_utf8(salida.stdout).unwrap();
+    assert!(salida.status.success());
+    assert!(texto.starts_with("Multiplicando matrices de 70 x 70 (semilla 23)"));
+    for nombre in [
+        "Vec<Vec<f64>>",
+        "Vec<Vec<f64>> (i-j-k)",
+        "Matriz directa",
+        "Matriz por bloques",
+    ] {
+        assert!(texto.contains(nombre), "{texto}");
+    }
+    // Sumar en otro orden cambia el redondeo, pero muy poco
+    for linea in texto.lines().filter(|linea| linea.contains("diferencia")) {
+        let diferencia: f64 = linea.rsplit(' ').next().unwrap().parse().unwrap();
+        assert!(diferencia < 1e-9, "{linea}");
+    }
+
+    assert_eq!(matrices(&["--tamanio", "0"]).status.code(), Some(2));
+}
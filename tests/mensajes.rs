@@ -0,0 +1,47 @@
+// Cada clave del catálogo debe estar en las dos tablas, y con --idioma
+// las lecciones deben cambiar de idioma
+use std::process::{Command, Output};
+
+fn correr(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_rust"))
+        .args(args)
+        .env_remove("RUST_NOTAS_IDIOMA")
+        .env(
+            "RUST_NOTAS_PROGRESO",
+            std::path::Path::new(env!("CARGO_TARGET_TMPDIR")).join("progreso.txt"),
+        )
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn no_faltan_mensajes_en_ningun_idioma() {
+    let salida = correr(&["mensajes"]);
+    assert!(
+        salida.status.success(),
+        "{}",
+        String::from_utf8_lossy(&salida.stdout)
+    );
+}
+
+#[test]
+fn las_lecciones_se_pueden_ver_en_ingles() {
+    let espanol = String::from_utf8(correr(&["run", "match_cf"]).stdout).unwrap();
+    let ingles = String::from_utf8(correr(&["run", "match_cf", "--idioma", "en"]).stdout).unwrap();
+    assert!(espanol.contains("Tienes un gato Amarillo"), "{espanol}");
+    assert!(ingles.contains("You have a Amarillo cat"), "{ingles}");
+}
+
+#[test]
+fn las_cadenas_de_tipos_datos_tambien_se_traducen() {
+    let espanol = String::from_utf8(correr(&["run", "tipos_datos"]).stdout).unwrap();
+    let ingles = String::from_utf8(correr(&["run", "tipos_datos", "--idioma", "en"]).stdout).unwrap();
+    assert!(espanol.contains("Cadena String de un str: Adio"), "{espanol}");
+    assert!(ingles.contains("String from a str: Adio"), "{ingles}");
+    assert!(!ingles.contains("Cadena"), "{ingles}");
+}
+
+#[test]
+fn un_idioma_desconocido_es_un_error_de_uso() {
+    assert_eq!(correr(&["list", "--idioma", "fr"]).status.code(), Some(2));
+}
@@ -0,0 +1,64 @@
+// Un archivo de progreso dañado o de otra versión debe dar un error
+// claro, nunca un panic
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+fn progress_con(nombre: &str, contenido: &str) -> (bool, String) {
+    let ruta = Path::new(env!("CARGO_TARGET_TMPDIR")).join(nombre);
+    fs::write(&ruta, contenido).unwrap();
+    let salida = Command::new(env!("CARGO_BIN_EXE_rust"))
+        .arg("progress")
+        .env("RUST_NOTAS_PROGRESO", &ruta)
+        .output()
+        .unwrap();
+    (salida.status.success(), String::from_utf8_lossy(&salida.stderr).into_owned())
+}
+
+#[test]
+fn un_archivo_valido_se_lee() {
+    let (exito, stderr) = progress_con("valido.txt", "# rust-notas progreso v1\nana\tleccion\ttraits\n");
+    assert!(exito, "{stderr}");
+}
+
+#[test]
+fn un_archivo_danado_da_un_error_claro() {
+    let (exito, stderr) = progress_con("danado.txt", "\u{0}\u{1}basura");
+    assert!(!exito);
+    assert!(stderr.contains("puede estar dañado"), "{stderr}");
+    assert!(!stderr.contains("panicked"), "{stderr}");
+}
+
+#[test]
+fn una_version_vieja_da_un_error_claro() {
+    let (exito, stderr) = progress_con("version.txt", "# rust-notas progreso v0\n");
+    assert!(!exito);
+    assert!(stderr.contains("versión 0"), "{stderr}");
+}
+
+#[test]
+fn una_linea_invalida_dice_cual_es() {
+    let (exito, stderr) = progress_con("linea.txt", "# rust-notas progreso v1\nana\tleccion\n");
+    assert!(!exito);
+    assert!(stderr.contains("línea 2"), "{stderr}");
+}
+
+#[test]
+fn un_alumno_con_salto_de_linea_no_dana_el_archivo() {
+    let ruta = Path::new(env!("CARGO_TARGET_TMPDIR")).join("alumno_raro.txt");
+    let _ = fs::remove_file(&ruta);
+    let salida = Command::new(env!("CARGO_BIN_EXE_rust"))
+        .args(["run", "tipos_datos"])
+        .env("RUST_NOTAS_PROGRESO", &ruta)
+        .env("ALUMNO", "ana\nbeto\tleccion\tstructs")
+        .output()
+        .unwrap();
+    assert!(salida.status.success());
+    let stderr = String::from_utf8_lossy(&salida.stderr);
+    assert!(stderr.contains("no sirve como nombre de alumno"), "{stderr}");
+
+    let contenido = fs::read_to_string(&ruta).unwrap();
+    assert_eq!(contenido, "# rust-notas progreso v1\nalumno\tleccion\ttipos_datos\n");
+    let (exito, stderr) = progress_con("alumno_raro.txt", &contenido);
+    assert!(exito, "{stderr}");
+}
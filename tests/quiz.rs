@@ -0,0 +1,106 @@
+// Los bancos de preguntas deben poder leerse, el quiz debe terminar
+// contestando siempre la primera opción y el puntaje debe contar bien
+// los aciertos
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Output, Stdio};
+
+fn quiz(leccion: &str, semilla: &str) -> Output {
+    quiz_con_respuestas(leccion, semilla, &"1\n".repeat(100))
+}
+
+fn quiz_con_respuestas(leccion: &str, semilla: &str, respuestas: &str) -> Output {
+    let mut hijo = Command::new(env!("CARGO_BIN_EXE_rust"))
+        .args(["quiz", leccion, "--semilla", semilla])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    hijo.stdin.take().unwrap().write_all(respuestas.as_bytes()).unwrap();
+    hijo.wait_with_output().unwrap()
+}
+
+#[test]
+fn todos_los_bancos_de_preguntas_se_pueden_jugar() {
+    let carpeta = Path::new(env!("CARGO_MANIFEST_DIR")).join("src/preguntas");
+    for banco in fs::read_dir(carpeta).unwrap() {
+        let ruta = banco.unwrap().path();
+        let leccion = ruta.file_stem().unwrap().to_str().unwrap();
+        let salida = quiz(leccion, "1");
+        let stdout = String::from_utf8_lossy(&salida.stdout);
+        assert!(
+            salida.status.success() && stdout.contains("Puntaje:"),
+            "el quiz de {leccion} falló:\n{stdout}{}",
+            String::from_utf8_lossy(&salida.stderr)
+        );
+    }
+}
+
+#[test]
+fn la_misma_semilla_da_el_mismo_quiz() {
+    assert_eq!(quiz("ownership", "42").stdout, quiz("ownership", "42").stdout);
+    assert_ne!(quiz("ownership", "42").stdout, quiz("ownership", "43").stdout);
+}
+
+struct Pregunta {
+    opciones: usize,
+    // Desde 1, como se contesta
+    correcta: usize,
+}
+
+/* Saca de la salida de un quiz contestado todo con 1 cuántas opciones
+ * tuvo cada pregunta y en qué número quedó la correcta
+ */
+fn leer_preguntas(salida: &str) -> Vec<Pregunta> {
+    let mut preguntas = Vec::new();
+    let mut opciones: Vec<&str> = Vec::new();
+    for linea in salida.lines() {
+        if let Some((numero, texto)) = linea.strip_prefix("  ").and_then(|resto| resto.split_once(". ")) {
+            if numero.parse::<usize>().is_ok() {
+                opciones.push(texto);
+                continue;
+            }
+        }
+        let Some(respuesta) = linea.strip_prefix("Opción: ") else {
+            continue;
+        };
+        let correcta = match respuesta.strip_prefix("Incorrecto, la respuesta era: ") {
+            Some(texto) => opciones.iter().position(|&opcion| opcion == texto).unwrap() + 1,
+            None => 1,
+        };
+        preguntas.push(Pregunta { opciones: opciones.len(), correcta });
+        opciones.clear();
+    }
+    preguntas
+}
+
+#[test]
+fn el_puntaje_cuenta_los_aciertos() {
+    let primera = quiz("ownership", "5");
+    let preguntas = leer_preguntas(&String::from_utf8(primera.stdout).unwrap());
+    assert!(preguntas.len() >= 4);
+
+    // Bien las preguntas pares y mal las nones
+    let respuestas: String = preguntas
+        .iter()
+        .enumerate()
+        .map(|(i, pregunta)| match i % 2 {
+            0 => format!("{}\n", pregunta.correcta),
+            _ => format!("{}\n", pregunta.correcta % pregunta.opciones + 1),
+        })
+        .collect();
+    let salida = quiz_con_respuestas("ownership", "5", &respuestas);
+    let stdout = String::from_utf8(salida.stdout).unwrap();
+
+    let bien = preguntas.len().div_ceil(2);
+    let mal = preguntas.len() / 2;
+    assert!(salida.status.success(), "{stdout}");
+    assert_eq!(stdout.matches("¡Correcto!").count(), bien, "{stdout}");
+    assert_eq!(stdout.matches("Incorrecto, la respuesta era: ").count(), mal, "{stdout}");
+    assert!(
+        stdout.contains(&format!("Puntaje: {bien} de {}", preguntas.len())),
+        "{stdout}"
+    );
+}
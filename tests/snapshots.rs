@@ -0,0 +1,19 @@
+// Compara la salida de cada lección con tests/snapshots/*.snap. Si un
+// cambio en la salida era a propósito: ACEPTAR_SNAPSHOTS=1 cargo test
+use std::process::Command;
+
+#[test]
+fn la_salida_de_las_lecciones_no_cambia() {
+    let salida = Command::new(env!("CARGO_BIN_EXE_rust"))
+        .arg("snapshot")
+        // Desde otra carpeta: los .snap se buscan junto al Cargo.toml
+        .current_dir(env!("CARGO_TARGET_TMPDIR"))
+        .output()
+        .unwrap();
+    assert!(
+        salida.status.success(),
+        "la salida de alguna lección cambió:\n{}{}",
+        String::from_utf8_lossy(&salida.stdout),
+        String::from_utf8_lossy(&salida.stderr)
+    );
+}